@@ -1,6 +1,15 @@
-//! Approximate the area of a path.
+//! Compute the area, centroid and second moments of area of a path.
+//!
+//! The `approximate_*` functions work on a flattened approximation of the path while
+//! [`signed_area`], [`centroid`] and [`area_moments`] integrate the curves directly
+//! using Green's theorem, which is exact for line segments and bézier curves.
+//!
+//! Sub-paths are implicitly closed. Each sub-path contributes with the sign of
+//! its area, so holes with the opposite winding order are subtracted.
 
-use crate::geom::vector;
+use crate::geom::traits::Segment;
+use crate::geom::{vector, CubicBezierSegment, LineSegment, QuadraticBezierSegment};
+use crate::math::{Point, Vector};
 use crate::path::{iterator::PathIterator, PathEvent};
 
 /// Compute the signed area of a path by summing the signed areas of its sub-paths.
@@ -70,6 +79,186 @@ impl<Iter: Iterator<Item = PathEvent>> Iterator for SignedAreas<Iter> {
     }
 }
 
+/// The area, centroid and second moments of area of a shape.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AreaMoments {
+    /// The signed area.
+    pub area: f64,
+    /// The center of mass, assuming uniform density.
+    pub centroid: Point,
+    /// Second moment of area about the horizontal axis going through the centroid,
+    /// `∫∫ (y - centroid.y)² dA`.
+    pub ixx: f64,
+    /// Second moment of area about the vertical axis going through the centroid,
+    /// `∫∫ (x - centroid.x)² dA`.
+    pub iyy: f64,
+    /// Product of area about the centroid, `∫∫ (x - centroid.x) * (y - centroid.y) dA`.
+    pub ixy: f64,
+}
+
+impl AreaMoments {
+    /// The polar moment of area about the centroid (`ixx + iyy`).
+    ///
+    /// Multiplied by the density, this is the moment of inertia around the
+    /// axis perpendicular to the plane.
+    #[inline]
+    pub fn polar_moment(&self) -> f64 {
+        self.ixx + self.iyy
+    }
+}
+
+/// Compute the exact signed area of a path, taking curves into account.
+///
+/// The sign follows the same convention as [`compute_winding`](crate::winding::compute_winding).
+pub fn signed_area<Iter>(path: Iter) -> f64
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut integrals = Integrals::default();
+    integrals.add_path(path);
+
+    integrals.area
+}
+
+/// Compute the centroid of a path, taking curves into account.
+///
+/// Returns `None` if the path has a null area.
+pub fn centroid<Iter>(path: Iter) -> Option<Point>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let moments = area_moments(path);
+    if moments.area == 0.0 {
+        return None;
+    }
+
+    Some(moments.centroid)
+}
+
+/// Compute the area, centroid and second moments of area of a path, taking curves
+/// into account.
+///
+/// If the path has a null area, the centroid is the origin and the second moments
+/// are relative to it.
+pub fn area_moments<Iter>(path: Iter) -> AreaMoments
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut integrals = Integrals::default();
+    integrals.add_path(path);
+
+    let origin = integrals.origin.unwrap_or_else(Point::zero);
+    let area = integrals.area;
+    if area == 0.0 {
+        return AreaMoments {
+            area,
+            centroid: origin,
+            ixx: integrals.ixx,
+            iyy: integrals.iyy,
+            ixy: integrals.ixy,
+        };
+    }
+
+    // The integrals are computed relative to the first point of the path, move
+    // them to the centroid using the parallel axis theorem.
+    let c = vector(integrals.sx, integrals.sy) / area;
+
+    AreaMoments {
+        area,
+        centroid: origin + c,
+        ixx: integrals.ixx - area * c.y * c.y,
+        iyy: integrals.iyy - area * c.x * c.x,
+        ixy: integrals.ixy - area * c.x * c.y,
+    }
+}
+
+// Abscissas and weights of the 6 points Gauss-Legendre quadrature over [0, 1].
+//
+// It is exact for polynomials up to degree 11 which is the degree of the highest
+// integrand (second moments of a cubic bézier curve).
+const GAUSS_LEGENDRE_6: [(f64, f64); 6] = [
+    (0.033765242898423975, 0.08566224618958518),
+    (0.16939530676686776, 0.1803807865240693),
+    (0.38069040695840156, 0.23395696728634552),
+    (0.6193095930415985, 0.23395696728634552),
+    (0.8306046932331322, 0.1803807865240693),
+    (0.966234757101576, 0.08566224618958518),
+];
+
+/// Boundary integrals over the path, relative to `origin`.
+#[derive(Default)]
+struct Integrals {
+    origin: Option<Point>,
+    area: f64,
+    sx: f64,
+    sy: f64,
+    ixx: f64,
+    iyy: f64,
+    ixy: f64,
+}
+
+impl Integrals {
+    fn add_path<Iter>(&mut self, path: Iter)
+    where
+        Iter: IntoIterator<Item = PathEvent>,
+    {
+        for evt in path {
+            match evt {
+                PathEvent::Begin { at } => {
+                    if self.origin.is_none() {
+                        self.origin = Some(at);
+                    }
+                }
+                PathEvent::Line { from, to } => {
+                    self.add_segment(&LineSegment { from, to });
+                }
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    self.add_segment(&QuadraticBezierSegment { from, ctrl, to });
+                }
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    self.add_segment(&CubicBezierSegment {
+                        from,
+                        ctrl1,
+                        ctrl2,
+                        to,
+                    });
+                }
+                PathEvent::End { last, first, .. } => {
+                    if last != first {
+                        self.add_segment(&LineSegment {
+                            from: last,
+                            to: first,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_segment<S: Segment<Scalar = f64>>(&mut self, segment: &S) {
+        let origin = self.origin.unwrap_or_else(|| segment.from());
+        self.origin = Some(origin);
+
+        for &(t, weight) in &GAUSS_LEGENDRE_6 {
+            let p = segment.sample(t) - origin;
+            let d: Vector = segment.derivative(t);
+            let (x, y) = (p.x, p.y);
+
+            self.area += weight * 0.5 * (x * d.y - y * d.x);
+            self.sx += weight * 0.5 * x * x * d.y;
+            self.sy -= weight * 0.5 * y * y * d.x;
+            self.ixx -= weight * y * y * y * d.x / 3.0;
+            self.iyy += weight * x * x * x * d.y / 3.0;
+            self.ixy += weight * 0.5 * x * x * y * d.y;
+        }
+    }
+}
+
 #[test]
 fn sub_path_signed_area() {
     use crate::geom::point;
@@ -116,3 +305,92 @@ fn sub_path_signed_area() {
 
     assert_eq!(approximate_signed_area(0.01, path.build().iter()), 5.0);
 }
+
+#[cfg(test)]
+fn assert_approx_eq(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn rectangle_moments() {
+    use crate::geom::point;
+    let mut path = crate::path::Path::builder();
+
+    path.begin(point(1.0, 1.0));
+    path.line_to(point(5.0, 1.0));
+    path.line_to(point(5.0, 3.0));
+    path.line_to(point(1.0, 3.0));
+    path.close();
+
+    let path = path.build();
+
+    let moments = area_moments(&path);
+    assert_approx_eq(moments.area, 8.0);
+    assert_approx_eq(moments.centroid.x, 3.0);
+    assert_approx_eq(moments.centroid.y, 2.0);
+    // b * h³ / 12
+    assert_approx_eq(moments.ixx, 4.0 * 8.0 / 12.0);
+    assert_approx_eq(moments.iyy, 2.0 * 64.0 / 12.0);
+    assert_approx_eq(moments.ixy, 0.0);
+
+    assert_approx_eq(signed_area(path.reversed()), -8.0);
+    assert_eq!(centroid(&path), Some(moments.centroid));
+}
+
+#[test]
+fn circle_moments() {
+    use crate::geom::{point, Angle, Arc};
+    use core::f64::consts::PI;
+
+    let mut path = crate::path::Path::builder();
+    let arc = Arc {
+        center: point(10.0, -3.0),
+        radii: vector(2.0, 2.0),
+        start_angle: Angle::radians(0.0),
+        sweep_angle: Angle::radians(2.0 * PI),
+        x_rotation: Angle::radians(0.0),
+    };
+    path.begin(arc.from());
+    arc.for_each_cubic_bezier(&mut |curve| {
+        path.cubic_bezier_to(curve.ctrl1, curve.ctrl2, curve.to);
+    });
+    path.close();
+    let path = path.build();
+
+    let moments = area_moments(&path);
+    // The cubic bézier approximation of the circle is close but not exact.
+    assert!((moments.area - 4.0 * PI).abs() < 5e-2);
+    assert_approx_eq(moments.centroid.x, 10.0);
+    assert_approx_eq(moments.centroid.y, -3.0);
+    // π r⁴ / 4
+    assert!((moments.ixx - 4.0 * PI).abs() < 5e-2);
+    assert!((moments.iyy - 4.0 * PI).abs() < 5e-2);
+    assert_approx_eq(moments.ixy, 0.0);
+}
+
+#[test]
+fn quadratic_area() {
+    use crate::geom::point;
+    let mut path = crate::path::Path::builder();
+
+    // Area under the parabola y = x² between 0 and 1 is 1/3.
+    path.begin(point(0.0, 0.0));
+    path.line_to(point(1.0, 0.0));
+    path.line_to(point(1.0, 1.0));
+    path.quadratic_bezier_to(point(0.5, 0.0), point(0.0, 0.0));
+    path.close();
+
+    let path = path.build();
+
+    assert_approx_eq(signed_area(&path), 1.0 / 3.0);
+    let c = centroid(&path).unwrap();
+    assert_approx_eq(c.x, 0.75);
+    assert_approx_eq(c.y, 0.3);
+
+    let mut empty = crate::path::Path::builder();
+    empty.begin(point(0.0, 0.0));
+    empty.line_to(point(1.0, 1.0));
+    empty.end(false);
+
+    assert_eq!(centroid(&empty.build()), None);
+}
@@ -0,0 +1,360 @@
+//! Build the geometry of charts from data series.
+//!
+//! A `Chart` maps data values to positions with a linear scale on each axis. Data
+//! series can then be converted into paths for line, area and bar charts, and axes
+//! with tick marks at round values, ready to be stroked or filled.
+//!
+//! Values that are not finite (for example `NaN` for missing data) leave gaps in the
+//! line and area charts.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::chart::{Chart, LinearScale};
+//! use lyon_algorithms::math::point;
+//!
+//! let data = [point(0.0, 3.0), point(1.0, 7.5), point(2.0, 4.2), point(3.0, 9.1)];
+//!
+//! // Map the data to a 400x300 area, with the y axis pointing up in screen coordinates.
+//! let chart = Chart {
+//!     x: LinearScale::new(0.0..3.0, 0.0..400.0),
+//!     y: LinearScale::new(0.0..9.1, 300.0..0.0).nice(5),
+//! };
+//!
+//! let line = chart.line_path(&data);
+//! let area = chart.area_path(&data, 0.0);
+//! let ticks = chart.y.ticks(5);
+//! assert_eq!(ticks, vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+//! let axis = chart.y_axis(0.0, &ticks, -5.0);
+//! ```
+
+use crate::math::{point, Point};
+use crate::path::Path;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// Maps values of a domain to a range of positions along an axis.
+///
+/// The range can be decreasing, for example to have larger values at the top of a
+/// chart in a y-down coordinate system.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct LinearScale {
+    pub domain: Range<f64>,
+    pub range: Range<f64>,
+}
+
+impl LinearScale {
+    #[inline]
+    pub fn new(domain: Range<f64>, range: Range<f64>) -> Self {
+        LinearScale { domain, range }
+    }
+
+    /// Maps a value of the domain to a position.
+    #[inline]
+    pub fn map(&self, value: f64) -> f64 {
+        let extent = self.domain.end - self.domain.start;
+        if extent == 0.0 {
+            return (self.range.start + self.range.end) * 0.5;
+        }
+
+        let t = (value - self.domain.start) / extent;
+        self.range.start + (self.range.end - self.range.start) * t
+    }
+
+    /// Returns round values within the domain, roughly `count` of them, spaced by
+    /// one, two or five times a power of ten.
+    pub fn ticks(&self, count: u32) -> Vec<f64> {
+        let (min, max) = ordered(&self.domain);
+        let step = match tick_step(min, max, count) {
+            Some(step) => step,
+            None => return alloc::vec![min],
+        };
+
+        let first = (min / step - TICK_EPSILON).ceil() as i64;
+        let last = (max / step + TICK_EPSILON).floor() as i64;
+
+        (first..=last).map(|i| tick_value(i, step)).collect()
+    }
+
+    /// Extends the domain to round values, so that its bounds are ticks.
+    ///
+    /// See `ticks`.
+    pub fn nice(mut self, count: u32) -> Self {
+        let (min, max) = ordered(&self.domain);
+        if let Some(step) = tick_step(min, max, count) {
+            let min = tick_value((min / step + TICK_EPSILON).floor() as i64, step);
+            let max = tick_value((max / step - TICK_EPSILON).ceil() as i64, step);
+            self.domain = if self.domain.start <= self.domain.end {
+                min..max
+            } else {
+                max..min
+            };
+        }
+
+        self
+    }
+}
+
+// Tolerance on the tick indices, so that the bounds of the domain are considered as
+// ticks despite rounding errors.
+const TICK_EPSILON: f64 = 1e-9;
+
+fn ordered(range: &Range<f64>) -> (f64, f64) {
+    (range.start.min(range.end), range.start.max(range.end))
+}
+
+// Dividing by the inverse of steps that are smaller than one avoids rounding errors,
+// for example 0.6 instead of 3.0 * 0.2 = 0.6000000000000001.
+fn tick_value(index: i64, step: f64) -> f64 {
+    if step < 1.0 {
+        index as f64 / (1.0 / step).round()
+    } else {
+        index as f64 * step
+    }
+}
+
+fn tick_step(min: f64, max: f64, count: u32) -> Option<f64> {
+    let extent = max - min;
+    if extent <= 0.0 || !extent.is_finite() {
+        return None;
+    }
+
+    let rough = extent / count.max(1) as f64;
+    let magnitude = 10.0f64.powf(rough.log10().floor());
+    let normalized = rough / magnitude;
+    let nice = if normalized < 1.5 {
+        1.0
+    } else if normalized < 3.0 {
+        2.0
+    } else if normalized < 7.0 {
+        5.0
+    } else {
+        10.0
+    };
+
+    Some(nice * magnitude)
+}
+
+/// Maps data points to positions with a scale on each axis.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Chart {
+    pub x: LinearScale,
+    pub y: LinearScale,
+}
+
+impl Chart {
+    /// Maps a data point to a position.
+    #[inline]
+    pub fn map(&self, p: Point) -> Point {
+        point(self.x.map(p.x), self.y.map(p.y))
+    }
+
+    /// Builds a polyline going through the data points.
+    pub fn line_path(&self, data: &[Point]) -> Path {
+        let mut builder = Path::builder();
+        for run in runs(data) {
+            builder.begin(self.map(run[0]));
+            for p in &run[1..] {
+                builder.line_to(self.map(*p));
+            }
+            builder.end(false);
+        }
+
+        builder.build()
+    }
+
+    /// Builds the area between the data points and the horizontal line at the
+    /// `baseline` value.
+    pub fn area_path(&self, data: &[Point], baseline: f64) -> Path {
+        let mut builder = Path::builder();
+        for run in runs(data) {
+            builder.begin(self.map(point(run[0].x, baseline)));
+            for p in run {
+                builder.line_to(self.map(*p));
+            }
+            builder.line_to(self.map(point(run[run.len() - 1].x, baseline)));
+            builder.close();
+        }
+
+        builder.build()
+    }
+
+    /// Builds a rectangle for each data point, between the `baseline` value and the
+    /// value of the point, centered on its x coordinate.
+    ///
+    /// The width of the bars is expressed in data units.
+    pub fn bar_path(&self, data: &[Point], width: f64, baseline: f64) -> Path {
+        let mut builder = Path::builder();
+        for p in data {
+            if !p.x.is_finite() || !p.y.is_finite() {
+                continue;
+            }
+
+            let a = self.map(point(p.x - width * 0.5, baseline));
+            let b = self.map(point(p.x + width * 0.5, p.y));
+            builder.begin(a);
+            builder.line_to(point(b.x, a.y));
+            builder.line_to(b);
+            builder.line_to(point(a.x, b.y));
+            builder.close();
+        }
+
+        builder.build()
+    }
+
+    /// Builds a horizontal axis line at the `y` value spanning the range of the x
+    /// scale, with a tick mark of length `tick_length` at each of the `ticks` values.
+    ///
+    /// The tick marks go towards positive y positions, or negative ones if the length
+    /// is negative.
+    pub fn x_axis(&self, y: f64, ticks: &[f64], tick_length: f64) -> Path {
+        let y = self.y.map(y);
+        let mut builder = Path::builder();
+        builder.begin(point(self.x.range.start, y));
+        builder.line_to(point(self.x.range.end, y));
+        builder.end(false);
+        for tick in ticks {
+            let x = self.x.map(*tick);
+            builder.begin(point(x, y));
+            builder.line_to(point(x, y + tick_length));
+            builder.end(false);
+        }
+
+        builder.build()
+    }
+
+    /// Builds a vertical axis line at the `x` value spanning the range of the y
+    /// scale, with a tick mark of length `tick_length` at each of the `ticks` values.
+    ///
+    /// The tick marks go towards positive x positions, or negative ones if the length
+    /// is negative.
+    pub fn y_axis(&self, x: f64, ticks: &[f64], tick_length: f64) -> Path {
+        let x = self.x.map(x);
+        let mut builder = Path::builder();
+        builder.begin(point(x, self.y.range.start));
+        builder.line_to(point(x, self.y.range.end));
+        builder.end(false);
+        for tick in ticks {
+            let y = self.y.map(*tick);
+            builder.begin(point(x, y));
+            builder.line_to(point(x + tick_length, y));
+            builder.end(false);
+        }
+
+        builder.build()
+    }
+}
+
+// The sequences of consecutive data points with finite coordinates.
+fn runs(data: &[Point]) -> impl Iterator<Item = &[Point]> {
+    data.split(|p| !p.x.is_finite() || !p.y.is_finite())
+        .filter(|run| !run.is_empty())
+}
+
+#[cfg(test)]
+use crate::path::PathEvent;
+
+#[test]
+fn ticks() {
+    let scale = LinearScale::new(0.0..1.0, 0.0..100.0);
+    assert_eq!(scale.ticks(5), alloc::vec![0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+
+    let scale = LinearScale::new(-13.0..87.0, 0.0..100.0);
+    assert_eq!(scale.ticks(4), alloc::vec![0.0, 20.0, 40.0, 60.0, 80.0]);
+    let nice = scale.nice(4);
+    assert_eq!(nice.domain, -20.0..100.0);
+    assert_eq!(nice.range, 0.0..100.0);
+
+    // Decreasing domains.
+    let scale = LinearScale::new(87.0..-13.0, 0.0..100.0).nice(4);
+    assert_eq!(scale.domain, 100.0..-20.0);
+    assert_eq!(scale.map(100.0), 0.0);
+
+    let scale = LinearScale::new(2.0..2.0, 0.0..100.0);
+    assert_eq!(scale.ticks(4), alloc::vec![2.0]);
+    assert_eq!(scale.map(2.0), 50.0);
+}
+
+#[test]
+fn chart_paths() {
+    let chart = Chart {
+        x: LinearScale::new(0.0..4.0, 0.0..400.0),
+        y: LinearScale::new(0.0..10.0, 100.0..0.0),
+    };
+    let data = [
+        point(0.0, 5.0),
+        point(1.0, 10.0),
+        point(2.0, f64::NAN),
+        point(3.0, 0.0),
+        point(4.0, 5.0),
+    ];
+
+    let line: Vec<PathEvent> = chart.line_path(&data).iter().collect();
+    assert_eq!(
+        line,
+        alloc::vec![
+            PathEvent::Begin {
+                at: point(0.0, 50.0)
+            },
+            PathEvent::Line {
+                from: point(0.0, 50.0),
+                to: point(100.0, 0.0)
+            },
+            PathEvent::End {
+                last: point(100.0, 0.0),
+                first: point(0.0, 50.0),
+                close: false
+            },
+            PathEvent::Begin {
+                at: point(300.0, 100.0)
+            },
+            PathEvent::Line {
+                from: point(300.0, 100.0),
+                to: point(400.0, 50.0)
+            },
+            PathEvent::End {
+                last: point(400.0, 50.0),
+                first: point(300.0, 100.0),
+                close: false
+            },
+        ]
+    );
+
+    // Trapezoids of 100 by 50 and 100 in screen units.
+    let area = crate::area::signed_area(&chart.area_path(&data, 0.0));
+    assert!((area.abs() - 7500.0 - 2500.0).abs() < 1e-9);
+
+    let bars = chart.bar_path(&data, 0.5, 0.0);
+    let area = crate::area::signed_area(&bars);
+    assert!((area.abs() - 50.0 * (50.0 + 100.0 + 0.0 + 50.0)).abs() < 1e-9);
+
+    let axis = chart.x_axis(0.0, &chart.x.ticks(4), 5.0);
+    let sub_paths = axis
+        .iter()
+        .filter(|evt| matches!(evt, PathEvent::Begin { .. }))
+        .count();
+    assert_eq!(sub_paths, 6);
+    let axis = chart.y_axis(0.0, &[0.0, 10.0], -5.0);
+    let ends: Vec<Point> = axis
+        .iter()
+        .filter_map(|evt| match evt {
+            PathEvent::End { last, .. } => Some(last),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        ends,
+        alloc::vec![point(0.0, 0.0), point(-5.0, 100.0), point(-5.0, 0.0)]
+    );
+}
@@ -0,0 +1,332 @@
+//! Tangents and normals along a path.
+//!
+//! Strokes, offsets, extruded side walls and text laid out along a path all need
+//! a consistent frame of reference at each vertex of the path. This module computes
+//! them along the flattened path so that every user makes the same choices at corners,
+//! cusps and for the orientation of the normals.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::frame::path_frames;
+//! use lyon_algorithms::geom::point;
+//! use lyon_algorithms::path::Polygon;
+//!
+//! let square = [
+//!     point(0.0, 0.0),
+//!     point(1.0, 0.0),
+//!     point(1.0, 1.0),
+//!     point(0.0, 1.0),
+//! ];
+//! let polygon = Polygon { points: &square, closed: true };
+//!
+//! for frame in path_frames(polygon.path_events(), 0.01) {
+//!     // The normals of closed sub-paths point outward.
+//!     let outward = frame.position - point(0.5, 0.5);
+//!     assert!(frame.normal.dot(outward) > 0.0);
+//! }
+//! ```
+
+use crate::geom::utils::tangent;
+use crate::math::{Point, Vector};
+use crate::path::iterator::{Flattened, PathIterator};
+use crate::path::PathEvent;
+use alloc::vec::Vec;
+
+/// The tangent and normal at a vertex of a flattened path.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PathFrame {
+    /// Position of the vertex.
+    pub position: Point,
+    /// Unit tangent, bisecting the directions of the edges before and after the vertex.
+    ///
+    /// At cusps, where the path goes back in the direction it came from, this is the
+    /// direction of the edge before the vertex.
+    pub tangent: Vector,
+    /// Unit normal, perpendicular to the tangent.
+    ///
+    /// For closed sub-paths the normal points out of the shape regardless of the
+    /// winding order of the sub-path. For open sub-paths it is the tangent rotated
+    /// by 90 degrees (pointing towards positive y when the tangent points towards
+    /// positive x).
+    pub normal: Vector,
+    /// Factor to apply to an offset distance along the normal so that offsetting the
+    /// vertex keeps the adjacent edges at that distance.
+    ///
+    /// This is `1.0` for straight angles and grows as corners get sharper. It is
+    /// infinite at cusps.
+    pub miter_length: f64,
+    /// Distance along the flattened sub-path from its first vertex.
+    pub distance: f64,
+    /// Index of the sub-path containing the vertex.
+    pub sub_path: u32,
+}
+
+impl PathFrame {
+    /// Position of the vertex offset along the normal, accounting for the miter length.
+    ///
+    /// The result is not meaningful at cusps where the miter length is infinite.
+    pub fn offset(&self, distance: f64) -> Point {
+        self.position + self.normal * (distance * self.miter_length)
+    }
+}
+
+/// Computes the tangents and normals at each vertex of a path.
+///
+/// Curves are flattened using the provided tolerance. Duplicate consecutive
+/// points are skipped and sub-paths made of a single point produce no frame.
+/// The last point of a closed sub-path is not repeated when it is equal to the
+/// first one.
+pub fn path_frames<Iter>(path: Iter, tolerance: f64) -> PathFrames<Iter::IntoIter>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    PathFrames {
+        events: path.into_iter().flattened(tolerance),
+        points: Vec::new(),
+        closed: false,
+        normal_sign: 1.0,
+        index: 0,
+        distance: 0.0,
+        sub_path: 0,
+        started: false,
+    }
+}
+
+/// An iterator over the frames of a path.
+///
+/// See `path_frames`.
+pub struct PathFrames<Iter> {
+    events: Flattened<Iter>,
+    points: Vec<Point>,
+    closed: bool,
+    normal_sign: f64,
+    index: usize,
+    distance: f64,
+    sub_path: u32,
+    started: bool,
+}
+
+impl<Iter> PathFrames<Iter>
+where
+    Iter: Iterator<Item = PathEvent>,
+{
+    // Read the next sub-path, returns false at the end of the path.
+    fn next_sub_path(&mut self) -> bool {
+        self.points.clear();
+        self.index = 0;
+        self.distance = 0.0;
+        if self.started {
+            self.sub_path += 1;
+        }
+
+        for evt in &mut self.events {
+            match evt {
+                PathEvent::Begin { at } => {
+                    self.started = true;
+                    self.points.push(at);
+                }
+                PathEvent::Line { to, .. } if self.points.last() != Some(&to) => {
+                    self.points.push(to);
+                }
+                PathEvent::End { close, .. } => {
+                    self.closed = close;
+                    if close && self.points.len() > 1 && self.points.first() == self.points.last() {
+                        self.points.pop();
+                    }
+                    // A closed sub-path needs at least three points to have an inside.
+                    if self.points.len() < 3 {
+                        self.closed = false;
+                    }
+
+                    self.normal_sign = 1.0;
+                    if self.closed {
+                        let n = self.points.len();
+                        let mut area = 0.0;
+                        for i in 0..n {
+                            let a = self.points[i].to_vector();
+                            let b = self.points[(i + 1) % n].to_vector();
+                            area += a.cross(b);
+                        }
+                        // With a positive area the rotated tangent points inward.
+                        if area > 0.0 {
+                            self.normal_sign = -1.0;
+                        }
+                    }
+
+                    return true;
+                }
+                _ => {}
+            }
+        }
+
+        false
+    }
+
+    fn frame(&self, i: usize) -> PathFrame {
+        let n = self.points.len();
+        let p = self.points[i];
+
+        let incoming = if i > 0 {
+            Some(p - self.points[i - 1])
+        } else if self.closed {
+            Some(p - self.points[n - 1])
+        } else {
+            None
+        };
+        let outgoing = if i + 1 < n {
+            Some(self.points[i + 1] - p)
+        } else if self.closed {
+            Some(self.points[0] - p)
+        } else {
+            None
+        };
+
+        let (tangent_vector, miter_length) = match (incoming, outgoing) {
+            (Some(a), Some(b)) => {
+                let a = a.normalize();
+                let b = b.normalize();
+                let bisector = a + b;
+                if bisector.square_length() < 1e-12 {
+                    (a, f64::INFINITY)
+                } else {
+                    let t = bisector.normalize();
+                    (t, 1.0 / t.dot(a))
+                }
+            }
+            (Some(v), None) | (None, Some(v)) => (v.normalize(), 1.0),
+            (None, None) => unreachable!(),
+        };
+
+        PathFrame {
+            position: p,
+            tangent: tangent_vector,
+            normal: tangent(tangent_vector) * self.normal_sign,
+            miter_length,
+            distance: self.distance,
+            sub_path: self.sub_path,
+        }
+    }
+}
+
+impl<Iter> Iterator for PathFrames<Iter>
+where
+    Iter: Iterator<Item = PathEvent>,
+{
+    type Item = PathFrame;
+
+    fn next(&mut self) -> Option<PathFrame> {
+        while self.index >= self.points.len() || self.points.len() < 2 {
+            if !self.next_sub_path() {
+                return None;
+            }
+        }
+
+        if self.index > 0 {
+            self.distance += (self.points[self.index] - self.points[self.index - 1]).length();
+        }
+
+        let frame = self.frame(self.index);
+        self.index += 1;
+
+        Some(frame)
+    }
+}
+
+#[cfg(test)]
+use crate::geom::{point, vector};
+#[cfg(test)]
+use crate::path::{Path, Polygon, Winding};
+
+#[test]
+fn square_frames() {
+    let points = [
+        point(0.0, 0.0),
+        point(1.0, 0.0),
+        point(1.0, 1.0),
+        point(0.0, 1.0),
+    ];
+    let mut reversed = points;
+    reversed.reverse();
+
+    for points in &[points, reversed] {
+        let polygon = Polygon {
+            points: &points[..],
+            closed: true,
+        };
+        let frames: Vec<PathFrame> = path_frames(polygon.path_events(), 0.01).collect();
+        assert_eq!(frames.len(), 4);
+
+        for frame in &frames {
+            let outward = (frame.position - point(0.5, 0.5)).normalize();
+            assert!((frame.normal - outward).length() < 1e-12);
+            assert!((frame.miter_length - 2.0f64.sqrt()).abs() < 1e-12);
+            // Offsetting the corners gives a larger square.
+            let offset = frame.offset(1.0) - point(0.5, 0.5);
+            assert!((offset.x.abs() - 1.5).abs() < 1e-12);
+            assert!((offset.y.abs() - 1.5).abs() < 1e-12);
+        }
+
+        assert_eq!(frames[3].distance, 3.0);
+    }
+}
+
+#[test]
+fn open_frames() {
+    let points = [
+        point(0.0, 0.0),
+        point(1.0, 0.0),
+        point(1.0, 0.0),
+        point(0.0, 0.0),
+    ];
+    let polygon = Polygon {
+        points: &points,
+        closed: false,
+    };
+
+    let frames: Vec<PathFrame> = path_frames(polygon.path_events(), 0.01).collect();
+    // The duplicate point is skipped.
+    assert_eq!(frames.len(), 3);
+
+    assert_eq!(frames[0].tangent, vector(1.0, 0.0));
+    assert_eq!(frames[0].normal, vector(0.0, 1.0));
+    assert_eq!(frames[0].miter_length, 1.0);
+
+    // A cusp.
+    assert_eq!(frames[1].tangent, vector(1.0, 0.0));
+    assert_eq!(frames[1].miter_length, f64::INFINITY);
+
+    assert_eq!(frames[2].tangent, vector(-1.0, 0.0));
+    assert_eq!(frames[2].distance, 2.0);
+}
+
+#[test]
+fn circle_frames() {
+    for &winding in &[Winding::Positive, Winding::Negative] {
+        let mut builder = Path::builder();
+        builder.add_circle(point(0.0, 0.0), 10.0, winding);
+        builder.add_circle(point(50.0, 0.0), 5.0, winding);
+        let path = builder.build();
+
+        let mut count = 0;
+        let mut last_distance = [0.0; 2];
+        for frame in path_frames(&path, 0.01) {
+            let center = if frame.sub_path == 0 {
+                point(0.0, 0.0)
+            } else {
+                point(50.0, 0.0)
+            };
+            let outward = (frame.position - center).normalize();
+            assert!((frame.normal - outward).length() < 0.05);
+            assert!(frame.tangent.dot(outward).abs() < 0.05);
+            last_distance[frame.sub_path as usize] = frame.distance;
+            count += 1;
+        }
+
+        assert!(count > 8);
+        assert!((last_distance[0] - 20.0 * core::f64::consts::PI).abs() < 1.0);
+        assert!((last_distance[1] - 10.0 * core::f64::consts::PI).abs() < 1.0);
+    }
+
+    assert_eq!(path_frames(&Path::new(), 0.01).count(), 0);
+}
@@ -352,7 +352,12 @@ impl Hatcher {
         self.segment.b.tangent = vector(f64::NAN, f64::NAN);
         self.compute_tangents = options.compute_tangents;
 
-        let mut y = events.edges.first().unwrap().from.y + output.next_offset(0);
+        let mut y = match events.edges.first() {
+            Some(edge) => edge.from.y + output.next_offset(0),
+            None => {
+                return;
+            }
+        };
         let mut y_max = y;
 
         for edge in &events.edges {
@@ -649,6 +654,68 @@ impl<Cb: FnMut(&Dot)> DotBuilder for RegularDotPattern<Cb> {
     }
 }
 
+/// Intersects a shape with a family of parallel lines and returns the resulting segments.
+///
+/// The lines are parallel to `direction` and `spacing` apart. The segments are the parts of
+/// the lines that are inside of the shape according to the even-odd fill rule, so sub-paths
+/// can be used to represent holes and multiple polygons can be sliced at once.
+///
+/// This is a convenience wrapper around `Hatcher::hatch_path`, useful for infill, engraving
+/// toolpaths or simple hatching fills.
+///
+/// ## Example
+///
+/// ```
+/// use lyon_algorithms::hatching::slice;
+/// use lyon_algorithms::geom::{point, vector};
+/// use lyon_algorithms::path::Polygon;
+///
+/// let square = [
+///     point(0.0, 0.0),
+///     point(10.0, 0.0),
+///     point(10.0, 10.0),
+///     point(0.0, 10.0),
+/// ];
+/// let polygon = Polygon { points: &square, closed: true };
+///
+/// let segments = slice(polygon.path_events(), vector(1.0, 0.0), 2.0, 0.1);
+/// assert_eq!(segments.len(), 4);
+/// ```
+pub fn slice<Iter>(
+    path: Iter,
+    direction: Vector,
+    spacing: f64,
+    tolerance: f64,
+) -> Vec<LineSegment<f64>>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut segments = Vec::new();
+    if spacing <= 0.0 {
+        return segments;
+    }
+
+    let options = HatchingOptions::tolerance(tolerance)
+        .with_angle(direction.angle_from_x_axis())
+        .with_tangents(false);
+
+    Hatcher::new().hatch_path(
+        path,
+        &options,
+        &mut RegularHatchingPattern {
+            interval: spacing,
+            callback: &mut |segment: &HatchSegment| {
+                segments.push(LineSegment {
+                    from: segment.a.position,
+                    to: segment.b.position,
+                });
+            },
+        },
+    );
+
+    segments
+}
+
 /// A `HatchBuilder` implementation for hatching patterns with constant intervals.
 pub struct RegularHatchingPattern<Cb: FnMut(&HatchSegment)> {
     /// The distance between each row of hatches.
@@ -753,3 +820,39 @@ fn simple_hatching() {
     );
     let _ = hatches.build();
 }
+
+#[test]
+fn slice_with_hole() {
+    use lyon_path::Path;
+
+    let mut path = Path::builder();
+    path.begin(point(0.0, 0.0));
+    path.line_to(point(10.0, 0.0));
+    path.line_to(point(10.0, 10.0));
+    path.line_to(point(0.0, 10.0));
+    path.end(true);
+    path.begin(point(3.0, 3.0));
+    path.line_to(point(7.0, 3.0));
+    path.line_to(point(7.0, 7.0));
+    path.line_to(point(3.0, 7.0));
+    path.end(true);
+    let path = path.build();
+
+    // Vertical lines at x = 2, 4, 6 and 8.
+    let segments = slice(path.iter(), vector(0.0, 1.0), 2.0, 0.1);
+
+    let mut total_length = 0.0;
+    for segment in &segments {
+        assert!((segment.from.x - segment.to.x).abs() < 1e-9);
+        total_length += segment.length();
+    }
+    // The lines at x = 4 and 6 go through the hole.
+    assert_eq!(segments.len(), 2 + 2 * 2);
+    assert!((total_length - (2.0 * 10.0 + 2.0 * 6.0)).abs() < 1e-9);
+}
+
+#[test]
+fn slice_empty_path() {
+    let path = lyon_path::Path::new();
+    assert!(slice(path.iter(), vector(1.0, 0.0), 1.0, 0.1).is_empty());
+}
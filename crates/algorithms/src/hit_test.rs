@@ -1,8 +1,8 @@
-//! Determine whether a point is inside a path.
+//! Determine whether a point is inside a path or its stroke.
 
 use crate::geom::{CubicBezierSegment, LineSegment, QuadraticBezierSegment};
 use crate::math::Point;
-use crate::path::{FillRule, PathEvent};
+use crate::path::{iterator::PathIterator, FillRule, LineCap, PathEvent};
 
 /// Returns whether the point is inside the path.
 pub fn hit_test_path<Iter>(point: &Point, path: Iter, fill_rule: FillRule, tolerance: f64) -> bool
@@ -161,6 +161,229 @@ fn test_segment(
     }
 }
 
+/// Parameters for `hit_test_stroke`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[non_exhaustive]
+pub struct StrokeHitTestOptions {
+    /// Line width of the stroke.
+    ///
+    /// Default value: `StrokeHitTestOptions::DEFAULT_LINE_WIDTH`.
+    pub line_width: f64,
+    /// What cap is used at the start of each sub-path.
+    ///
+    /// Default value: `LineCap::Butt`.
+    pub start_cap: LineCap,
+    /// What cap is used at the end of each sub-path.
+    ///
+    /// Default value: `LineCap::Butt`.
+    pub end_cap: LineCap,
+    /// Extra distance around the stroke within which points are still considered
+    /// to hit it.
+    ///
+    /// This makes very thin strokes easier to click on.
+    ///
+    /// Default value: `0.0`.
+    pub hit_tolerance: f64,
+    /// Maximum allowed distance to the path when building an approximation.
+    ///
+    /// Default value: `StrokeHitTestOptions::DEFAULT_TOLERANCE`.
+    pub tolerance: f64,
+}
+
+impl Default for StrokeHitTestOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl StrokeHitTestOptions {
+    pub const DEFAULT_LINE_WIDTH: f64 = 1.0;
+    pub const DEFAULT_TOLERANCE: f64 = 0.1;
+
+    pub const DEFAULT: Self = StrokeHitTestOptions {
+        line_width: Self::DEFAULT_LINE_WIDTH,
+        start_cap: LineCap::Butt,
+        end_cap: LineCap::Butt,
+        hit_tolerance: 0.0,
+        tolerance: Self::DEFAULT_TOLERANCE,
+    };
+
+    #[inline]
+    pub fn line_width(line_width: f64) -> Self {
+        Self::DEFAULT.with_line_width(line_width)
+    }
+
+    #[inline]
+    pub fn with_line_width(mut self, line_width: f64) -> Self {
+        self.line_width = line_width;
+        self
+    }
+
+    #[inline]
+    pub fn with_line_cap(mut self, cap: LineCap) -> Self {
+        self.start_cap = cap;
+        self.end_cap = cap;
+        self
+    }
+
+    #[inline]
+    pub fn with_start_cap(mut self, cap: LineCap) -> Self {
+        self.start_cap = cap;
+        self
+    }
+
+    #[inline]
+    pub fn with_end_cap(mut self, cap: LineCap) -> Self {
+        self.end_cap = cap;
+        self
+    }
+
+    #[inline]
+    pub fn with_hit_tolerance(mut self, hit_tolerance: f64) -> Self {
+        self.hit_tolerance = hit_tolerance;
+        self
+    }
+
+    #[inline]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// Returns whether the point is inside the stroke of the path.
+///
+/// The stroke is dilated by `options.hit_tolerance`, which is useful to make sure that
+/// thin lines remain easy to pick.
+///
+/// Line caps are taken into account at the extremities of open sub-paths. Joins are
+/// treated as round joins, which slightly overestimates the stroke around bevel joins
+/// and underestimates it around sharp miter joins.
+pub fn hit_test_stroke<Iter>(point: &Point, path: Iter, options: &StrokeHitTestOptions) -> bool
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let radius = options.line_width * 0.5 + options.hit_tolerance;
+    let start_extension = cap_extension(options.start_cap, options);
+    let end_extension = cap_extension(options.end_cap, options);
+
+    let mut first_edge = None;
+    let mut pending_edge = None;
+
+    for evt in path.into_iter().flattened(options.tolerance) {
+        match evt {
+            PathEvent::Begin { .. } => {
+                first_edge = None;
+                pending_edge = None;
+            }
+            PathEvent::Line { from, to } => {
+                let edge = LineSegment { from, to };
+                if first_edge.is_none() {
+                    first_edge = Some(edge);
+                    continue;
+                }
+                if let Some(prev) = pending_edge.replace(edge) {
+                    if edge_hit(*point, &prev, radius, None, None) {
+                        return true;
+                    }
+                }
+            }
+            PathEvent::End { last, first, close } => {
+                let first_edge = match first_edge {
+                    Some(edge) => edge,
+                    None => {
+                        // A sub-path with a single point only has a stroke if it has caps.
+                        let has_caps =
+                            options.start_cap != LineCap::Butt || options.end_cap != LineCap::Butt;
+                        if has_caps && (*point - first).length() <= radius {
+                            return true;
+                        }
+                        continue;
+                    }
+                };
+
+                let hit = if close {
+                    let closing_edge = LineSegment {
+                        from: last,
+                        to: first,
+                    };
+                    edge_hit(*point, &first_edge, radius, None, None)
+                        || pending_edge
+                            .map(|edge| edge_hit(*point, &edge, radius, None, None))
+                            .unwrap_or(false)
+                        || edge_hit(*point, &closing_edge, radius, None, None)
+                } else if let Some(last_edge) = pending_edge {
+                    edge_hit(*point, &first_edge, radius, start_extension, None)
+                        || edge_hit(*point, &last_edge, radius, None, end_extension)
+                } else {
+                    edge_hit(*point, &first_edge, radius, start_extension, end_extension)
+                };
+
+                if hit {
+                    return true;
+                }
+            }
+            PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {
+                debug_assert!(false, "Unexpected curve in a flattened path");
+            }
+        }
+    }
+
+    false
+}
+
+// How far beyond the end of the sub-path the stroke extends, or None for round caps.
+fn cap_extension(cap: LineCap, options: &StrokeHitTestOptions) -> Option<f64> {
+    match cap {
+        LineCap::Round => None,
+        LineCap::Butt => Some(options.hit_tolerance),
+        LineCap::Square => Some(options.line_width * 0.5 + options.hit_tolerance),
+    }
+}
+
+// Tests whether a point is within `radius` of an edge.
+//
+// If an extension is provided, the corresponding end of the edge has a flat cap
+// extended by the given distance, otherwise it is round.
+fn edge_hit(
+    point: Point,
+    edge: &LineSegment<f64>,
+    radius: f64,
+    start_extension: Option<f64>,
+    end_extension: Option<f64>,
+) -> bool {
+    let v = edge.to_vector();
+    let length = v.length();
+    if length == 0.0 {
+        return (point - edge.from).length() <= radius;
+    }
+
+    let direction = v / length;
+    let d = point - edge.from;
+    let along = d.dot(direction);
+    let across = d.cross(direction).abs();
+
+    if along < 0.0 {
+        return match start_extension {
+            Some(extension) => along >= -extension && across <= radius,
+            None => d.length() <= radius,
+        };
+    }
+
+    if along > length {
+        return match end_extension {
+            Some(extension) => along <= length + extension && across <= radius,
+            None => (point - edge.to).length() <= radius,
+        };
+    }
+
+    across <= radius
+}
+
 #[test]
 fn test_hit_test() {
     use crate::math::point;
@@ -318,3 +541,63 @@ fn hit_test_double_count() {
         1
     );
 }
+
+#[test]
+fn hit_test_thin_stroke() {
+    use crate::math::point;
+    use crate::path::Path;
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.end(false);
+    let path = builder.build();
+
+    let options = StrokeHitTestOptions::line_width(1.0);
+
+    assert!(hit_test_stroke(&point(5.0, 0.4), &path, &options));
+    assert!(hit_test_stroke(&point(10.4, 5.0), &path, &options));
+    assert!(!hit_test_stroke(&point(5.0, 0.6), &path, &options));
+    assert!(!hit_test_stroke(&point(5.0, 5.0), &path, &options));
+
+    // The hit tolerance dilates the stroke.
+    let options = options.with_hit_tolerance(2.0);
+    assert!(hit_test_stroke(&point(5.0, 2.4), &path, &options));
+    assert!(!hit_test_stroke(&point(5.0, 2.6), &path, &options));
+}
+
+#[test]
+fn hit_test_stroke_caps() {
+    use crate::math::point;
+    use crate::path::Path;
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.end(false);
+    let path = builder.build();
+
+    let butt = StrokeHitTestOptions::line_width(2.0).with_line_cap(LineCap::Butt);
+    let square = butt.with_line_cap(LineCap::Square);
+    let round = butt.with_line_cap(LineCap::Round);
+
+    assert!(!hit_test_stroke(&point(-0.5, 0.0), &path, &butt));
+    assert!(hit_test_stroke(&point(-0.5, 0.0), &path, &square));
+    assert!(hit_test_stroke(&point(-0.5, 0.0), &path, &round));
+
+    // In the corner of the square cap but outside of the round one.
+    assert!(hit_test_stroke(&point(10.9, 0.9), &path, &square));
+    assert!(!hit_test_stroke(&point(10.9, 0.9), &path, &round));
+    assert!(!hit_test_stroke(&point(10.9, 0.9), &path, &butt));
+
+    // Closed sub-paths don't have caps.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+    assert!(hit_test_stroke(&point(-0.5, 0.0), &path, &butt));
+    assert!(hit_test_stroke(&point(5.0, 5.0), &path, &butt));
+}
@@ -0,0 +1,261 @@
+//! Build paths from implicit functions.
+//!
+//! The shape is the region where a function of the position is greater than or equal
+//! to a threshold, which is a convenient way to describe blobby shapes such as
+//! metaballs. The function is sampled on a grid and the contours of the samples are
+//! extracted with marching squares. Their vertices are then moved onto the exact
+//! iso-line of the function using Newton iterations, which removes the artifacts of the
+//! grid, and finally simplified.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::implicit::{polygonize, PolygonizeOptions};
+//! use lyon_algorithms::math::{point, Box2D};
+//! use lyon_algorithms::path::PathEvent;
+//!
+//! // Two metaballs close enough to merge.
+//! let balls = [(point(-6.0, 0.0), 5.0), (point(6.0, 0.0), 5.0)];
+//! let metaballs = |p: lyon_algorithms::math::Point| -> f64 {
+//!     balls.iter().map(|(center, radius)| radius * radius / (p - *center).square_length()).sum()
+//! };
+//!
+//! let bounds = Box2D { min: point(-20.0, -20.0), max: point(20.0, 20.0) };
+//! let options = PolygonizeOptions::cell_size(1.0).with_threshold(1.0);
+//! let path = polygonize(&bounds, metaballs, &options);
+//!
+//! let sub_paths = path.iter().filter(|evt| matches!(evt, PathEvent::Begin { .. })).count();
+//! assert_eq!(sub_paths, 1);
+//! ```
+
+use crate::math::{vector, Box2D, Point};
+use crate::path::Path;
+use crate::trace::{simplify_contour, trace_contours};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// Parameters for `polygonize`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct PolygonizeOptions {
+    /// Distance between two samples of the grid.
+    ///
+    /// Features of the shape that are smaller than the cells can be missed.
+    ///
+    /// Default value: `PolygonizeOptions::DEFAULT_CELL_SIZE`.
+    pub cell_size: f64,
+    /// The shape is where the function is greater than or equal to the threshold.
+    ///
+    /// Default value: `0.0`.
+    pub threshold: f64,
+    /// Maximum allowed distance between the contours and the simplified path.
+    ///
+    /// Default value: `PolygonizeOptions::DEFAULT_TOLERANCE`.
+    pub tolerance: f64,
+}
+
+impl Default for PolygonizeOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl PolygonizeOptions {
+    pub const DEFAULT_CELL_SIZE: f64 = 1.0;
+    pub const DEFAULT_TOLERANCE: f64 = 0.1;
+
+    pub const DEFAULT: Self = PolygonizeOptions {
+        cell_size: Self::DEFAULT_CELL_SIZE,
+        threshold: 0.0,
+        tolerance: Self::DEFAULT_TOLERANCE,
+    };
+
+    #[inline]
+    pub fn cell_size(cell_size: f64) -> Self {
+        Self::DEFAULT.with_cell_size(cell_size)
+    }
+
+    #[inline]
+    pub fn with_cell_size(mut self, cell_size: f64) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    #[inline]
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    #[inline]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// Builds a path from the region of `bounds` where `function` is greater than or equal
+/// to `options.threshold`.
+///
+/// The shape is clipped to the bounds. Sub-paths have a positive signed area and holes
+/// a negative one (see `Winding`), so the result can be filled with either fill rule.
+///
+/// For signed distance functions that are negative inside of the shape, negate the
+/// function.
+pub fn polygonize<F>(bounds: &Box2D, function: F, options: &PolygonizeOptions) -> Path
+where
+    F: Fn(Point) -> f64,
+{
+    let cell_size = options.cell_size;
+    assert!(cell_size > 0.0);
+
+    let width = (bounds.width() / cell_size).ceil() as usize + 1;
+    let height = (bounds.height() / cell_size).ceil() as usize + 1;
+    let position = |x: f64, y: f64| bounds.min + vector(x, y) * cell_size;
+
+    let mut samples = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            samples.push(function(position(x as f64, y as f64).min(bounds.max)));
+        }
+    }
+
+    let contours = trace_contours(width, height, |idx| samples[idx], options.threshold);
+
+    let mut builder = Path::builder();
+    for contour in contours {
+        let points: Vec<Point> = contour
+            .iter()
+            .map(|p| {
+                let p = position(p.x, p.y);
+                let clipped = p.clamp(bounds.min, bounds.max);
+                // The points outside of the bounds are on the edges of the grid that
+                // connect to the virtual samples around it, they are only clipped.
+                if clipped != p {
+                    return clipped;
+                }
+
+                project(&function, p, options.threshold, cell_size).clamp(bounds.min, bounds.max)
+            })
+            .collect();
+
+        let points = simplify_contour(&points, options.tolerance);
+        if points.len() < 3 {
+            continue;
+        }
+
+        builder.begin(points[0]);
+        for p in &points[1..] {
+            builder.line_to(*p);
+        }
+        builder.close();
+    }
+
+    builder.build()
+}
+
+// Moves a point onto the iso-line with Newton iterations along the gradient, which is
+// evaluated with central differences.
+fn project<F>(function: &F, p: Point, threshold: f64, cell_size: f64) -> Point
+where
+    F: Fn(Point) -> f64,
+{
+    let h = cell_size * 1e-4;
+    let mut position = p;
+    for _ in 0..4 {
+        let value = function(position) - threshold;
+        let gradient = vector(
+            function(position + vector(h, 0.0)) - function(position - vector(h, 0.0)),
+            function(position + vector(0.0, h)) - function(position - vector(0.0, h)),
+        ) / (2.0 * h);
+
+        let square_length = gradient.square_length();
+        if square_length <= 0.0 || !square_length.is_finite() || !value.is_finite() {
+            break;
+        }
+
+        let next = position - gradient * (value / square_length);
+        // The grid position is within a cell of the iso-line, larger steps mean that
+        // the iteration does not converge or jumps to another part of the shape.
+        if !next.x.is_finite() || !next.y.is_finite() || (next - p).length() > cell_size {
+            break;
+        }
+        position = next;
+    }
+
+    position
+}
+
+#[cfg(test)]
+use crate::area::signed_area;
+#[cfg(test)]
+use crate::math::point;
+
+#[test]
+fn polygonize_circle() {
+    let center = point(1.0, 2.0);
+    let circle = |p: Point| 10.0 - (p - center).length();
+    let bounds = Box2D {
+        min: point(-20.0, -20.0),
+        max: point(20.0, 20.0),
+    };
+
+    // Coarse cells are enough for a precise result.
+    let options = PolygonizeOptions::cell_size(4.0).with_tolerance(0.0);
+    let path = polygonize(&bounds, circle, &options);
+    let mut num_points = 0;
+    for evt in path.iter() {
+        if let crate::path::PathEvent::Line { to, .. } = evt {
+            assert!(((to - center).length() - 10.0).abs() < 1e-6);
+            num_points += 1;
+        }
+    }
+    assert!(num_points > 16);
+
+    let area = signed_area(&path);
+    let expected = core::f64::consts::PI * 100.0;
+    assert!(area > 0.0);
+    assert!((area - expected).abs() < 0.05 * expected);
+
+    // Simplification removes vertices.
+    let simplified = polygonize(&bounds, circle, &options.with_tolerance(0.5));
+    assert!(simplified.iter().count() < path.iter().count());
+
+    // The shape is clipped to the bounds.
+    let bounds = Box2D {
+        min: point(0.0, 0.0),
+        max: point(10.0, 10.0),
+    };
+    let clipped = polygonize(&bounds, circle, &PolygonizeOptions::cell_size(0.5));
+    for evt in clipped.iter() {
+        if let crate::path::PathEvent::Line { to, .. } = evt {
+            assert!(to.x >= 0.0 && to.y >= 0.0 && to.x <= 10.0 && to.y <= 10.0);
+        }
+    }
+    assert!(signed_area(&clipped) > 90.0);
+}
+
+#[test]
+fn polygonize_ring() {
+    // A ring, with a hole.
+    let ring = |p: Point| 1.0 - ((p - point(0.0, 0.0)).length() - 6.0).abs();
+    let bounds = Box2D {
+        min: point(-10.0, -10.0),
+        max: point(10.0, 10.0),
+    };
+    let path = polygonize(&bounds, ring, &PolygonizeOptions::cell_size(0.5));
+
+    let sub_paths = path
+        .iter()
+        .filter(|evt| matches!(evt, crate::path::PathEvent::Begin { .. }))
+        .count();
+    assert_eq!(sub_paths, 2);
+
+    let expected = core::f64::consts::PI * (49.0 - 25.0);
+    assert!((signed_area(&path) - expected).abs() < 0.02 * expected);
+}
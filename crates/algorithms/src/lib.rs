@@ -16,14 +16,25 @@ pub extern crate lyon_path as path;
 
 pub mod aabb;
 pub mod area;
+pub mod chart;
 pub mod fit;
+pub mod frame;
 pub mod hatching;
 pub mod hit_test;
+pub mod implicit;
 pub mod length;
 pub mod measure;
+pub mod minkowski;
+pub mod morphology;
+pub mod packing;
+pub mod pattern;
+pub mod polygon;
+pub mod polylabel;
 pub mod raycast;
 pub mod rect;
 pub mod rounded_polygon;
+pub mod spline;
+pub mod trace;
 pub mod walk;
 pub mod winding;
 
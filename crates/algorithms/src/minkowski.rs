@@ -0,0 +1,373 @@
+//! Minkowski sum of convex polygons.
+//!
+//! The Minkowski sum of two shapes A and B is the set of all points `a + b` with `a` in A
+//! and `b` in B. It is typically used to inflate collision shapes or to compute the area
+//! covered by a pen shape stamped along a path.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::minkowski::minkowski_sum;
+//! use lyon_algorithms::geom::point;
+//!
+//! let square = [
+//!     point(0.0, 0.0),
+//!     point(1.0, 0.0),
+//!     point(1.0, 1.0),
+//!     point(0.0, 1.0),
+//! ];
+//! let triangle = [point(-1.0, 0.0), point(1.0, 0.0), point(0.0, 1.0)];
+//!
+//! let sum = minkowski_sum(&square, &triangle);
+//! assert_eq!(sum.len(), 6);
+//! ```
+
+use crate::math::{Point, Vector};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Computes the Minkowski sum of two convex polygons.
+///
+/// The polygons are implicitly closed. Their winding order does not matter.
+/// If one of the inputs is not convex, its convex hull is used instead, which
+/// produces the convex hull of the actual Minkowski sum.
+///
+/// The result is a convex polygon with a positive signed area (the same convention
+/// as `Winding::Positive`) without collinear vertices, starting at the vertex with
+/// the lowest y coordinate.
+///
+/// Returns an empty vector if any of the inputs is empty.
+pub fn minkowski_sum(a: &[Point], b: &[Point]) -> Vec<Point> {
+    let a = convex_input(a);
+    let b = convex_input(b);
+
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let n = a.len();
+    let m = b.len();
+    let mut result = Vec::with_capacity(n + m);
+
+    let mut i = 0;
+    let mut j = 0;
+    while i < n || j < m {
+        result.push(a[i % n] + b[j % m].to_vector());
+
+        if i == n {
+            j += 1;
+            continue;
+        }
+        if j == m {
+            i += 1;
+            continue;
+        }
+
+        let edge_a = a[(i + 1) % n] - a[i];
+        let edge_b = b[(j + 1) % m] - b[j];
+        let cross = edge_a.cross(edge_b);
+        if cross > 0.0 {
+            i += 1;
+        } else if cross < 0.0 {
+            j += 1;
+        } else {
+            i += 1;
+            j += 1;
+        }
+    }
+
+    remove_collinear_points(&mut result);
+
+    result
+}
+
+/// Computes the convex hull of a set of points.
+///
+/// The result has a positive signed area, does not contain collinear points and
+/// starts at the point with the lowest y coordinate (and lowest x coordinate in case
+/// of a tie).
+pub fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut sorted: Vec<Point> = points.to_vec();
+    sorted.sort_by(|a, b| compare_points(*a, *b));
+    sorted.dedup();
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Andrew's monotone chain algorithm.
+    let mut hull: Vec<Point> = Vec::with_capacity(sorted.len() + 1);
+    for p in &sorted {
+        while hull.len() >= 2 && !is_left_turn(&hull, *p) {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+
+    let lower_len = hull.len() + 1;
+    for p in sorted.iter().rev().skip(1) {
+        while hull.len() >= lower_len && !is_left_turn(&hull, *p) {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+
+    // The last point is the same as the first one.
+    hull.pop();
+
+    rotate_to_lowest_point(&mut hull);
+
+    hull
+}
+
+fn is_left_turn(hull: &[Point], p: Point) -> bool {
+    let a = hull[hull.len() - 2];
+    let b = hull[hull.len() - 1];
+
+    (b - a).cross(p - a) > 0.0
+}
+
+/// Returns whether a polygon is convex.
+///
+/// The polygon is implicitly closed. Collinear and duplicate consecutive points are
+/// accepted. Polygons with fewer than three distinct points are considered convex.
+pub fn is_convex(points: &[Point]) -> bool {
+    let n = points.len();
+    if n < 3 {
+        return true;
+    }
+
+    let mut sign: f64 = 0.0;
+    let mut total_angle: f64 = 0.0;
+    let mut prev_edge: Option<Vector> = None;
+    for i in 0..=n {
+        let edge = points[(i + 1) % n] - points[i % n];
+        if edge.square_length() == 0.0 {
+            continue;
+        }
+        if let Some(prev) = prev_edge {
+            let cross = prev.cross(edge);
+            if cross != 0.0 {
+                if sign * cross < 0.0 {
+                    return false;
+                }
+                sign = cross;
+            }
+            total_angle += prev.angle_to(edge).radians;
+        }
+        prev_edge = Some(edge);
+    }
+
+    // Self-intersecting shapes with consistent turns (for example a pentagram) turn
+    // more than once around.
+    (total_angle.abs() - 2.0 * core::f64::consts::PI).abs() < 1e-6
+}
+
+/// Copy the input into a convex polygon with positive winding order, starting at the
+/// lowest point.
+fn convex_input(points: &[Point]) -> Vec<Point> {
+    if !is_convex(points) {
+        return convex_hull(points);
+    }
+
+    let mut result: Vec<Point> = points.to_vec();
+    result.dedup();
+    while result.len() > 1 && result.first() == result.last() {
+        result.pop();
+    }
+
+    if signed_double_area(&result) < 0.0 {
+        result.reverse();
+    }
+
+    remove_collinear_points(&mut result);
+    rotate_to_lowest_point(&mut result);
+
+    result
+}
+
+fn signed_double_area(points: &[Point]) -> f64 {
+    let n = points.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        area += points[i].to_vector().cross(points[(i + 1) % n].to_vector());
+    }
+
+    area
+}
+
+fn remove_collinear_points(points: &mut Vec<Point>) {
+    let mut i = 0;
+    while points.len() >= 3 && i < points.len() {
+        let n = points.len();
+        let prev = points[(i + n - 1) % n];
+        let current = points[i];
+        let next = points[(i + 1) % n];
+        if (current - prev).cross(next - current) == 0.0 {
+            points.remove(i);
+            // Go back one step since the previous point may now be collinear.
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+fn rotate_to_lowest_point(points: &mut [Point]) {
+    let lowest = points
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| compare_points((**a).yx(), (**b).yx()))
+        .map(|(idx, _)| idx);
+
+    if let Some(idx) = lowest {
+        points.rotate_left(idx);
+    }
+}
+
+fn compare_points(a: Point, b: Point) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+#[cfg(test)]
+use crate::geom::point;
+
+#[test]
+fn minkowski_sum_squares() {
+    let a = [
+        point(0.0, 0.0),
+        point(1.0, 0.0),
+        point(1.0, 1.0),
+        point(0.0, 1.0),
+    ];
+    // Negative winding order.
+    let b = [
+        point(-1.0, -1.0),
+        point(-1.0, 1.0),
+        point(1.0, 1.0),
+        point(1.0, -1.0),
+    ];
+
+    let sum = minkowski_sum(&a, &b);
+
+    assert_eq!(
+        sum,
+        alloc::vec![
+            point(-1.0, -1.0),
+            point(2.0, -1.0),
+            point(2.0, 2.0),
+            point(-1.0, 2.0),
+        ]
+    );
+}
+
+#[test]
+fn minkowski_sum_square_triangle() {
+    let square = [
+        point(0.0, 0.0),
+        point(1.0, 0.0),
+        point(1.0, 1.0),
+        point(0.0, 1.0),
+    ];
+    let triangle = [point(-1.0, 0.0), point(1.0, 0.0), point(0.0, 1.0)];
+
+    let sum = minkowski_sum(&square, &triangle);
+
+    assert_eq!(
+        sum,
+        alloc::vec![
+            point(-1.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(1.0, 2.0),
+            point(0.0, 2.0),
+            point(-1.0, 1.0),
+        ]
+    );
+}
+
+#[test]
+fn minkowski_sum_point() {
+    let square = [
+        point(0.0, 0.0),
+        point(1.0, 0.0),
+        point(1.0, 1.0),
+        point(0.0, 1.0),
+    ];
+
+    let sum = minkowski_sum(&square, &[point(10.0, 5.0)]);
+
+    assert_eq!(
+        sum,
+        alloc::vec![
+            point(10.0, 5.0),
+            point(11.0, 5.0),
+            point(11.0, 6.0),
+            point(10.0, 6.0),
+        ]
+    );
+
+    assert!(minkowski_sum(&square, &[]).is_empty());
+}
+
+#[test]
+fn minkowski_sum_non_convex() {
+    // An L shape, its convex hull is used.
+    let l_shape = [
+        point(0.0, 0.0),
+        point(2.0, 0.0),
+        point(2.0, 1.0),
+        point(1.0, 1.0),
+        point(1.0, 2.0),
+        point(0.0, 2.0),
+    ];
+    assert!(!is_convex(&l_shape));
+
+    let sum = minkowski_sum(&l_shape, &[point(0.0, 0.0)]);
+
+    assert_eq!(
+        sum,
+        alloc::vec![
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(1.0, 2.0),
+            point(0.0, 2.0),
+        ]
+    );
+}
+
+#[test]
+fn convexity() {
+    let pentagram = [
+        point(0.0, -1.0),
+        point(0.588, 0.809),
+        point(-0.951, -0.309),
+        point(0.951, -0.309),
+        point(-0.588, 0.809),
+    ];
+    assert!(!is_convex(&pentagram));
+
+    let with_collinear = [
+        point(0.0, 0.0),
+        point(1.0, 0.0),
+        point(2.0, 0.0),
+        point(2.0, 2.0),
+        point(2.0, 2.0),
+        point(0.0, 2.0),
+    ];
+    assert!(is_convex(&with_collinear));
+
+    let hull = convex_hull(&with_collinear);
+    assert_eq!(
+        hull,
+        alloc::vec![
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 2.0),
+            point(0.0, 2.0),
+        ]
+    );
+}
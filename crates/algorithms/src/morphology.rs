@@ -0,0 +1,556 @@
+//! Morphological operations on the fill of a path.
+//!
+//! The fill is rasterized into a grid of samples, the morphological operation is
+//! applied to the samples with an exact euclidean distance transform, and the result
+//! is converted back into a path using marching squares followed by a simplification
+//! of the contours.
+//!
+//! Going through a raster representation limits the precision of the result to the
+//! size of the cells, but unlike offsetting the outline of the path directly, it is
+//! not affected by self-intersections, overlapping sub-paths or other defects
+//! that are common in imported shapes.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::morphology::{close, MorphologyOptions};
+//! use lyon_algorithms::math::{point, Box2D};
+//! use lyon_algorithms::path::{Path, PathEvent, Winding};
+//!
+//! // Two squares separated by a thin gap.
+//! let mut builder = Path::builder();
+//! builder.add_rectangle(&Box2D { min: point(0.0, 0.0), max: point(10.0, 10.0) }, Winding::Positive);
+//! builder.add_rectangle(&Box2D { min: point(10.2, 0.0), max: point(20.0, 10.0) }, Winding::Positive);
+//! let path = builder.build();
+//!
+//! // Closing the shape fills the gap, leaving a single sub-path.
+//! let result = close(&path, 0.5, &MorphologyOptions::cell_size(0.1));
+//! let sub_paths = result.iter().filter(|evt| matches!(evt, PathEvent::Begin { .. })).count();
+//! assert_eq!(sub_paths, 1);
+//! ```
+
+use crate::geom::LineSegment;
+use crate::math::{Box2D, Point};
+use crate::path::{iterator::PathIterator, FillRule, Path, PathEvent};
+use crate::trace::{simplify_contour, trace_contours};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// Parameters for the morphological operations.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct MorphologyOptions {
+    /// Distance between two samples of the grid.
+    ///
+    /// The result is within about one cell of the exact operation. Smaller cells
+    /// improve the precision at the cost of memory and computation time.
+    ///
+    /// Default value: `MorphologyOptions::DEFAULT_CELL_SIZE`.
+    pub cell_size: f64,
+    /// The fill rule used to rasterize the path.
+    ///
+    /// Default value: `FillRule::NonZero`, which fills overlapping sub-paths
+    /// regardless of their orientation.
+    pub fill_rule: FillRule,
+    /// Maximum allowed distance to the path when flattening its curves.
+    ///
+    /// Default value: `MorphologyOptions::DEFAULT_TOLERANCE`.
+    pub tolerance: f64,
+}
+
+impl Default for MorphologyOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl MorphologyOptions {
+    pub const DEFAULT_CELL_SIZE: f64 = 1.0;
+    pub const DEFAULT_TOLERANCE: f64 = 0.1;
+
+    pub const DEFAULT: Self = MorphologyOptions {
+        cell_size: Self::DEFAULT_CELL_SIZE,
+        fill_rule: FillRule::NonZero,
+        tolerance: Self::DEFAULT_TOLERANCE,
+    };
+
+    #[inline]
+    pub fn cell_size(cell_size: f64) -> Self {
+        Self::DEFAULT.with_cell_size(cell_size)
+    }
+
+    #[inline]
+    pub fn with_cell_size(mut self, cell_size: f64) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    #[inline]
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    #[inline]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// Grows the fill of a path by `radius` in every direction.
+///
+/// A negative radius shrinks the fill instead: `dilate(path, -r, options)` is
+/// `erode(path, r, options)`.
+///
+/// Sub-paths of the result have a positive signed area and holes have a negative
+/// one (see `Winding`), so the result can be filled with either fill rule.
+pub fn dilate<Iter>(path: Iter, radius: f64, options: &MorphologyOptions) -> Path
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    apply(path, &[Operation::Dilate(radius)], options)
+}
+
+/// Shrinks the fill of a path by `radius` in every direction.
+///
+/// Parts of the shape that are thinner than twice the radius disappear.
+///
+/// A negative radius grows the fill instead: `erode(path, -r, options)` is
+/// `dilate(path, r, options)`.
+///
+/// See `dilate` for the orientation of the resulting sub-paths.
+pub fn erode<Iter>(path: Iter, radius: f64, options: &MorphologyOptions) -> Path
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    apply(path, &[Operation::Erode(radius)], options)
+}
+
+/// Erodes then dilates the fill of a path.
+///
+/// This removes the parts of the shape that are thinner than twice the radius,
+/// such as spikes and small islands, and rounds convex corners without changing
+/// the rest of the shape.
+///
+/// With a negative radius this is `close` with the opposite radius.
+///
+/// See `dilate` for the orientation of the resulting sub-paths.
+pub fn open<Iter>(path: Iter, radius: f64, options: &MorphologyOptions) -> Path
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    apply(
+        path,
+        &[Operation::Erode(radius), Operation::Dilate(radius)],
+        options,
+    )
+}
+
+/// Dilates then erodes the fill of a path.
+///
+/// This fills the holes and gaps that are thinner than twice the radius, such as
+/// cracks and seams between almost adjacent sub-paths, and rounds concave corners
+/// without changing the rest of the shape.
+///
+/// With a negative radius this is `open` with the opposite radius.
+///
+/// See `dilate` for the orientation of the resulting sub-paths.
+pub fn close<Iter>(path: Iter, radius: f64, options: &MorphologyOptions) -> Path
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    apply(
+        path,
+        &[Operation::Dilate(radius), Operation::Erode(radius)],
+        options,
+    )
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Operation {
+    Dilate(f64),
+    Erode(f64),
+}
+
+impl Operation {
+    // Dilating by a negative radius is eroding by the opposite one, and vice versa.
+    fn normalized(self) -> Self {
+        match self {
+            Operation::Dilate(radius) if radius < 0.0 => Operation::Erode(-radius),
+            Operation::Erode(radius) if radius < 0.0 => Operation::Dilate(-radius),
+            op => op,
+        }
+    }
+}
+
+fn apply<Iter>(path: Iter, operations: &[Operation], options: &MorphologyOptions) -> Path
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let cell_size = options.cell_size;
+    assert!(cell_size > 0.0);
+
+    // Sub-paths are implicitly closed.
+    let mut edges = Vec::new();
+    for evt in path.into_iter().flattened(options.tolerance) {
+        match evt {
+            PathEvent::Line { from, to } => {
+                edges.push(LineSegment { from, to });
+            }
+            PathEvent::End { last, first, .. } if last != first => {
+                edges.push(LineSegment {
+                    from: last,
+                    to: first,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if edges.is_empty() {
+        return Path::new();
+    }
+
+    let mut bounds = Box2D {
+        min: edges[0].from,
+        max: edges[0].from,
+    };
+    for edge in &edges {
+        bounds.min = bounds.min.min(edge.to);
+        bounds.max = bounds.max.max(edge.to);
+    }
+
+    // Leave room for the dilations.
+    let radius: f64 = operations
+        .iter()
+        .map(|op| match op.normalized() {
+            Operation::Dilate(radius) => radius,
+            Operation::Erode(_) => 0.0,
+        })
+        .sum();
+    // The extra half cell places the samples in the middle of the cells when the
+    // edges of the shape are aligned with the grid, which avoids biasing the result.
+    let padding = radius + 2.5 * cell_size;
+    let origin = bounds.min - crate::math::vector(padding, padding);
+    let width = ((bounds.width() + 2.0 * padding) / cell_size).ceil() as usize + 1;
+    let height = ((bounds.height() + 2.0 * padding) / cell_size).ceil() as usize + 1;
+
+    let mut mask = Mask {
+        origin,
+        cell_size,
+        width,
+        height,
+        samples: rasterize(&edges, origin, cell_size, width, height, options.fill_rule),
+    };
+
+    for op in operations {
+        match op.normalized() {
+            Operation::Dilate(radius) => mask.dilate(radius / cell_size),
+            Operation::Erode(radius) => mask.erode(radius / cell_size),
+        }
+    }
+
+    mask.to_path()
+}
+
+// Samples the fill at the nodes of the grid.
+fn rasterize(
+    edges: &[LineSegment<f64>],
+    origin: Point,
+    cell_size: f64,
+    width: usize,
+    height: usize,
+    fill_rule: FillRule,
+) -> Vec<bool> {
+    let mut samples = vec![false; width * height];
+    let mut crossings: Vec<(f64, i32)> = Vec::new();
+    for y in 0..height {
+        let sample_y = origin.y + y as f64 * cell_size;
+
+        crossings.clear();
+        for edge in edges {
+            if (edge.from.y <= sample_y) != (edge.to.y <= sample_y) {
+                let t = (sample_y - edge.from.y) / (edge.to.y - edge.from.y);
+                let x = edge.from.x + (edge.to.x - edge.from.x) * t;
+                let winding = if edge.to.y > edge.from.y { 1 } else { -1 };
+                crossings.push((x, winding));
+            }
+        }
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        let mut winding = 0;
+        let mut next_crossing = crossings.iter().peekable();
+        for x in 0..width {
+            let sample_x = origin.x + x as f64 * cell_size;
+            while let Some(&&(crossing_x, w)) = next_crossing.peek() {
+                if crossing_x >= sample_x {
+                    break;
+                }
+                winding += w;
+                next_crossing.next();
+            }
+
+            samples[y * width + x] = match fill_rule {
+                FillRule::EvenOdd => winding % 2 != 0,
+                FillRule::NonZero => winding != 0,
+            };
+        }
+    }
+
+    samples
+}
+
+struct Mask {
+    origin: Point,
+    cell_size: f64,
+    width: usize,
+    height: usize,
+    samples: Vec<bool>,
+}
+
+impl Mask {
+    fn dilate(&mut self, radius: f64) {
+        let distances = squared_distances(&self.samples, self.width, self.height, true);
+        let max = radius * radius;
+        for (sample, d) in self.samples.iter_mut().zip(distances) {
+            *sample |= d <= max;
+        }
+    }
+
+    fn erode(&mut self, radius: f64) {
+        let distances = squared_distances(&self.samples, self.width, self.height, false);
+        let max = radius * radius;
+        for (sample, d) in self.samples.iter_mut().zip(distances) {
+            *sample &= d > max;
+        }
+    }
+
+    fn to_path(&self) -> Path {
+        let contours = trace_contours(
+            self.width,
+            self.height,
+            |idx| if self.samples[idx] { 1.0 } else { 0.0 },
+            0.5,
+        );
+
+        let mut builder = Path::builder();
+        for contour in contours {
+            let points: Vec<Point> = contour
+                .iter()
+                .map(|p| self.origin + p.to_vector() * self.cell_size)
+                .collect();
+            let points = simplify_contour(&points, self.cell_size * 0.5);
+            if points.len() < 3 {
+                continue;
+            }
+
+            builder.begin(points[0]);
+            for p in &points[1..] {
+                builder.line_to(*p);
+            }
+            builder.close();
+        }
+
+        builder.build()
+    }
+}
+
+/// Squared distance (in cells) from each sample of a grid to the closest sample with
+/// the provided value, using the separable algorithm described in "Distance Transforms
+/// of Sampled Functions" by Pedro F. Felzenszwalb and Daniel P. Huttenlocher.
+///
+/// Samples are stored row by row.
+pub(crate) fn squared_distances(
+    samples: &[bool],
+    width: usize,
+    height: usize,
+    value: bool,
+) -> Vec<f64> {
+    let (w, h) = (width, height);
+    // Larger than any distance on the grid while keeping the arithmetic exact.
+    let far = (w * w + h * h) as f64;
+
+    let mut distances: Vec<f64> = samples
+        .iter()
+        .map(|&sample| if sample == value { 0.0 } else { far })
+        .collect();
+
+    let n = w.max(h);
+    let mut f = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    let mut v = vec![0; n];
+    let mut z = vec![0.0; n + 1];
+
+    for x in 0..w {
+        for y in 0..h {
+            f[y] = distances[y * w + x];
+        }
+        distance_transform_1d(&f[..h], &mut d[..h], &mut v, &mut z);
+        for y in 0..h {
+            distances[y * w + x] = d[y];
+        }
+    }
+
+    for y in 0..h {
+        let row = &mut distances[y * w..(y + 1) * w];
+        f[..w].copy_from_slice(row);
+        distance_transform_1d(&f[..w], row, &mut v, &mut z);
+    }
+
+    distances
+}
+
+// Computes the lower envelope of the parabolas rooted at each sample.
+fn distance_transform_1d(f: &[f64], d: &mut [f64], v: &mut [usize], z: &mut [f64]) {
+    let n = f.len();
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+
+    let parabola = |q: usize| f[q] + (q * q) as f64;
+    for q in 1..n {
+        // z[0] is minus infinity so this stops at the first parabola.
+        let mut s = (parabola(q) - parabola(v[k])) / (2 * (q - v[k])) as f64;
+        while s <= z[k] {
+            k -= 1;
+            s = (parabola(q) - parabola(v[k])) / (2 * (q - v[k])) as f64;
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    }
+
+    let mut k = 0;
+    for (q, d) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let offset = q as f64 - v[k] as f64;
+        *d = offset * offset + f[v[k]];
+    }
+}
+
+#[cfg(test)]
+use crate::area::signed_area;
+#[cfg(test)]
+use crate::hit_test::hit_test_path;
+#[cfg(test)]
+use crate::math::point;
+#[cfg(test)]
+use crate::path::Winding;
+
+#[cfg(test)]
+fn rectangle(builder: &mut crate::path::path::Builder, min: Point, max: Point) {
+    builder.add_rectangle(&Box2D { min, max }, Winding::Positive);
+}
+
+#[test]
+fn dilate_and_erode_square() {
+    let mut builder = Path::builder();
+    rectangle(&mut builder, point(0.0, 0.0), point(10.0, 10.0));
+    let square = builder.build();
+    let options = MorphologyOptions::cell_size(0.05);
+
+    // The rounded corners cut (4 - PI) * r² from the expanded square.
+    let dilated = dilate(&square, 2.0, &options);
+    let expected = 14.0 * 14.0 - (4.0 - core::f64::consts::PI) * 4.0;
+    assert!((signed_area(&dilated) - expected).abs() < 0.01 * expected);
+    assert!(hit_test_path(
+        &point(-1.9, 5.0),
+        &dilated,
+        FillRule::EvenOdd,
+        0.1
+    ));
+    assert!(!hit_test_path(
+        &point(-1.6, -1.6),
+        &dilated,
+        FillRule::EvenOdd,
+        0.1
+    ));
+
+    let eroded = erode(&square, 2.0, &options);
+    assert!((signed_area(&eroded) - 36.0).abs() < 0.01 * 36.0);
+
+    // Negative radii swap the operations.
+    assert!(dilate(&square, -2.0, &options).iter().eq(eroded.iter()));
+    assert!(erode(&square, -2.0, &options).iter().eq(dilated.iter()));
+
+    assert!(erode(&square, 5.5, &options).iter().next().is_none());
+    assert!(dilate(&Path::new(), 1.0, &options).iter().next().is_none());
+}
+
+#[test]
+fn open_and_close() {
+    let options = MorphologyOptions::cell_size(0.05);
+
+    // A square with a thin spike and a separate tiny island.
+    let mut builder = Path::builder();
+    rectangle(&mut builder, point(0.0, 0.0), point(10.0, 10.0));
+    rectangle(&mut builder, point(10.0, 4.8), point(15.0, 5.2));
+    rectangle(&mut builder, point(20.0, 0.0), point(20.5, 0.5));
+    let path = builder.build();
+
+    let opened = open(&path, 0.5, &options);
+    assert!(!hit_test_path(
+        &point(12.0, 5.0),
+        &opened,
+        FillRule::EvenOdd,
+        0.1
+    ));
+    assert!(!hit_test_path(
+        &point(20.25, 0.25),
+        &opened,
+        FillRule::EvenOdd,
+        0.1
+    ));
+    // The rest of the square is preserved, except for its rounded corners.
+    let expected = 100.0 - (4.0 - core::f64::consts::PI) * 0.25;
+    assert!((signed_area(&opened) - expected).abs() < 0.01 * expected);
+
+    // A square with a hole, and another square separated by a thin gap.
+    let mut builder = Path::builder();
+    rectangle(&mut builder, point(0.0, 0.0), point(10.0, 10.0));
+    builder.add_rectangle(
+        &Box2D {
+            min: point(3.0, 3.0),
+            max: point(7.0, 7.0),
+        },
+        Winding::Negative,
+    );
+    rectangle(&mut builder, point(5.0, 10.3), point(10.0, 15.0));
+    let path = builder.build();
+
+    let closed = close(&path, 0.5, &options);
+    assert!(hit_test_path(
+        &point(7.0, 10.15),
+        &closed,
+        FillRule::NonZero,
+        0.1
+    ));
+    // The hole is too large to be filled.
+    assert!(!hit_test_path(
+        &point(5.0, 5.0),
+        &closed,
+        FillRule::NonZero,
+        0.1
+    ));
+    let sub_paths = closed
+        .iter()
+        .filter(|evt| matches!(evt, PathEvent::Begin { .. }))
+        .count();
+    assert_eq!(sub_paths, 2);
+
+    // The outline has a positive area and the hole a negative one.
+    let expected = 100.0 - 16.0 + 5.0 * 4.7 + 5.0 * 0.3;
+    assert!((signed_area(&closed) - expected).abs() < 0.01 * expected);
+}
@@ -0,0 +1,398 @@
+//! Arrange polygons into a rectangle without overlap.
+//!
+//! This is a best-effort irregular nesting algorithm, for example to build texture
+//! atlases of decals or laser-cutting layouts. The polygons are placed from the
+//! largest to the smallest. Each one is tried at positions next to the bounding boxes
+//! of the polygons that are already placed and at the corners of the rectangle, and is
+//! then pushed towards the minimum corner of the rectangle for as long as it doesn't
+//! collide with anything. Collisions are tested between the polygons themselves rather
+//! than their bounding boxes, so that concave shapes can interlock.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::packing::{pack_polygons, PackingOptions};
+//! use lyon_algorithms::math::{point, Box2D};
+//! use lyon_algorithms::path::Polygon;
+//!
+//! let triangle = [point(0.0, 0.0), point(10.0, 0.0), point(0.0, 10.0)];
+//! let polygons = [
+//!     Polygon { points: &triangle, closed: true },
+//!     Polygon { points: &triangle, closed: true },
+//! ];
+//!
+//! // The two triangles only fit if one of them is flipped.
+//! let bounds = Box2D { min: point(0.0, 0.0), max: point(10.5, 10.5) };
+//! let options = PackingOptions::spacing(0.1).with_rotations(2);
+//! let placements = pack_polygons(&polygons, &bounds, &options);
+//!
+//! assert!(placements.iter().all(|placement| placement.is_some()));
+//! ```
+
+use crate::geom::LineSegment;
+use crate::math::{point, vector, Angle, Box2D, Point, Transform, Vector};
+use crate::path::Polygon;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+/// Parameters for `pack_polygons`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct PackingOptions {
+    /// Minimum distance between two polygons and between the polygons and the
+    /// boundary of the rectangle.
+    ///
+    /// Default value: `0.0`.
+    pub spacing: f64,
+    /// Number of evenly spaced orientations to try for each polygon. For example
+    /// a value of four allows rotations by multiples of 90 degrees.
+    ///
+    /// Default value: `1` (the polygons are not rotated).
+    pub rotations: u32,
+}
+
+impl Default for PackingOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl PackingOptions {
+    pub const DEFAULT: Self = PackingOptions {
+        spacing: 0.0,
+        rotations: 1,
+    };
+
+    #[inline]
+    pub fn spacing(spacing: f64) -> Self {
+        Self::DEFAULT.with_spacing(spacing)
+    }
+
+    #[inline]
+    pub fn with_spacing(mut self, spacing: f64) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    #[inline]
+    pub fn with_rotations(mut self, rotations: u32) -> Self {
+        self.rotations = rotations;
+        self
+    }
+}
+
+/// Arranges polygons into a rectangle without overlap.
+///
+/// Returns, for each polygon, the transformation (a rotation followed by a
+/// translation) that moves it to its place in the rectangle, or `None` if it could
+/// not be placed.
+///
+/// This is a greedy algorithm that doesn't backtrack: it doesn't guarantee that the
+/// polygons are placed whenever a solution exists. Its cost grows with the square of
+/// the number of polygons and of their number of vertices.
+pub fn pack_polygons(
+    polygons: &[Polygon<Point>],
+    bounds: &Box2D,
+    options: &PackingOptions,
+) -> Vec<Option<Transform>> {
+    let spacing = options.spacing.max(0.0);
+    let inner = bounds.inflate(-spacing, -spacing);
+
+    // Place the largest polygons first.
+    let mut order: Vec<usize> = (0..polygons.len()).collect();
+    let areas: Vec<f64> = polygons.iter().map(|p| area(p.points)).collect();
+    order.sort_by(|a, b| {
+        areas[*b]
+            .partial_cmp(&areas[*a])
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+
+    let mut placed: Vec<Placed> = Vec::new();
+    let mut result = alloc::vec![None; polygons.len()];
+
+    for idx in order {
+        let points = polygons[idx].points;
+        if points.len() < 3 {
+            continue;
+        }
+
+        let mut best: Option<(Placed, Transform)> = None;
+        for r in 0..options.rotations.max(1) {
+            let angle = Angle::radians(2.0 * PI * r as f64 / options.rotations.max(1) as f64);
+            let rotation = Transform::rotation(angle);
+            let rotated: Vec<Point> = points
+                .iter()
+                .map(|p| rotation.transform_point(*p))
+                .collect();
+            let local_bounds = bounding_box(&rotated);
+            let size = local_bounds.size();
+            if size.width > inner.width() || size.height > inner.height() {
+                continue;
+            }
+
+            let shape = Shape {
+                points: &rotated,
+                bounds: local_bounds,
+            };
+
+            let mut candidates = Vec::with_capacity(4 + placed.len() * 4);
+            candidates.push(inner.min);
+            candidates.push(point(inner.max.x - size.width, inner.min.y));
+            candidates.push(point(inner.min.x, inner.max.y - size.height));
+            candidates.push(inner.max - size);
+            for other in &placed {
+                let right = other.bounds.max.x + spacing;
+                let top = other.bounds.max.y + spacing;
+                candidates.push(point(right, other.bounds.min.y));
+                candidates.push(point(other.bounds.min.x, top));
+                candidates.push(point(right, inner.min.y));
+                candidates.push(point(inner.min.x, top));
+            }
+
+            for candidate in candidates {
+                let offset = candidate - local_bounds.min;
+                if !fits(&shape, offset, &inner, &placed, spacing) {
+                    continue;
+                }
+
+                let offset = compact(&shape, offset, &inner, &placed, spacing);
+                let position = local_bounds.min + offset;
+                let is_better = match &best {
+                    Some((current, _)) => {
+                        let current = current.bounds.min;
+                        (position.y, position.x) < (current.y, current.x)
+                    }
+                    None => true,
+                };
+
+                if is_better {
+                    let points: Vec<Point> = rotated.iter().map(|p| *p + offset).collect();
+                    best = Some((
+                        Placed {
+                            bounds: local_bounds.translate(offset),
+                            points,
+                        },
+                        rotation.then_translate(offset),
+                    ));
+                }
+            }
+        }
+
+        if let Some((item, transform)) = best {
+            placed.push(item);
+            result[idx] = Some(transform);
+        }
+    }
+
+    result
+}
+
+struct Shape<'l> {
+    points: &'l [Point],
+    bounds: Box2D,
+}
+
+struct Placed {
+    points: Vec<Point>,
+    bounds: Box2D,
+}
+
+// Moves the shape towards the minimum corner of the rectangle with steps that are
+// halved every time they lead to a collision.
+fn compact(
+    shape: &Shape,
+    mut offset: Vector,
+    inner: &Box2D,
+    placed: &[Placed],
+    spacing: f64,
+) -> Vector {
+    let size = shape.bounds.size();
+    let mut step = size.width.max(size.height) * 0.5;
+    let min_step = step * 1e-3;
+    while step > min_step {
+        let mut moved = false;
+        for direction in &[vector(0.0, -1.0), vector(-1.0, 0.0)] {
+            let next = offset + *direction * step;
+            if fits(shape, next, inner, placed, spacing) {
+                offset = next;
+                moved = true;
+            }
+        }
+
+        if !moved {
+            step *= 0.5;
+        }
+    }
+
+    offset
+}
+
+fn fits(shape: &Shape, offset: Vector, inner: &Box2D, placed: &[Placed], spacing: f64) -> bool {
+    let bounds = shape.bounds.translate(offset);
+    if bounds.min.x < inner.min.x
+        || bounds.min.y < inner.min.y
+        || bounds.max.x > inner.max.x
+        || bounds.max.y > inner.max.y
+    {
+        return false;
+    }
+
+    placed.iter().all(|other| {
+        let inflated = other.bounds.inflate(spacing, spacing);
+        if !inflated.intersects(&bounds) {
+            return true;
+        }
+
+        !collide(shape.points, offset, &other.points, spacing)
+    })
+}
+
+// Whether two polygons overlap or are closer than the spacing.
+fn collide(a: &[Point], offset: Vector, b: &[Point], spacing: f64) -> bool {
+    let edge = |points: &[Point], i: usize, offset: Vector| LineSegment {
+        from: points[i] + offset,
+        to: points[(i + 1) % points.len()] + offset,
+    };
+
+    for i in 0..a.len() {
+        let ea = edge(a, i, offset);
+        for j in 0..b.len() {
+            let eb = edge(b, j, Vector::zero());
+            if ea.intersects(&eb) {
+                return true;
+            }
+            if spacing > 0.0
+                && (ea.distance_to_point(eb.from) < spacing
+                    || ea.distance_to_point(eb.to) < spacing
+                    || eb.distance_to_point(ea.from) < spacing
+                    || eb.distance_to_point(ea.to) < spacing)
+            {
+                return true;
+            }
+        }
+    }
+
+    // No edges cross, the polygons overlap if one contains the other.
+    contains(b, a[0] + offset) || contains(a, b[0] - offset)
+}
+
+// Even-odd point in polygon test.
+fn contains(polygon: &[Point], p: Point) -> bool {
+    let mut inside = false;
+    for i in 0..polygon.len() {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % polygon.len()];
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+fn area(points: &[Point]) -> f64 {
+    let mut area = 0.0;
+    for i in 0..points.len() {
+        area += points[i]
+            .to_vector()
+            .cross(points[(i + 1) % points.len()].to_vector());
+    }
+
+    (area * 0.5).abs()
+}
+
+fn bounding_box(points: &[Point]) -> Box2D {
+    let mut min = points[0];
+    let mut max = points[0];
+    for p in &points[1..] {
+        min = min.min(*p);
+        max = max.max(*p);
+    }
+
+    Box2D { min, max }
+}
+
+#[test]
+fn pack_shapes() {
+    let square = [
+        point(0.0, 0.0),
+        point(10.0, 0.0),
+        point(10.0, 10.0),
+        point(0.0, 10.0),
+    ];
+    let triangle = [point(0.0, 0.0), point(8.0, 0.0), point(4.0, 6.0)];
+    let l_shape = [
+        point(0.0, 0.0),
+        point(12.0, 0.0),
+        point(12.0, 4.0),
+        point(4.0, 4.0),
+        point(4.0, 12.0),
+        point(0.0, 12.0),
+    ];
+
+    let mut polygons = Vec::new();
+    for _ in 0..3 {
+        polygons.push(Polygon {
+            points: &square[..],
+            closed: true,
+        });
+        polygons.push(Polygon {
+            points: &triangle[..],
+            closed: true,
+        });
+        polygons.push(Polygon {
+            points: &l_shape[..],
+            closed: true,
+        });
+    }
+
+    let bounds = Box2D {
+        min: point(0.0, 0.0),
+        max: point(40.0, 40.0),
+    };
+    let spacing = 1.0;
+    let options = PackingOptions::spacing(spacing).with_rotations(4);
+    let placements = pack_polygons(&polygons, &bounds, &options);
+
+    let mut transformed = Vec::new();
+    for (polygon, placement) in polygons.iter().zip(placements.iter()) {
+        let transform = placement.unwrap();
+        let points: Vec<Point> = polygon
+            .points
+            .iter()
+            .map(|p| transform.transform_point(*p))
+            .collect();
+        for p in &points {
+            assert!(bounds
+                .inflate(-spacing * 0.99, -spacing * 0.99)
+                .contains(*p));
+        }
+        transformed.push(points);
+    }
+
+    for i in 0..transformed.len() {
+        for j in 0..i {
+            assert!(!collide(
+                &transformed[i],
+                Vector::zero(),
+                &transformed[j],
+                spacing * 0.99
+            ));
+        }
+    }
+
+    // Too large.
+    let big = [point(0.0, 0.0), point(50.0, 0.0), point(0.0, 5.0)];
+    let placements = pack_polygons(
+        &[Polygon {
+            points: &big,
+            closed: true,
+        }],
+        &bounds,
+        &PackingOptions::DEFAULT,
+    );
+    assert_eq!(placements, alloc::vec![None]);
+}
@@ -0,0 +1,356 @@
+//! Fill a region with a repeating pattern.
+//!
+//! Rather than tessellating the pattern over the whole region, the content of a
+//! single tile is tessellated once and drawn several times, typically with
+//! instanced rendering. This module computes the transforms of the instances of
+//! the tile that are needed to cover an arbitrary region.
+//!
+//! The instances that are not fully inside of the region overlap its boundary.
+//! They must be clipped by the renderer (for example using a stencil or a mask
+//! made from the tessellation of the region).
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::pattern::{tile_instances, TilePattern};
+//! use lyon_algorithms::geom::{point, Box2D};
+//! use lyon_algorithms::math::Transform;
+//! use lyon_algorithms::path::{FillRule, Polygon};
+//!
+//! let region = [
+//!     point(0.0, 0.0),
+//!     point(100.0, 0.0),
+//!     point(100.0, 100.0),
+//!     point(0.0, 100.0),
+//! ];
+//!
+//! // 10x10 tiles, the content of the tile is contained in the unit square.
+//! let pattern = TilePattern::new(Transform::scale(10.0, 10.0));
+//!
+//! let instances = tile_instances(
+//!     Polygon { points: &region, closed: true }.path_events(),
+//!     FillRule::NonZero,
+//!     &pattern,
+//!     0.01,
+//! );
+//!
+//! assert_eq!(instances.len(), 100);
+//! assert!(instances.iter().all(|instance| instance.fully_inside));
+//! ```
+
+use crate::geom::LineSegment;
+use crate::hit_test::hit_test_path;
+use crate::math::{point, Box2D, Point, Transform};
+use crate::path::{iterator::PathIterator, FillRule, PathEvent};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// The maximum number of tiles in the grid covering the bounding box of a region.
+///
+/// See `tile_instances`.
+pub const MAX_TILES: usize = 1 << 20;
+
+/// Describes how a tile is repeated.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TilePattern {
+    /// Transformation from the tile space to the space of the region.
+    ///
+    /// In tile space, the tile at position `(i, j)` in the grid is obtained by
+    /// translating the tile by `(i, j)`. The transform can scale, rotate or skew the
+    /// pattern but must be invertible.
+    pub transform: Transform,
+    /// The bounds of the content of the tile in tile space.
+    ///
+    /// It is usually the unit square, but the content of a tile may overlap its
+    /// neighbors.
+    pub tile_bounds: Box2D,
+}
+
+impl TilePattern {
+    /// A pattern with tiles of content contained in the unit square.
+    pub fn new(transform: Transform) -> Self {
+        TilePattern {
+            transform,
+            tile_bounds: Box2D {
+                min: point(0.0, 0.0),
+                max: point(1.0, 1.0),
+            },
+        }
+    }
+
+    pub fn with_tile_bounds(mut self, tile_bounds: Box2D) -> Self {
+        self.tile_bounds = tile_bounds;
+        self
+    }
+}
+
+/// An instance of the tile.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TileInstance {
+    /// Transformation from tile space to the space of the region for this instance.
+    pub transform: Transform,
+    /// Position of the instance in the grid.
+    pub index: (i32, i32),
+    /// Whether the content of the instance is entirely inside of the region, in which
+    /// case it does not need to be clipped.
+    pub fully_inside: bool,
+}
+
+/// Computes the instances of a tile pattern that overlap a region.
+///
+/// Curves are flattened using the provided tolerance and the inside of the
+/// region is determined with the provided fill rule.
+///
+/// Returns an empty vector if the transform of the pattern is not invertible, or
+/// if more than `MAX_TILES` tiles are needed to cover the bounding box of the
+/// region.
+pub fn tile_instances<Iter>(
+    region: Iter,
+    fill_rule: FillRule,
+    pattern: &TilePattern,
+    tolerance: f64,
+) -> Vec<TileInstance>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut instances = Vec::new();
+
+    let inverse = match pattern.transform.inverse() {
+        Some(inverse) => inverse,
+        None => return instances,
+    };
+
+    let events: Vec<PathEvent> = region.into_iter().flattened(tolerance).collect();
+    let mut edges = Vec::new();
+    for evt in &events {
+        match *evt {
+            PathEvent::Line { from, to } => {
+                edges.push(LineSegment { from, to });
+            }
+            PathEvent::End { last, first, .. } => {
+                edges.push(LineSegment {
+                    from: last,
+                    to: first,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if edges.is_empty() {
+        return instances;
+    }
+
+    // Range of tiles overlapping the bounding box of the region, in tile space.
+    let bounds = Box2D::from_points(edges.iter().map(|edge| edge.from));
+    let tile_space_bounds = Box2D::from_points([
+        inverse.transform_point(bounds.min),
+        inverse.transform_point(point(bounds.max.x, bounds.min.y)),
+        inverse.transform_point(bounds.max),
+        inverse.transform_point(point(bounds.min.x, bounds.max.y)),
+    ]);
+    let tile = pattern.tile_bounds;
+    let orientation = pattern.transform.determinant().signum();
+    let min_i = (tile_space_bounds.min.x - tile.max.x).floor();
+    let max_i = (tile_space_bounds.max.x - tile.min.x).ceil();
+    let min_j = (tile_space_bounds.min.y - tile.max.y).floor();
+    let max_j = (tile_space_bounds.max.y - tile.min.y).ceil();
+
+    // This also rejects non-finite bounds, for which the number of tiles is NaN.
+    let num_tiles = (max_i - min_i + 1.0) * (max_j - min_j + 1.0);
+    let in_range = |v: f64| v >= i32::MIN as f64 && v <= i32::MAX as f64;
+    if num_tiles.is_nan()
+        || num_tiles > MAX_TILES as f64
+        || ![min_i, max_i, min_j, max_j].iter().all(|v| in_range(*v))
+    {
+        return instances;
+    }
+    let (min_i, max_i, min_j, max_j) = (min_i as i32, max_i as i32, min_j as i32, max_j as i32);
+
+    for j in min_j..=max_j {
+        for i in min_i..=max_i {
+            let transform = Transform::translation(i as f64, j as f64).then(&pattern.transform);
+            let quad = [
+                transform.transform_point(tile.min),
+                transform.transform_point(point(tile.max.x, tile.min.y)),
+                transform.transform_point(tile.max),
+                transform.transform_point(point(tile.min.x, tile.max.y)),
+            ];
+
+            let overlaps_boundary = edges
+                .iter()
+                .any(|edge| goes_through_quad(&quad, orientation, edge));
+
+            // If the boundary of the region does not go through the tile, the tile is
+            // either entirely inside or entirely outside.
+            let fully_inside = !overlaps_boundary && {
+                let center = transform.transform_point(tile.center());
+                hit_test_path(&center, events.iter().cloned(), fill_rule, tolerance)
+            };
+
+            if overlaps_boundary || fully_inside {
+                instances.push(TileInstance {
+                    transform,
+                    index: (i, j),
+                    fully_inside,
+                });
+            }
+        }
+    }
+
+    instances
+}
+
+// Whether part of the segment is strictly inside of a convex quadrilateral.
+//
+// Clips the segment with the half-planes formed by each side of the quadrilateral.
+// Segments that only touch the sides or the corners are not considered inside.
+fn goes_through_quad(quad: &[Point; 4], orientation: f64, edge: &LineSegment<f64>) -> bool {
+    let d = edge.to_vector();
+    let mut t_min = 0.0;
+    let mut t_max = 1.0;
+    for k in 0..4 {
+        let side = quad[(k + 1) % 4] - quad[k];
+        // The signed distance to the side is c0 + t * c1 along the segment.
+        let c0 = orientation * side.cross(edge.from - quad[k]);
+        let c1 = orientation * side.cross(d);
+        if c1 == 0.0 {
+            if c0 <= 0.0 {
+                return false;
+            }
+        } else if c1 > 0.0 {
+            t_min = f64::max(t_min, -c0 / c1);
+        } else {
+            t_max = f64::min(t_max, -c0 / c1);
+        }
+    }
+
+    t_min < t_max
+}
+
+#[test]
+fn rotated_pattern() {
+    use crate::path::Polygon;
+
+    let region = [
+        point(0.0, 0.0),
+        point(50.0, 0.0),
+        point(50.0, 30.0),
+        point(0.0, 30.0),
+    ];
+    let pattern =
+        TilePattern::new(Transform::scale(7.0, 7.0).then_rotate(crate::math::Angle::degrees(30.0)));
+
+    let instances = tile_instances(
+        Polygon {
+            points: &region,
+            closed: true,
+        }
+        .path_events(),
+        FillRule::EvenOdd,
+        &pattern,
+        0.01,
+    );
+
+    // Sample the region and check that each sample is covered by an instance.
+    for y in 0..30 {
+        for x in 0..50 {
+            let p = point(x as f64 + 0.5, y as f64 + 0.5);
+            let covered = instances.iter().any(|instance| {
+                let local = instance.transform.inverse().unwrap().transform_point(p);
+                pattern.tile_bounds.contains(local)
+            });
+            assert!(covered, "{:?} is not covered", p);
+        }
+    }
+
+    for instance in &instances {
+        let center = instance.transform.transform_point(point(0.5, 0.5));
+        if instance.fully_inside {
+            assert!(center.x > 0.0 && center.x < 50.0 && center.y > 0.0 && center.y < 30.0);
+        }
+        // Instances don't go too far from the region.
+        assert!(center.x > -10.0 && center.x < 60.0 && center.y > -10.0 && center.y < 40.0);
+    }
+
+    assert!(instances.iter().any(|instance| instance.fully_inside));
+    assert!(instances.iter().any(|instance| !instance.fully_inside));
+}
+
+#[test]
+fn pattern_with_holes() {
+    let mut builder = crate::path::Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(40.0, 0.0));
+    builder.line_to(point(40.0, 40.0));
+    builder.line_to(point(0.0, 40.0));
+    builder.close();
+    builder.begin(point(10.0, 10.0));
+    builder.line_to(point(30.0, 10.0));
+    builder.line_to(point(30.0, 30.0));
+    builder.line_to(point(10.0, 30.0));
+    builder.close();
+    // A small island in a single tile.
+    builder.begin(point(62.0, 2.0));
+    builder.line_to(point(64.0, 2.0));
+    builder.line_to(point(64.0, 4.0));
+    builder.close();
+    let path = builder.build();
+
+    let pattern = TilePattern::new(Transform::scale(10.0, 10.0));
+    let instances = tile_instances(&path, FillRule::EvenOdd, &pattern, 0.01);
+
+    // The ring is 12 tiles, plus the island.
+    assert_eq!(instances.len(), 13);
+    assert!(instances
+        .iter()
+        .all(|instance| instance.fully_inside != (instance.index == (6, 0))));
+    assert!(!instances.iter().any(|instance| instance.index == (1, 1)));
+
+    // A tile that overflows its cell touches the hole.
+    let pattern = pattern.with_tile_bounds(Box2D {
+        min: point(-0.5, -0.5),
+        max: point(1.5, 1.5),
+    });
+    let instances = tile_instances(&path, FillRule::EvenOdd, &pattern, 0.01);
+    assert!(instances.iter().all(|instance| !instance.fully_inside));
+}
+
+#[test]
+fn too_many_tiles() {
+    use crate::path::Polygon;
+
+    let region = [
+        point(0.0, 0.0),
+        point(1e6, 0.0),
+        point(1e6, 1e6),
+        point(0.0, 1e6),
+    ];
+    let region = Polygon {
+        points: &region,
+        closed: true,
+    };
+
+    let pattern = TilePattern::new(Transform::scale(1.0, 1.0));
+    assert!(tile_instances(region.path_events(), FillRule::NonZero, &pattern, 0.01).is_empty());
+
+    let pattern = TilePattern::new(Transform::scale(1e4, 1e4));
+    let instances = tile_instances(region.path_events(), FillRule::NonZero, &pattern, 0.01);
+    assert_eq!(instances.len(), 100 * 100);
+
+    // Far away from the origin, the indices of the tiles don't fit in an i32.
+    let square = [
+        point(0.0, 0.0),
+        point(10.0, 0.0),
+        point(10.0, 10.0),
+        point(0.0, 10.0),
+    ];
+    let square = Polygon {
+        points: &square,
+        closed: true,
+    };
+    let pattern = TilePattern::new(Transform::translation(-1e10, 0.0));
+    assert!(tile_instances(square.path_events(), FillRule::NonZero, &pattern, 0.01).is_empty());
+}
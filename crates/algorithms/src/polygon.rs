@@ -0,0 +1,24 @@
+//! Polygons with holes.
+
+use crate::math::Point;
+use crate::path::iterator::FromPolyline;
+use crate::path::PathEvent;
+use alloc::vec::Vec;
+
+/// A polygon with holes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ComplexPolygon {
+    /// The outline of the polygon, with a positive signed area (see `Winding`).
+    pub exterior: Vec<Point>,
+    /// The holes, with a negative signed area.
+    pub holes: Vec<Vec<Point>>,
+}
+
+impl ComplexPolygon {
+    /// Iterates over the outline and the holes as closed sub-paths.
+    pub fn path_events(&self) -> impl Iterator<Item = PathEvent> + '_ {
+        core::iter::once(&self.exterior)
+            .chain(self.holes.iter())
+            .flat_map(|points| FromPolyline::closed(points.iter().cloned()))
+    }
+}
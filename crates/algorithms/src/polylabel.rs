@@ -0,0 +1,264 @@
+//! Find the pole of inaccessibility of a shape.
+//!
+//! The pole of inaccessibility is the interior point that is the farthest away from
+//! the boundary of the shape. It is the center of the largest inscribed circle and
+//! is typically used to place labels inside of polygons, since unlike the centroid
+//! it is guaranteed to be inside of the shape.
+//!
+//! This is an implementation of the "polylabel" algorithm described by Vladimir
+//! Agafonkin in <https://github.com/mapbox/polylabel>.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::polylabel::pole_of_inaccessibility;
+//! use lyon_algorithms::geom::point;
+//! use lyon_algorithms::path::Polygon;
+//!
+//! let u_shape = [
+//!     point(0.0, 0.0),
+//!     point(3.0, 0.0),
+//!     point(3.0, 3.0),
+//!     point(2.0, 3.0),
+//!     point(2.0, 1.0),
+//!     point(1.0, 1.0),
+//!     point(1.0, 3.0),
+//!     point(0.0, 3.0),
+//! ];
+//! let polygon = Polygon { points: &u_shape, closed: true };
+//!
+//! let (position, distance) = pole_of_inaccessibility(polygon.path_events(), 0.01).unwrap();
+//! // The label goes at the bottom of the U rather than in the hole in its middle.
+//! assert!(position.y < 1.0);
+//! assert!(distance > 0.4);
+//! ```
+
+use crate::aabb::fast_bounding_box;
+use crate::geom::LineSegment;
+use crate::math::{point, Box2D, Point};
+use crate::path::{iterator::PathIterator, PathEvent};
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+// The minimum tolerance used to flatten curves, relative to the size of the shape.
+const MIN_RELATIVE_TOLERANCE: f64 = 1e-6;
+
+/// Compute the pole of inaccessibility of a path.
+///
+/// Returns the position of the pole and its distance to the boundary of the shape.
+///
+/// The result is within `precision` of the actual pole. Curves are flattened using the
+/// same value as tolerance, but no less than a millionth of the size of the shape.
+/// Sub-paths are implicitly closed and the inside of the shape is determined with the
+/// even-odd fill rule, so sub-paths can be used to represent holes.
+///
+/// Returns `None` if the path is empty or has a null area.
+pub fn pole_of_inaccessibility<Iter>(path: Iter, precision: f64) -> Option<(Point, f64)>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    // Flattening needs a positive tolerance and produces a lot of edges for tiny ones,
+    // so the tolerance is clamped relative to the size of the shape.
+    let events: Vec<PathEvent> = path.into_iter().collect();
+    let size = fast_bounding_box(events.iter().cloned()).size();
+    let tolerance = precision.max(MIN_RELATIVE_TOLERANCE * size.width.max(size.height));
+
+    let mut edges: Vec<LineSegment<f64>> = Vec::new();
+    for evt in events.into_iter().flattened(tolerance) {
+        match evt {
+            PathEvent::Line { from, to } => {
+                edges.push(LineSegment { from, to });
+            }
+            PathEvent::End { last, first, .. } => {
+                edges.push(LineSegment {
+                    from: last,
+                    to: first,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let bounds = Box2D::from_points(edges.iter().map(|edge| edge.from));
+    let size = bounds.size();
+    let cell_size = size.width.min(size.height);
+    if edges.is_empty() || cell_size <= 0.0 {
+        return None;
+    }
+
+    let precision = precision.max(f64::EPSILON * size.width.max(size.height));
+
+    let mut queue = BinaryHeap::new();
+    let half = cell_size * 0.5;
+    let mut y = bounds.min.y;
+    while y < bounds.max.y {
+        let mut x = bounds.min.x;
+        while x < bounds.max.x {
+            queue.push(Cell::new(point(x + half, y + half), half, &edges));
+            x += cell_size;
+        }
+        y += cell_size;
+    }
+
+    // The center of the bounding box is a good first guess for simple shapes.
+    let mut best = Cell::new(bounds.center(), 0.0, &edges);
+
+    while let Some(cell) = queue.pop() {
+        if cell.distance > best.distance {
+            best = cell;
+        }
+
+        // Don't explore cells that can't contain a better solution.
+        if cell.max_distance - best.distance <= precision {
+            continue;
+        }
+
+        let half = cell.half_size * 0.5;
+        for &(dx, dy) in &[(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+            let center = point(cell.center.x + dx * half, cell.center.y + dy * half);
+            queue.push(Cell::new(center, half, &edges));
+        }
+    }
+
+    if best.distance <= 0.0 {
+        return None;
+    }
+
+    Some((best.center, best.distance))
+}
+
+#[derive(Copy, Clone)]
+struct Cell {
+    center: Point,
+    half_size: f64,
+    // Signed distance from the center to the boundary, negative outside of the shape.
+    distance: f64,
+    // Maximum distance to the boundary a point in this cell can have.
+    max_distance: f64,
+}
+
+impl Cell {
+    fn new(center: Point, half_size: f64, edges: &[LineSegment<f64>]) -> Self {
+        let distance = signed_distance(center, edges);
+        Cell {
+            center,
+            half_size,
+            distance,
+            max_distance: distance + half_size * core::f64::consts::SQRT_2,
+        }
+    }
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_distance == other.max_distance
+    }
+}
+
+impl Eq for Cell {}
+
+impl PartialOrd for Cell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.max_distance
+            .partial_cmp(&other.max_distance)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+fn signed_distance(p: Point, edges: &[LineSegment<f64>]) -> f64 {
+    let mut inside = false;
+    let mut min_sq_distance = f64::MAX;
+
+    for edge in edges {
+        let (a, b) = (edge.from, edge.to);
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+
+        min_sq_distance = min_sq_distance.min(edge.square_distance_to_point(p));
+    }
+
+    let distance = min_sq_distance.sqrt();
+    if inside {
+        distance
+    } else {
+        -distance
+    }
+}
+
+#[test]
+fn square_pole() {
+    use crate::path::Polygon;
+
+    let points = [
+        point(0.0, 0.0),
+        point(4.0, 0.0),
+        point(4.0, 4.0),
+        point(0.0, 4.0),
+    ];
+    let polygon = Polygon {
+        points: &points,
+        closed: true,
+    };
+
+    let (position, distance) = pole_of_inaccessibility(polygon.path_events(), 0.001).unwrap();
+    assert!((position - point(2.0, 2.0)).length() < 0.01);
+    assert!((distance - 2.0).abs() < 0.001);
+}
+
+#[test]
+fn pole_with_hole() {
+    let mut builder = crate::path::Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 4.0));
+    builder.line_to(point(0.0, 4.0));
+    builder.close();
+    // A hole that covers the left part of the rectangle.
+    builder.begin(point(1.0, 1.0));
+    builder.line_to(point(1.0, 3.0));
+    builder.line_to(point(5.0, 3.0));
+    builder.line_to(point(5.0, 1.0));
+    builder.close();
+    let path = builder.build();
+
+    let (position, distance) = pole_of_inaccessibility(&path, 0.001).unwrap();
+    assert!(position.x > 5.0);
+    assert!((distance - 2.0).abs() < 0.01);
+}
+
+#[test]
+fn curved_pole() {
+    let mut builder = crate::path::Path::builder();
+    builder.add_circle(point(1.0, 2.0), 3.0, crate::path::Winding::Positive);
+    let path = builder.build();
+
+    let (position, distance) = pole_of_inaccessibility(&path, 0.0).unwrap();
+    assert!((position - point(1.0, 2.0)).length() < 0.01);
+    assert!((distance - 3.0).abs() < 0.01);
+}
+
+#[test]
+fn degenerate_pole() {
+    let mut builder = crate::path::Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.close();
+    let path = builder.build();
+
+    assert_eq!(pole_of_inaccessibility(&path, 0.01), None);
+    assert_eq!(
+        pole_of_inaccessibility(&crate::path::Path::new(), 0.01),
+        None
+    );
+}
@@ -1,8 +1,9 @@
-//! Find the first collision between a ray and a path.
+//! Find the collisions between a ray and paths.
 
 use crate::geom::{CubicBezierSegment, Line, LineSegment, QuadraticBezierSegment};
 use crate::math::{point, vector, Point, Vector};
 use crate::path::PathEvent;
+use alloc::vec::Vec;
 
 pub struct Ray {
     pub origin: Point,
@@ -94,6 +95,124 @@ where
     })
 }
 
+/// An intersection between a ray and one of the paths passed to `raycast_paths`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PathHit {
+    /// Position of the intersection along the ray, such that the position is
+    /// `ray.origin + ray.direction * t`.
+    pub t: f64,
+    pub position: Point,
+    /// Unit normal of the path at the intersection, facing the origin of the ray.
+    pub normal: Vector,
+    /// Index of the intersected path.
+    pub path_index: usize,
+}
+
+/// Find all intersections between a ray and a set of paths, sorted by distance
+/// from the origin of the ray.
+///
+/// Unlike `raycast_path`, curves are intersected exactly instead of being flattened.
+/// Intersections are searched in the `[0, 1[` range of each segment so that an
+/// endpoint shared by two line segments is only reported once. Rounding errors
+/// may still produce duplicate or missing hits when the ray goes exactly through
+/// the endpoint of a curve.
+pub fn raycast_paths<Paths, Iter>(ray: &Ray, paths: Paths) -> Vec<PathHit>
+where
+    Paths: IntoIterator<Item = Iter>,
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut hits = Vec::new();
+
+    let ray_len = ray.direction.square_length();
+    if ray_len == 0.0 || ray_len.is_nan() {
+        return hits;
+    }
+
+    let line = Line {
+        point: ray.origin,
+        vector: ray.direction,
+    };
+
+    for (path_index, path) in paths.into_iter().enumerate() {
+        // Intersections are considered in the [0, 1[ range along each segment, so
+        // that shared endpoints are only reported once.
+        let mut add_hit = |t: f64, position: Point, tangent: Vector| {
+            if t >= 1.0 {
+                return;
+            }
+            let ray_t = (position - ray.origin).dot(ray.direction) / ray_len;
+            if ray_t < 0.0 {
+                return;
+            }
+            let mut normal = vector(-tangent.y, tangent.x);
+            if normal.dot(ray.direction) > 0.0 {
+                normal = -normal;
+            }
+            hits.push(PathHit {
+                t: ray_t,
+                position,
+                normal: normal.normalize(),
+                path_index,
+            });
+        };
+
+        for evt in path {
+            match evt {
+                PathEvent::Begin { .. } => {}
+                PathEvent::Line { from, to } => {
+                    let segment = LineSegment { from, to };
+                    if let Some(t) = segment.line_intersection_t(&line) {
+                        add_hit(t, segment.sample(t), segment.to_vector());
+                    }
+                }
+                PathEvent::End { last, first, .. } => {
+                    let segment = LineSegment {
+                        from: last,
+                        to: first,
+                    };
+                    if let Some(t) = segment.line_intersection_t(&line) {
+                        add_hit(t, segment.sample(t), segment.to_vector());
+                    }
+                }
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    let curve = QuadraticBezierSegment { from, ctrl, to };
+                    for t in curve.line_intersections_t(&line) {
+                        let mut tangent = curve.derivative(t);
+                        if tangent.square_length() == 0.0 {
+                            tangent = to - from;
+                        }
+                        add_hit(t, curve.sample(t), tangent);
+                    }
+                }
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    let curve = CubicBezierSegment {
+                        from,
+                        ctrl1,
+                        ctrl2,
+                        to,
+                    };
+                    for t in curve.line_intersections_t(&line) {
+                        let mut tangent = curve.derivative(t);
+                        if tangent.square_length() == 0.0 {
+                            tangent = to - from;
+                        }
+                        add_hit(t, curve.sample(t), tangent);
+                    }
+                }
+            }
+        }
+    }
+
+    hits.sort_by(|a, b| a.t.partial_cmp(&b.t).unwrap_or(core::cmp::Ordering::Equal));
+
+    hits
+}
+
 struct RayCastInner {
     ray: Line<f64>,
     min_dot: f64,
@@ -182,3 +301,57 @@ fn test_raycast() {
     .unwrap();
     assert!(hit.position.approx_eq(&point(1.0, 0.0)));
 }
+
+#[test]
+fn test_raycast_paths() {
+    use crate::path::{Path, Winding};
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(1.0, 0.0));
+    builder.line_to(point(1.0, 1.0));
+    builder.line_to(point(0.0, 1.0));
+    builder.end(true);
+    let square = builder.build();
+
+    let mut builder = Path::builder();
+    builder.add_circle(point(5.0, 0.5), 1.0, Winding::Positive);
+    let circle = builder.build();
+
+    let ray = Ray {
+        origin: point(-1.0, 0.7),
+        direction: vector(2.0, 0.0),
+    };
+    let hits = raycast_paths(&ray, [&square, &circle]);
+
+    assert_eq!(hits.len(), 4);
+    let dx = (1.0f64 - 0.2 * 0.2).sqrt();
+    let expected = [(0.0, 0), (1.0, 0), (5.0 - dx, 1), (5.0 + dx, 1)];
+    for (hit, &(x, path_index)) in hits.iter().zip(expected.iter()) {
+        assert!((hit.position - point(x, 0.7)).length() < 1e-3);
+        assert!((hit.t - (hit.position.x + 1.0) / 2.0).abs() < 1e-9);
+        assert!(hit.normal.dot(ray.direction) < 0.0);
+        assert!((hit.normal.length() - 1.0).abs() < 1e-9);
+        assert_eq!(hit.path_index, path_index);
+    }
+    assert_eq!(hits[0].normal, vector(-1.0, 0.0));
+
+    // Going through a corner only hits once.
+    let ray = Ray {
+        origin: point(-1.0, -1.0),
+        direction: vector(1.0, 1.0),
+    };
+    let hits = raycast_paths(&ray, [&square]);
+    assert_eq!(hits.len(), 2);
+    assert!((hits[0].position - point(0.0, 0.0)).length() < 1e-9);
+    assert!((hits[1].position - point(1.0, 1.0)).length() < 1e-9);
+
+    // Nothing behind the origin.
+    let ray = Ray {
+        origin: point(0.5, 0.7),
+        direction: vector(1.0, 0.0),
+    };
+    let hits = raycast_paths(&ray, [&square, &circle]);
+    assert_eq!(hits.len(), 3);
+    assert_eq!(hits[0].path_index, 0);
+}
@@ -1,13 +1,28 @@
 use lyon_path::{
     geom::{euclid, Angle, Vector},
     traits::PathBuilder,
-    ArcFlags, Attributes, Polygon, Winding,
+    ArcFlags, Attributes, Path, Polygon, Winding, NO_ATTRIBUTES,
 };
 
 pub type Point = euclid::default::Point2D<f64>;
 
+/// Builds a path from a polygon, replacing each corner with a circular arc.
+///
+/// The radius is clamped at each corner to half of the shortest adjacent edge
+/// so that the arcs of two neighboring corners never overlap.
+///
+/// If the polygon is not closed, its first and last points are kept as is.
+pub fn round_corners(polygon: Polygon<Point>, radius: f64) -> Path {
+    let mut builder = Path::builder();
+    add_rounded_polygon(&mut builder, polygon, radius, NO_ATTRIBUTES);
+
+    builder.build()
+}
+
 /// Adds a sub-path from a polygon but rounds the corners.
 ///
+/// If the polygon is not closed, its first and last points are not rounded.
+///
 /// There must be no sub-path in progress when this method is called.
 /// No sub-path is in progress after the method is called.
 pub fn add_rounded_polygon<B: PathBuilder>(
@@ -20,6 +35,11 @@ pub fn add_rounded_polygon<B: PathBuilder>(
         return;
     }
 
+    if !polygon.closed {
+        add_rounded_polyline(builder, polygon.points, radius, attributes);
+        return;
+    }
+
     //p points are original polygon points
     //q points are the actual points we will draw lines and arcs between
     let clamped_radius = clamp_radius(
@@ -66,6 +86,42 @@ pub fn add_rounded_polygon<B: PathBuilder>(
     builder.end(polygon.closed);
 }
 
+fn add_rounded_polyline<B: PathBuilder>(
+    builder: &mut B,
+    points: &[Point],
+    radius: f64,
+    attributes: Attributes,
+) {
+    builder.begin(points[0], attributes);
+
+    for window in points.windows(3) {
+        let (p_previous, p_current, p_next) = (window[0], window[1], window[2]);
+
+        let clamped_radius = clamp_radius(radius, p_previous, p_current, p_next);
+        let q1 = get_point_between(p_current, p_previous, clamped_radius);
+        let q2 = get_point_between(p_current, p_next, clamped_radius);
+
+        builder.line_to(q1, attributes);
+        let turn_winding = get_winding(p_previous, p_current, p_next);
+
+        arc(
+            builder,
+            Vector::new(clamped_radius, clamped_radius),
+            Angle { radians: 0.0 },
+            ArcFlags {
+                large_arc: false,
+                sweep: turn_winding == Winding::Negative,
+            },
+            q1,
+            q2,
+            attributes,
+        );
+    }
+
+    builder.line_to(points[points.len() - 1], attributes);
+    builder.end(false);
+}
+
 fn clamp_radius(radius: f64, p_previous: Point, p_current: Point, p_next: Point) -> f64 {
     let shorter_edge = ((p_current - p_next).length()).min((p_previous - p_current).length());
 
@@ -74,6 +130,9 @@ fn clamp_radius(radius: f64, p_previous: Point, p_current: Point, p_next: Point)
 
 fn get_point_between(p1: Point, p2: Point, radius: f64) -> Point {
     let dist = p1.distance_to(p2);
+    if dist == 0.0 {
+        return p1;
+    }
     let ratio = radius / dist;
 
     p1.lerp(p2, ratio)
@@ -207,3 +266,65 @@ fn rounded_polygon() {
         previous = e.to();
     }
 }
+
+#[test]
+fn round_corners_open_polyline() {
+    use crate::geom::point;
+
+    let points = [point(0.0, 0.0), point(2.0, 0.0), point(2.0, 2.0)];
+    let path = round_corners(
+        Polygon {
+            points: &points,
+            closed: false,
+        },
+        0.5,
+    );
+
+    let mut first = None;
+    let mut last = None;
+    let mut num_curves = 0;
+    for event in path.iter() {
+        match event {
+            path::Event::Begin { at } => first = Some(at),
+            path::Event::Quadratic { .. } => num_curves += 1,
+            path::Event::End { last: l, close, .. } => {
+                assert!(!close);
+                last = Some(l);
+            }
+            _ => {}
+        }
+    }
+
+    // The end points are preserved and only the middle corner is rounded.
+    assert_eq!(first, Some(points[0]));
+    assert_eq!(last, Some(points[2]));
+    assert!(num_curves > 0);
+}
+
+#[test]
+fn round_corners_clamped_radius() {
+    use crate::geom::point;
+
+    let points = [
+        point(0.0, 0.0),
+        point(1.0, 0.0),
+        point(1.0, 1.0),
+        point(0.0, 1.0),
+    ];
+
+    // A radius larger than half of the edge length is clamped so that the
+    // result is a circle of radius 0.5.
+    let path = round_corners(
+        Polygon {
+            points: &points,
+            closed: true,
+        },
+        10.0,
+    );
+
+    for event in path.iter() {
+        let p = event.to();
+        let d = p.distance_to(point(0.5, 0.5));
+        assert!((d - 0.5).abs() < 0.01, "{:?} {:?}", p, d);
+    }
+}
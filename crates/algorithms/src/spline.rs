@@ -0,0 +1,299 @@
+//! Build smooth paths from sequences of points.
+//!
+//! Splines are often specified with points rather than with the control points
+//! of bézier curves. The functions in this module convert them into cubic bézier
+//! curves.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::spline::add_catmull_rom;
+//! use lyon_algorithms::geom::point;
+//! use lyon_algorithms::path::{Path, Polygon, NO_ATTRIBUTES};
+//!
+//! let points = [
+//!     point(0.0, 0.0),
+//!     point(1.0, 1.0),
+//!     point(2.0, 0.0),
+//!     point(3.0, 1.0),
+//! ];
+//!
+//! let mut builder = Path::builder();
+//! add_catmull_rom(
+//!     &mut builder,
+//!     Polygon { points: &points, closed: false },
+//!     NO_ATTRIBUTES,
+//! );
+//! let path = builder.build();
+//!
+//! // One cubic bézier curve between each pair of consecutive points.
+//! assert_eq!(path.iter().count(), 5);
+//! ```
+
+use crate::math::Point;
+use crate::path::{traits::PathBuilder, Attributes, Polygon};
+
+/// Adds a sub-path going through each point of a polygon with a uniform Catmull-Rom spline.
+///
+/// Each pair of consecutive points is connected with a cubic bézier curve. The tangent
+/// at each point is parallel to the segment between its two neighbors. If the polygon
+/// is not closed, the tangents at the first and last points point towards their only
+/// neighbor.
+///
+/// There must be no sub-path in progress when this method is called.
+/// No sub-path is in progress after the method is called.
+pub fn add_catmull_rom<B: PathBuilder>(
+    builder: &mut B,
+    polygon: Polygon<Point>,
+    attributes: Attributes,
+) {
+    let points = polygon.points;
+    let n = points.len();
+    if n == 0 {
+        return;
+    }
+
+    builder.begin(points[0], attributes);
+
+    let num_segments = if polygon.closed { n } else { n - 1 };
+    if n >= 2 {
+        for i in 0..num_segments {
+            let (p0, p1, p2, p3) = if polygon.closed {
+                (
+                    points[(i + n - 1) % n],
+                    points[i],
+                    points[(i + 1) % n],
+                    points[(i + 2) % n],
+                )
+            } else {
+                (
+                    points[i.saturating_sub(1)],
+                    points[i],
+                    points[i + 1],
+                    points[(i + 2).min(n - 1)],
+                )
+            };
+
+            let ctrl1 = p1 + (p2 - p0) / 6.0;
+            let ctrl2 = p2 - (p3 - p1) / 6.0;
+            builder.cubic_bezier_to(ctrl1, ctrl2, p2, attributes);
+        }
+    }
+
+    builder.end(polygon.closed);
+}
+
+/// Adds a sub-path approximating the points of a polygon with a uniform cubic B-spline.
+///
+/// The points are the control points of the spline, the curve does not go through them.
+/// If the polygon is not closed, the first and last points are repeated so that the
+/// curve starts and ends at them.
+///
+/// There must be no sub-path in progress when this method is called.
+/// No sub-path is in progress after the method is called.
+pub fn add_b_spline<B: PathBuilder>(
+    builder: &mut B,
+    polygon: Polygon<Point>,
+    attributes: Attributes,
+) {
+    let points = polygon.points;
+    let n = points.len();
+    if n == 0 {
+        return;
+    }
+
+    // Index the control points, repeating the endpoints of open splines twice
+    // to clamp the curve to them.
+    let num_points = if polygon.closed { n } else { n + 4 };
+    let point_at = |i: usize| {
+        if polygon.closed {
+            points[i % n]
+        } else {
+            points[i.saturating_sub(2).min(n - 1)]
+        }
+    };
+
+    let first = b_spline_point(point_at(0), point_at(1), point_at(2));
+    builder.begin(first, attributes);
+
+    let num_segments = if polygon.closed { n } else { num_points - 3 };
+    if n >= 2 {
+        for i in 0..num_segments {
+            let p1 = point_at(i + 1);
+            let p2 = point_at(i + 2);
+            let p3 = point_at(i + 3);
+
+            let ctrl1 = p1.lerp(p2, 1.0 / 3.0);
+            let ctrl2 = p1.lerp(p2, 2.0 / 3.0);
+            let to = b_spline_point(p1, p2, p3);
+            builder.cubic_bezier_to(ctrl1, ctrl2, to, attributes);
+        }
+    }
+
+    builder.end(polygon.closed);
+}
+
+// Position of the spline at the knot associated to `p1`.
+fn b_spline_point(p0: Point, p1: Point, p2: Point) -> Point {
+    ((p0.to_vector() + p1.to_vector() * 4.0 + p2.to_vector()) / 6.0).to_point()
+}
+
+#[cfg(test)]
+use crate::geom::{point, CubicBezierSegment};
+#[cfg(test)]
+use crate::path::{Path, PathEvent, NO_ATTRIBUTES};
+#[cfg(test)]
+use alloc::vec::Vec;
+
+#[cfg(test)]
+fn cubics(path: &Path) -> Vec<CubicBezierSegment<f64>> {
+    path.iter()
+        .filter_map(|evt| match evt {
+            PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => Some(CubicBezierSegment {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn catmull_rom_interpolates_points() {
+    let points = [
+        point(0.0, 0.0),
+        point(1.0, 2.0),
+        point(3.0, 2.0),
+        point(4.0, 0.0),
+    ];
+
+    let mut builder = Path::builder();
+    add_catmull_rom(
+        &mut builder,
+        Polygon {
+            points: &points,
+            closed: false,
+        },
+        NO_ATTRIBUTES,
+    );
+    let curves = cubics(&builder.build());
+
+    assert_eq!(curves.len(), 3);
+    for (i, curve) in curves.iter().enumerate() {
+        assert_eq!(curve.from, points[i]);
+        assert_eq!(curve.to, points[i + 1]);
+    }
+
+    // The tangent at an interior point is parallel to the segment between its neighbors.
+    let tangent = curves[0].derivative(1.0);
+    assert!(tangent.cross(points[2] - points[0]).abs() < 1e-12);
+    // The curve is smooth.
+    assert!((curves[0].derivative(1.0) - curves[1].derivative(0.0)).length() < 1e-12);
+
+    let mut builder = Path::builder();
+    add_catmull_rom(
+        &mut builder,
+        Polygon {
+            points: &points,
+            closed: true,
+        },
+        NO_ATTRIBUTES,
+    );
+    let curves = cubics(&builder.build());
+    assert_eq!(curves.len(), 4);
+    assert_eq!(curves[3].to, points[0]);
+    assert!((curves[3].derivative(1.0) - curves[0].derivative(0.0)).length() < 1e-12);
+}
+
+#[test]
+fn b_spline() {
+    let points = [
+        point(0.0, 0.0),
+        point(10.0, 0.0),
+        point(10.0, 10.0),
+        point(0.0, 10.0),
+    ];
+
+    let mut builder = Path::builder();
+    add_b_spline(
+        &mut builder,
+        Polygon {
+            points: &points,
+            closed: true,
+        },
+        NO_ATTRIBUTES,
+    );
+    let curves = cubics(&builder.build());
+
+    assert_eq!(curves.len(), 4);
+    for i in 0..4 {
+        // Consecutive curves are continuous up to the second derivative.
+        let next = &curves[(i + 1) % 4];
+        assert!((curves[i].to - next.from).length() < 1e-12);
+        assert!((curves[i].derivative(1.0) - next.derivative(0.0)).length() < 1e-12);
+        assert!((curves[i].second_derivative(1.0) - next.second_derivative(0.0)).length() < 1e-12);
+    }
+    // The curve does not go through the control points.
+    assert!((curves[0].from - point(50.0 / 6.0, 10.0 / 6.0)).length() < 1e-12);
+
+    let mut builder = Path::builder();
+    add_b_spline(
+        &mut builder,
+        Polygon {
+            points: &points,
+            closed: false,
+        },
+        NO_ATTRIBUTES,
+    );
+    let curves = cubics(&builder.build());
+    assert_eq!(curves.first().unwrap().from, points[0]);
+    assert_eq!(curves.last().unwrap().to, points[3]);
+    for i in 0..(curves.len() - 1) {
+        assert!((curves[i].to - curves[i + 1].from).length() < 1e-12);
+        assert!((curves[i].derivative(1.0) - curves[i + 1].derivative(0.0)).length() < 1e-12);
+    }
+}
+
+#[test]
+fn degenerate_splines() {
+    let mut builder = Path::builder();
+    add_catmull_rom(
+        &mut builder,
+        Polygon {
+            points: &[],
+            closed: false,
+        },
+        NO_ATTRIBUTES,
+    );
+    add_b_spline(
+        &mut builder,
+        Polygon {
+            points: &[point(1.0, 2.0)],
+            closed: true,
+        },
+        NO_ATTRIBUTES,
+    );
+    let path = builder.build();
+
+    let events: Vec<PathEvent> = path.iter().collect();
+    assert_eq!(
+        events,
+        alloc::vec![
+            PathEvent::Begin {
+                at: point(1.0, 2.0)
+            },
+            PathEvent::End {
+                last: point(1.0, 2.0),
+                first: point(1.0, 2.0),
+                close: true
+            },
+        ]
+    );
+}
@@ -0,0 +1,739 @@
+//! Convert bitmaps into polygons.
+//!
+//! The contours are extracted with marching squares, which places the vertices on
+//! the segments between the centers of neighboring pixels, interpolating the pixel
+//! values to find where they cross the threshold. Compared to following the edges of
+//! the pixels this cuts the corners of the pixel grid, and with anti-aliased masks or
+//! signed distance fields it recovers sub-pixel positions. The contours are then
+//! simplified with the Ramer-Douglas-Peucker algorithm.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_algorithms::trace::{trace_bitmap, Bitmap};
+//!
+//! // A ring.
+//! let pixels: [u8; 25] = [
+//!     0,   0,   0,   0, 0,
+//!     0, 255, 255, 255, 0,
+//!     0, 255,   0, 255, 0,
+//!     0, 255, 255, 255, 0,
+//!     0,   0,   0,   0, 0,
+//! ];
+//! let bitmap = Bitmap { data: &pixels, width: 5, height: 5 };
+//!
+//! let polygons = trace_bitmap(&bitmap, 128.0, 0.01);
+//! assert_eq!(polygons.len(), 1);
+//! assert_eq!(polygons[0].holes.len(), 1);
+//!
+//! // The polygons can be filled directly.
+//! let _events = polygons[0].path_events();
+//! ```
+
+use crate::area::signed_area;
+use crate::geom::LineSegment;
+use crate::hit_test::hit_test_path;
+use crate::math::{point, vector, Point};
+use crate::morphology::squared_distances;
+use crate::path::iterator::FromPolyline;
+use crate::path::FillRule;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+pub use crate::polygon::ComplexPolygon;
+
+/// A view over the pixels of a single channel image, stored row by row.
+#[derive(Copy, Clone, Debug)]
+pub struct Bitmap<'l, T> {
+    pub data: &'l [T],
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Traces the contours of the pixels of a bitmap that are greater than or equal
+/// to a threshold.
+///
+/// The pixel at column `x` and row `y` is centered on `(x + 0.5, y + 0.5)`, so the
+/// polygons use the pixel grid as coordinate system. Pixels outside of the bitmap are
+/// considered outside of the shape. Diagonally adjacent pixels are connected if the
+/// average of the four pixels around their common corner is above the threshold.
+///
+/// The contours are simplified with the provided tolerance, in pixels.
+///
+/// For signed distance fields that are negative inside of the shape, negate the
+/// values and the threshold.
+pub fn trace_bitmap<T>(bitmap: &Bitmap<T>, threshold: f64, tolerance: f64) -> Vec<ComplexPolygon>
+where
+    T: Copy + Into<f64>,
+{
+    assert!(bitmap.data.len() >= bitmap.width * bitmap.height);
+
+    let mut exteriors = Vec::new();
+    let mut holes = Vec::new();
+    let contours = trace_contours(
+        bitmap.width,
+        bitmap.height,
+        |idx| bitmap.data[idx].into(),
+        threshold,
+    );
+    for mut contour in contours {
+        for p in &mut contour {
+            *p += vector(0.5, 0.5);
+        }
+        let area = signed_area(ring(&contour));
+        if area > 0.0 {
+            exteriors.push((area, contour));
+        } else if area < 0.0 {
+            holes.push(contour);
+        }
+    }
+
+    let mut polygons: Vec<ComplexPolygon> = exteriors
+        .iter()
+        .map(|(_, exterior)| ComplexPolygon {
+            exterior: simplify_contour(exterior, tolerance),
+            holes: Vec::new(),
+        })
+        .collect();
+
+    // Each hole belongs to the smallest exterior that contains it. The contours don't
+    // intersect, so testing a single point is enough.
+    for hole in holes {
+        let mut container = None;
+        let mut min_area = f64::INFINITY;
+        for (idx, (area, exterior)) in exteriors.iter().enumerate() {
+            if *area < min_area && hit_test_path(&hole[0], ring(exterior), FillRule::EvenOdd, 0.1) {
+                container = Some(idx);
+                min_area = *area;
+            }
+        }
+
+        let hole = simplify_contour(&hole, tolerance);
+        if let (Some(idx), true) = (container, hole.len() >= 3) {
+            polygons[idx].holes.push(hole);
+        }
+    }
+
+    polygons.retain(|polygon| polygon.exterior.len() >= 3);
+
+    polygons
+}
+
+/// Extracts the iso-contours of a scalar field at several thresholds.
+///
+/// Returns the polygons enclosing the values greater than or equal to each threshold,
+/// in the same order as the thresholds. This works like `trace_bitmap`, and the
+/// polygons use the same coordinate system.
+///
+/// The regions of higher thresholds are nested in the regions of lower thresholds,
+/// within the simplification tolerance. Filling the levels from the lowest threshold
+/// to the highest, each on top of the previous ones, produces a contour map.
+pub fn iso_contours<T>(
+    field: &Bitmap<T>,
+    thresholds: &[f64],
+    tolerance: f64,
+) -> Vec<Vec<ComplexPolygon>>
+where
+    T: Copy + Into<f64>,
+{
+    thresholds
+        .iter()
+        .map(|&threshold| trace_bitmap(field, threshold, tolerance))
+        .collect()
+}
+
+/// Parameters for `sprite_outline`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpriteOutlineOptions {
+    /// Pixels with an alpha value strictly greater than this threshold are covered
+    /// by the outline.
+    ///
+    /// Default value: `0.0`, which covers all pixels that are not fully transparent.
+    pub alpha_threshold: f64,
+    /// Minimum distance in pixels between the covered pixels and the outline.
+    ///
+    /// A margin of a pixel or two avoids clipping the pixels that are sampled with
+    /// bilinear filtering.
+    ///
+    /// Default value: `SpriteOutlineOptions::DEFAULT_DILATION`.
+    pub dilation: f64,
+    /// Maximum number of vertices of the outlines, holes included.
+    ///
+    /// Default value: `SpriteOutlineOptions::DEFAULT_MAX_VERTICES`.
+    pub max_vertices: usize,
+}
+
+impl Default for SpriteOutlineOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl SpriteOutlineOptions {
+    pub const DEFAULT_DILATION: f64 = 1.0;
+    pub const DEFAULT_MAX_VERTICES: usize = 32;
+
+    pub const DEFAULT: Self = SpriteOutlineOptions {
+        alpha_threshold: 0.0,
+        dilation: Self::DEFAULT_DILATION,
+        max_vertices: Self::DEFAULT_MAX_VERTICES,
+    };
+
+    #[inline]
+    pub fn max_vertices(max_vertices: usize) -> Self {
+        Self::DEFAULT.with_max_vertices(max_vertices)
+    }
+
+    #[inline]
+    pub fn with_max_vertices(mut self, max_vertices: usize) -> Self {
+        self.max_vertices = max_vertices;
+        self
+    }
+
+    #[inline]
+    pub fn with_dilation(mut self, dilation: f64) -> Self {
+        self.dilation = dilation;
+        self
+    }
+
+    #[inline]
+    pub fn with_alpha_threshold(mut self, alpha_threshold: f64) -> Self {
+        self.alpha_threshold = alpha_threshold;
+        self
+    }
+}
+
+/// Computes polygons that tightly cover the visible pixels of a sprite.
+///
+/// Filling the polygons (for example with the fill tessellator) instead of a quad
+/// reduces the overdraw of sprites with large transparent areas. The texture
+/// coordinates of a vertex are its position divided by the size of the bitmap.
+///
+/// The polygons use the pixel grid as coordinate system, like `trace_bitmap`. They
+/// contain the whole area of the visible pixels, dilated by `options.dilation`, and
+/// may extend past the bounds of the bitmap. The contours are simplified as much as
+/// needed to fit in the vertex budget, growing the margin accordingly. The bounding
+/// rectangle of the dilated visible pixels is returned instead if it is smaller than
+/// the simplified outline, or if the budget can't be met (for example when it is
+/// less than four).
+///
+/// Returns an empty vector if there is no visible pixel.
+pub fn sprite_outline<T>(alpha: &Bitmap<T>, options: &SpriteOutlineOptions) -> Vec<ComplexPolygon>
+where
+    T: Copy + Into<f64>,
+{
+    let (width, height) = (alpha.width, alpha.height);
+    assert!(alpha.data.len() >= width * height);
+
+    let dilation = options.dilation.max(0.0);
+    let max_tolerance = (width.max(height) as f64).min(64.0);
+
+    // The samples of the distance field are at the center of the pixels, so the
+    // outline has to be a bit farther than the dilation to contain the corners of the
+    // visible pixels, and to absorb the error of the interpolation between samples.
+    let margin = dilation + core::f64::consts::FRAC_1_SQRT_2 + 0.5;
+    let padding = (margin + max_tolerance).ceil() as usize + 2;
+
+    let w = width + 2 * padding;
+    let h = height + 2 * padding;
+    let mut visible = vec![false; w * h];
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+    for y in 0..height {
+        for x in 0..width {
+            if alpha.data[y * width + x].into() > options.alpha_threshold {
+                visible[(y + padding) * w + x + padding] = true;
+                bounds = Some(match bounds {
+                    Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                    None => (x, y, x, y),
+                });
+            }
+        }
+    }
+
+    let (x0, y0, x1, y1) = match bounds {
+        Some(bounds) => bounds,
+        None => {
+            return Vec::new();
+        }
+    };
+
+    let min = point(x0 as f64 - dilation, y0 as f64 - dilation);
+    let max = point(x1 as f64 + 1.0 + dilation, y1 as f64 + 1.0 + dilation);
+    let rectangle = vec![ComplexPolygon {
+        exterior: vec![min, point(max.x, min.y), max, point(min.x, max.y)],
+        holes: Vec::new(),
+    }];
+    let rectangle_area = (max.x - min.x) * (max.y - min.y);
+
+    if options.max_vertices >= 4 {
+        let distances: Vec<f64> = squared_distances(&visible, w, h, true)
+            .iter()
+            .map(|d| -d.sqrt())
+            .collect();
+
+        let mut tolerance = 0.5;
+        while tolerance <= max_tolerance {
+            // Simplifying the contours moves them by up to the tolerance, so they are
+            // traced that much farther from the visible pixels. The distances are
+            // negated so that the inside is above the threshold.
+            let offset = margin + tolerance;
+            let polygons = trace_bitmap(
+                &Bitmap {
+                    data: &distances,
+                    width: w,
+                    height: h,
+                },
+                -offset,
+                tolerance,
+            );
+            let num_vertices: usize = polygons
+                .iter()
+                .map(|polygon| {
+                    polygon.exterior.len() + polygon.holes.iter().map(Vec::len).sum::<usize>()
+                })
+                .sum();
+
+            if num_vertices <= options.max_vertices {
+                // Larger tolerances would only make the outline larger.
+                let area: f64 = polygons
+                    .iter()
+                    .map(|polygon| signed_area(polygon.path_events()))
+                    .sum();
+                if area >= rectangle_area {
+                    break;
+                }
+
+                let shift = vector(padding as f64, padding as f64);
+                return polygons
+                    .into_iter()
+                    .map(|polygon| ComplexPolygon {
+                        exterior: polygon.exterior.iter().map(|p| *p - shift).collect(),
+                        holes: polygon
+                            .holes
+                            .iter()
+                            .map(|hole| hole.iter().map(|p| *p - shift).collect())
+                            .collect(),
+                    })
+                    .collect();
+            }
+
+            tolerance *= 2.0;
+        }
+    }
+
+    rectangle
+}
+
+fn ring(points: &[Point]) -> FromPolyline<impl Iterator<Item = Point> + '_> {
+    FromPolyline::closed(points.iter().cloned())
+}
+
+/// Extracts the contours of the samples of a grid that are greater than or equal to
+/// the threshold, using marching squares.
+///
+/// The sample at `(x, y)` is `sample(y * width + x)` and is positioned at `(x, y)`.
+/// Samples outside of the grid are outside of the shape. The inside is on the left
+/// of the contours, so that outlines have a positive signed area and holes a negative
+/// one.
+pub(crate) fn trace_contours(
+    width: usize,
+    height: usize,
+    sample: impl Fn(usize) -> f64,
+    threshold: f64,
+) -> Vec<Vec<Point>> {
+    // The grid is padded with a row or column of outside samples on each side.
+    let w = width + 2;
+    let h = height + 2;
+    let value = |x: usize, y: usize| {
+        if x == 0 || y == 0 || x > width || y > height {
+            None
+        } else {
+            Some(sample((y - 1) * width + x - 1))
+        }
+    };
+    let is_inside = |v: Option<f64>| matches!(v, Some(v) if v >= threshold);
+
+    // The contour points are on the edges of the grid that separate an inside sample
+    // from an outside one. Each grid edge has an id: `2 * sample` for the edge to the
+    // right of the sample and `2 * sample + 1` for the edge below it.
+    let none = u32::MAX;
+    let mut next = vec![none; 2 * w * h];
+
+    for y in 0..(h - 1) {
+        for x in 0..(w - 1) {
+            let values = [
+                value(x, y),
+                value(x + 1, y),
+                value(x + 1, y + 1),
+                value(x, y + 1),
+            ];
+            let inside = values.map(is_inside);
+            let s = y * w + x;
+            // The grid edges between consecutive corners.
+            let edges = [2 * s, 2 * (s + 1) + 1, 2 * (s + w), 2 * s + 1];
+
+            let leaving = |k: usize| inside[k] && !inside[(k + 1) % 4];
+            let entering = |k: usize| !inside[k] && inside[(k + 1) % 4];
+
+            // In the ambiguous cases where the inside corners are on a diagonal, the
+            // center of the square decides whether they are connected.
+            let saddle = inside[0] == inside[2] && inside[1] == inside[3] && inside[0] != inside[1];
+            let connected = saddle
+                && match values {
+                    [Some(a), Some(b), Some(c), Some(d)] => (a + b + c + d) * 0.25 >= threshold,
+                    _ => false,
+                };
+
+            for l in 0..4 {
+                if !leaving(l) {
+                    continue;
+                }
+                // Link each edge leaving the inside to the edge entering it, so that
+                // the inside is on the left of the contour.
+                for i in 1..4 {
+                    let k = if connected {
+                        (l + i) % 4
+                    } else {
+                        (l + 4 - i) % 4
+                    };
+                    if entering(k) {
+                        next[edges[l]] = edges[k] as u32;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    let crossing = |id: usize| {
+        let s = id / 2;
+        let (x, y) = (s % w, s / w);
+        let (x2, y2) = if id % 2 == 1 { (x, y + 1) } else { (x + 1, y) };
+        let t = match (value(x, y), value(x2, y2)) {
+            (Some(a), Some(b)) if a != b => ((threshold - a) / (b - a)).clamp(0.0, 1.0),
+            _ => 0.5,
+        };
+
+        point(x as f64 - 1.0, y as f64 - 1.0).lerp(point(x2 as f64 - 1.0, y2 as f64 - 1.0), t)
+    };
+
+    let mut contours = Vec::new();
+    for start in 0..next.len() {
+        if next[start] == none {
+            continue;
+        }
+
+        let mut contour = Vec::new();
+        let mut id = start;
+        while next[id] != none {
+            contour.push(crossing(id));
+            let following = next[id] as usize;
+            next[id] = none;
+            id = following;
+        }
+        contours.push(contour);
+    }
+
+    contours
+}
+
+/// Simplifies a closed contour with the Ramer-Douglas-Peucker algorithm.
+pub(crate) fn simplify_contour(contour: &[Point], tolerance: f64) -> Vec<Point> {
+    let n = contour.len();
+    if n < 3 {
+        return contour.to_vec();
+    }
+
+    // Split the contour at the point that is the farthest from the first one.
+    let first = contour[0];
+    let mut split = 0;
+    let mut max_distance = 0.0;
+    for (i, p) in contour.iter().enumerate() {
+        let d = (*p - first).square_length();
+        if d > max_distance {
+            max_distance = d;
+            split = i;
+        }
+    }
+
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[split] = true;
+
+    let mut stack = vec![(0, split), (split, n)];
+    while let Some((start, end)) = stack.pop() {
+        if end - start < 2 {
+            continue;
+        }
+        let segment = LineSegment {
+            from: contour[start],
+            to: contour[end % n],
+        };
+
+        let mut farthest = start;
+        let mut max_distance = tolerance;
+        for (i, p) in contour.iter().enumerate().take(end).skip(start + 1) {
+            let d = segment.distance_to_point(*p);
+            if d > max_distance {
+                max_distance = d;
+                farthest = i;
+            }
+        }
+
+        if farthest != start {
+            keep[farthest] = true;
+            stack.push((start, farthest));
+            stack.push((farthest, end));
+        }
+    }
+
+    contour
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+#[test]
+fn trace_nested_shapes() {
+    // A square with a hole containing an island, and a separate pixel.
+    let mut pixels = [0u8; 100];
+    for y in 1..8 {
+        for x in 1..8 {
+            let ring = x == 1 || x == 7 || y == 1 || y == 7;
+            let island = x == 4 && y == 4;
+            if ring || island {
+                pixels[y * 10 + x] = 1;
+            }
+        }
+    }
+    pixels[9 * 10 + 9] = 1;
+    let bitmap = Bitmap {
+        data: &pixels,
+        width: 10,
+        height: 10,
+    };
+
+    let mut polygons = trace_bitmap(&bitmap, 0.5, 0.0);
+    polygons.sort_by(|a, b| {
+        let a = signed_area(a.path_events());
+        let b = signed_area(b.path_events());
+        b.partial_cmp(&a).unwrap()
+    });
+    assert_eq!(polygons.len(), 3);
+
+    // The corners are cut at the middle of the edges of the pixels.
+    assert_eq!(polygons[0].holes.len(), 1);
+    let area = |points: &[Point]| signed_area(ring(points));
+    assert!((area(&polygons[0].exterior) - (7.0 * 7.0 - 0.5)).abs() < 1e-9);
+    assert!((area(&polygons[0].holes[0]) + (5.0 * 5.0 - 0.5)).abs() < 1e-9);
+    for polygon in &polygons[1..] {
+        assert!(polygon.holes.is_empty());
+        assert!((area(&polygon.exterior) - 0.5).abs() < 1e-9);
+    }
+    assert!(hit_test_path(
+        &point(4.5, 4.5),
+        polygons[1].path_events(),
+        FillRule::EvenOdd,
+        0.1
+    ));
+    assert!(hit_test_path(
+        &point(9.5, 9.5),
+        polygons[2].path_events(),
+        FillRule::EvenOdd,
+        0.1
+    ));
+
+    // Aligned vertices are removed.
+    assert_eq!(polygons[0].exterior.len(), 8);
+}
+
+#[test]
+fn trace_distance_field() {
+    // A signed distance field of a circle, positive inside.
+    let (size, radius) = (64, 20.0);
+    let mut field = vec![0.0f32; size * size];
+    for y in 0..size {
+        for x in 0..size {
+            let p = point(x as f64 + 0.5, y as f64 + 0.5);
+            field[y * size + x] = (radius - (p - point(32.0, 32.0)).length()) as f32;
+        }
+    }
+    let bitmap = Bitmap {
+        data: &field,
+        width: size,
+        height: size,
+    };
+
+    let polygons = trace_bitmap(&bitmap, 0.0, 0.01);
+    assert_eq!(polygons.len(), 1);
+    for p in &polygons[0].exterior {
+        assert!(((*p - point(32.0, 32.0)).length() - radius).abs() < 0.02);
+    }
+    let area = signed_area(polygons[0].path_events());
+    assert!((area - core::f64::consts::PI * radius * radius).abs() < 1.0);
+
+    // Diagonal pixels are connected when the pixels around their corner average above
+    // the threshold.
+    let pixels = [1.0f64, 0.4, 0.4, 1.0];
+    let bitmap = Bitmap {
+        data: &pixels,
+        width: 2,
+        height: 2,
+    };
+    assert_eq!(trace_bitmap(&bitmap, 0.6, 0.0).len(), 1);
+    assert_eq!(trace_bitmap(&bitmap, 0.8, 0.0).len(), 2);
+}
+
+#[test]
+fn metaballs() {
+    let (width, height) = (80, 40);
+    let balls = [(point(25.0, 20.0), 8.0), (point(55.0, 20.0), 8.0)];
+    let mut field = vec![0.0f64; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            let p = point(x as f64 + 0.5, y as f64 + 0.5);
+            field[y * width + x] = balls
+                .iter()
+                .map(|(center, radius)| radius * radius / (p - *center).square_length())
+                .sum();
+        }
+    }
+    let bitmap = Bitmap {
+        data: &field,
+        width,
+        height,
+    };
+
+    let thresholds = [0.25, 0.5, 1.0, 2.0];
+    let levels = iso_contours(&bitmap, &thresholds, 0.05);
+    let counts: Vec<usize> = levels.iter().map(Vec::len).collect();
+    // The balls are merged at the lower thresholds.
+    assert_eq!(counts, [1, 1, 2, 2]);
+
+    for level in 1..levels.len() {
+        let area = |polygons: &[ComplexPolygon]| -> f64 {
+            polygons
+                .iter()
+                .map(|polygon| signed_area(polygon.path_events()))
+                .sum()
+        };
+        assert!(area(&levels[level]) < area(&levels[level - 1]));
+
+        // Each contour is inside of one of the contours of the previous level.
+        for polygon in &levels[level] {
+            for p in &polygon.exterior {
+                assert!(levels[level - 1].iter().any(|outer| hit_test_path(
+                    p,
+                    outer.path_events(),
+                    FillRule::NonZero,
+                    0.1
+                )));
+            }
+        }
+    }
+
+    // The vertices are on the iso-line.
+    for polygon in &levels[2] {
+        for p in &polygon.exterior {
+            let value: f64 = balls
+                .iter()
+                .map(|(center, radius)| radius * radius / (*p - *center).square_length())
+                .sum();
+            assert!((value - 1.0).abs() < 0.02);
+        }
+    }
+}
+
+#[test]
+fn sprite_outlines() {
+    // A disc with a thin line sticking out of it, in a larger transparent image.
+    let (width, height) = (48, 40);
+    let mut pixels = vec![0u8; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            let p = point(x as f64 + 0.5, y as f64 + 0.5);
+            let in_disc = (p - point(20.0, 20.0)).length() < 10.0;
+            let in_line = y == 20 && (20..44).contains(&x);
+            if in_disc || in_line {
+                pixels[y * width + x] = if in_line { 10 } else { 255 };
+            }
+        }
+    }
+    let bitmap = Bitmap {
+        data: &pixels,
+        width,
+        height,
+    };
+
+    let covers = |polygons: &[ComplexPolygon], margin: f64| {
+        for y in 0..height {
+            for x in 0..width {
+                if pixels[y * width + x] == 0 {
+                    continue;
+                }
+                let (x, y) = (x as f64, y as f64);
+                for corner in &[
+                    point(x - margin, y - margin),
+                    point(x + 1.0 + margin, y - margin),
+                    point(x + 1.0 + margin, y + 1.0 + margin),
+                    point(x - margin, y + 1.0 + margin),
+                ] {
+                    let inside = polygons.iter().any(|polygon| {
+                        hit_test_path(corner, polygon.path_events(), FillRule::NonZero, 0.1)
+                    });
+                    if !inside {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    };
+    let num_vertices = |polygons: &[ComplexPolygon]| -> usize {
+        polygons.iter().map(|polygon| polygon.exterior.len()).sum()
+    };
+    let area = |polygons: &[ComplexPolygon]| -> f64 {
+        polygons
+            .iter()
+            .map(|polygon| signed_area(polygon.path_events()))
+            .sum()
+    };
+
+    for &max_vertices in &[8, 16, 32, 64] {
+        let options = SpriteOutlineOptions::max_vertices(max_vertices);
+        let polygons = sprite_outline(&bitmap, &options);
+        assert_eq!(polygons.len(), 1);
+        assert!(num_vertices(&polygons) <= max_vertices);
+        assert!(covers(&polygons, options.dilation * 0.99));
+        // Never larger than the dilated bounding rectangle of the visible pixels.
+        assert!(area(&polygons) <= 36.0 * 22.0);
+        if max_vertices >= 16 {
+            // Tighter than the bounding rectangle without dilation.
+            assert!(area(&polygons) < 34.0 * 20.0);
+        }
+    }
+
+    // The faint line is ignored with a higher threshold, so there is less overdraw.
+    let options = SpriteOutlineOptions::DEFAULT.with_alpha_threshold(20.0);
+    let without_line = sprite_outline(&bitmap, &options);
+    assert!(area(&without_line) < area(&sprite_outline(&bitmap, &SpriteOutlineOptions::DEFAULT)));
+
+    // Very small budgets produce a rectangle.
+    let rectangle = sprite_outline(&bitmap, &SpriteOutlineOptions::max_vertices(3));
+    assert_eq!(num_vertices(&rectangle), 4);
+    assert!(covers(&rectangle, 0.99));
+    assert!(area(&rectangle) > 0.0);
+
+    let empty = vec![0u8; 16];
+    let bitmap = Bitmap {
+        data: &empty,
+        width: 4,
+        height: 4,
+    };
+    assert!(sprite_outline(&bitmap, &SpriteOutlineOptions::DEFAULT).is_empty());
+}
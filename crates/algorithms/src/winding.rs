@@ -1,7 +1,10 @@
 // Compute the winding of a path.
 
+use crate::area::signed_area;
 use crate::geom::vector;
-use crate::path::{PathEvent, Winding};
+use crate::hit_test::hit_test_path;
+use crate::path::{FillRule, Path, PathEvent, Winding};
+use alloc::vec::Vec;
 
 /// Compute the winding of the next sub-path.
 ///
@@ -75,6 +78,73 @@ impl<Iter: Iterator<Item = PathEvent>> Iterator for Windings<Iter> {
     }
 }
 
+/// Builds a copy of a path where the winding of each sub-path follows its nesting.
+///
+/// Sub-paths that are inside of an even number of other sub-paths get a positive
+/// winding and the others a negative one, reversing them if need be. With the
+/// `NonZero` fill rule, a hole that has the same winding as the shape around it is
+/// filled instead of being cut out, which is a common problem with data that doesn't
+/// follow a winding convention. Once normalized, both fill rules give the same result
+/// for paths without self-intersections or overlapping sub-paths.
+///
+/// A sub-path is considered to be inside of another one if its first endpoint is and
+/// the area of the other sub-path is larger. Curves are approximated with the provided
+/// tolerance for the inside tests. Sub-paths without area are left unchanged.
+pub fn normalize_winding<Iter>(path: Iter, tolerance: f64) -> Path
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut sub_paths: Vec<Path> = Vec::new();
+    let mut builder = Path::builder();
+    for evt in path {
+        let end = matches!(evt, PathEvent::End { .. });
+        builder.path_event(evt);
+        if end {
+            sub_paths.push(core::mem::replace(&mut builder, Path::builder()).build());
+        }
+    }
+
+    let areas: Vec<f64> = sub_paths
+        .iter()
+        .map(|sub_path| signed_area(sub_path.iter()).abs())
+        .collect();
+
+    let mut output = Path::builder();
+    for (idx, sub_path) in sub_paths.iter().enumerate() {
+        let first = match sub_path.iter().next() {
+            Some(PathEvent::Begin { at }) => at,
+            _ => continue,
+        };
+        let depth = sub_paths
+            .iter()
+            .enumerate()
+            .filter(|(other, other_path)| {
+                *other != idx
+                    && areas[*other] > areas[idx]
+                    && hit_test_path(&first, other_path.iter(), FillRule::EvenOdd, tolerance)
+            })
+            .count();
+
+        let expected = if depth % 2 == 0 {
+            Winding::Positive
+        } else {
+            Winding::Negative
+        };
+        let winding = compute_winding(&mut sub_path.iter());
+        if areas[idx] != 0.0 && winding != Some(expected) {
+            for evt in sub_path.reversed() {
+                output.path_event(evt);
+            }
+        } else {
+            for evt in sub_path.iter() {
+                output.path_event(evt);
+            }
+        }
+    }
+
+    output.build()
+}
+
 #[test]
 fn path_winding() {
     use crate::geom::point;
@@ -100,3 +170,49 @@ fn path_winding() {
     assert_eq!(compute_winding(&mut iter), Some(Winding::Negative));
     assert_eq!(compute_winding(&mut iter), None);
 }
+
+#[test]
+fn normalized_winding() {
+    use crate::geom::point;
+
+    // Nested squares, all with a positive winding.
+    let mut path = Path::builder();
+    for (min, max) in &[(0.0, 10.0), (2.0, 8.0), (4.0, 6.0), (20.0, 30.0)] {
+        path.begin(point(*min, *min));
+        path.line_to(point(*max, *min));
+        path.line_to(point(*max, *max));
+        path.line_to(point(*min, *max));
+        path.close();
+    }
+    let path = path.build();
+
+    let normalized = normalize_winding(path.iter(), 0.01);
+    let windings: Vec<Winding> = Windings(normalized.iter()).collect();
+    assert_eq!(
+        windings,
+        alloc::vec![
+            Winding::Positive,
+            Winding::Negative,
+            Winding::Positive,
+            Winding::Positive
+        ]
+    );
+    assert!((signed_area(normalized.iter()) - (100.0 - 36.0 + 4.0 + 100.0)).abs() < 1e-9);
+
+    // The fill with the non-zero rule now has the hole.
+    let inside = |p| hit_test_path(&p, normalized.iter(), FillRule::NonZero, 0.01);
+    assert!(inside(point(1.0, 1.0)));
+    assert!(!inside(point(3.0, 3.0)));
+    assert!(inside(point(5.0, 5.0)));
+    assert!(hit_test_path(
+        &point(3.0, 3.0),
+        path.iter(),
+        FillRule::NonZero,
+        0.01
+    ));
+
+    // Normalizing again doesn't change anything.
+    let again: Vec<PathEvent> = normalize_winding(normalized.iter(), 0.01).iter().collect();
+    let normalized: Vec<PathEvent> = normalized.iter().collect();
+    assert_eq!(again, normalized);
+}
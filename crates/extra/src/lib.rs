@@ -3,11 +3,15 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
+extern crate lyon_algorithms as algorithms;
 extern crate lyon_path as path;
 
 pub use path::geom::euclid;
 pub use path::math;
 
 pub mod debugging;
+pub mod mvt;
 pub mod parser;
+pub mod projection;
 pub mod rust_logo;
+pub mod wkb;
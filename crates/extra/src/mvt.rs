@@ -0,0 +1,674 @@
+//! Decode the geometry of Mapbox Vector Tiles.
+//!
+//! [Mapbox Vector Tiles](https://github.com/mapbox/vector-tile-spec) are protobuf
+//! messages containing layers of features, each with a geometry encoded as a sequence
+//! of move-to, line-to and close-path commands, and some properties. This module
+//! decodes the subset of the format that a renderer needs: the layers, the features
+//! with their id and properties, and the geometry as points, line strings and polygons
+//! with holes.
+//!
+//! The coordinates are expressed in the tile's coordinate system, with the y axis
+//! pointing down and the tile covering the square from `0` to the `extent` of the
+//! layer. Geometry near the edges of the tile can extend beyond it.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_extra::mvt::{decode_geometry, Geometry, GeometryType};
+//! use lyon_extra::math::point;
+//!
+//! // A triangle, from the specification.
+//! let commands = [9, 6, 12, 18, 10, 12, 24, 44, 15];
+//! let geometry = decode_geometry(GeometryType::Polygon, &commands).unwrap();
+//!
+//! let polygons = match &geometry {
+//!     Geometry::Polygons(polygons) => polygons,
+//!     _ => panic!(),
+//! };
+//! assert_eq!(
+//!     polygons[0].exterior,
+//!     vec![point(3.0, 6.0), point(8.0, 12.0), point(20.0, 34.0)]
+//! );
+//!
+//! // The path can be given to the fill or stroke tessellators.
+//! let _path = geometry.to_path();
+//! ```
+
+use algorithms::area::signed_area;
+pub use algorithms::polygon::ComplexPolygon;
+use path::iterator::FromPolyline;
+use path::math::{point, Point};
+use path::{Path, Polygon};
+
+extern crate thiserror;
+
+use self::thiserror::Error;
+
+#[non_exhaustive]
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    #[error("Unexpected end of the data.")]
+    UnexpectedEnd,
+    #[error("Invalid variable-length integer.")]
+    Varint,
+    #[error("Invalid wire type {wire_type} for field {field}.")]
+    WireType { field: u32, wire_type: u8 },
+    #[error("Invalid UTF-8 string.")]
+    Utf8,
+    #[error("Tag {index} refers to a missing key or value.")]
+    Tag { index: usize },
+    #[error("Invalid geometry command {command} at index {index}.")]
+    Command { command: u32, index: usize },
+}
+
+/// A decoded vector tile.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tile {
+    pub layers: Vec<Layer>,
+}
+
+/// A named set of features.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    pub version: u32,
+    /// The size of the tile in the coordinate system of the geometry.
+    ///
+    /// Default value: `4096`.
+    pub extent: u32,
+    pub features: Vec<Feature>,
+}
+
+/// A geometry with some properties.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Feature {
+    pub id: Option<u64>,
+    /// The key and value pairs of the feature.
+    pub properties: Vec<(String, Value)>,
+    pub geometry: Geometry,
+}
+
+/// The value of a property.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    String(String),
+    Float(f32),
+    Double(f64),
+    /// Signed integers, both the `int` and `sint` variants of the format.
+    Int(i64),
+    UInt(u64),
+    Bool(bool),
+}
+
+/// The type of geometry that the commands of a feature describe.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GeometryType {
+    Unknown,
+    Point,
+    LineString,
+    Polygon,
+}
+
+/// The geometry of a feature.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Geometry {
+    Unknown,
+    Points(Vec<Point>),
+    LineStrings(Vec<Vec<Point>>),
+    /// Polygons whose exterior have a positive signed area and holes a negative one.
+    Polygons(Vec<ComplexPolygon>),
+}
+
+impl Geometry {
+    /// Builds a path with an open sub-path per line string and a closed sub-path per
+    /// ring of the polygons.
+    ///
+    /// Points don't cover any area or length and are not part of the path.
+    pub fn to_path(&self) -> Path {
+        let mut builder = Path::builder();
+        match self {
+            Geometry::Unknown | Geometry::Points(_) => {}
+            Geometry::LineStrings(lines) => {
+                for line in lines {
+                    builder.add_polygon(Polygon {
+                        points: line,
+                        closed: false,
+                    });
+                }
+            }
+            Geometry::Polygons(polygons) => {
+                for polygon in polygons {
+                    let rings = core::iter::once(&polygon.exterior).chain(polygon.holes.iter());
+                    for ring in rings {
+                        builder.add_polygon(Polygon {
+                            points: ring,
+                            closed: true,
+                        });
+                    }
+                }
+            }
+        }
+
+        builder.build()
+    }
+}
+
+/// Decodes a tile from the bytes of its protobuf message.
+///
+/// Unknown fields are skipped.
+pub fn decode_tile(data: &[u8]) -> Result<Tile, DecodeError> {
+    let mut tile = Tile::default();
+    let mut reader = Reader::new(data);
+    while reader.next_field()? {
+        match reader.field {
+            3 => tile.layers.push(decode_layer(reader.bytes()?)?),
+            _ => reader.skip()?,
+        }
+    }
+
+    Ok(tile)
+}
+
+fn decode_layer(data: &[u8]) -> Result<Layer, DecodeError> {
+    let mut layer = Layer {
+        name: String::new(),
+        version: 1,
+        extent: 4096,
+        features: Vec::new(),
+    };
+    // The keys and values can come after the features that refer to them.
+    let mut features = Vec::new();
+    let mut keys = Vec::new();
+    let mut values = Vec::new();
+
+    let mut reader = Reader::new(data);
+    while reader.next_field()? {
+        match reader.field {
+            1 => layer.name = reader.string()?,
+            2 => features.push(reader.bytes()?),
+            3 => keys.push(reader.string()?),
+            4 => values.push(decode_value(reader.bytes()?)?),
+            5 => layer.extent = reader.varint()? as u32,
+            15 => layer.version = reader.varint()? as u32,
+            _ => reader.skip()?,
+        }
+    }
+
+    for feature in features {
+        layer
+            .features
+            .push(decode_feature(feature, &keys, &values)?);
+    }
+
+    Ok(layer)
+}
+
+fn decode_feature(data: &[u8], keys: &[String], values: &[Value]) -> Result<Feature, DecodeError> {
+    let mut id = None;
+    let mut tags = Vec::new();
+    let mut geometry_type = GeometryType::Unknown;
+    let mut commands = Vec::new();
+
+    let mut reader = Reader::new(data);
+    while reader.next_field()? {
+        match reader.field {
+            1 => id = Some(reader.varint()?),
+            2 => reader.packed(&mut tags)?,
+            3 => {
+                geometry_type = match reader.varint()? {
+                    1 => GeometryType::Point,
+                    2 => GeometryType::LineString,
+                    3 => GeometryType::Polygon,
+                    _ => GeometryType::Unknown,
+                }
+            }
+            4 => reader.packed(&mut commands)?,
+            _ => reader.skip()?,
+        }
+    }
+
+    let mut properties = Vec::with_capacity(tags.len() / 2);
+    for (index, pair) in tags.chunks(2).enumerate() {
+        let key = keys.get(pair[0] as usize);
+        let value = pair.get(1).and_then(|v| values.get(*v as usize));
+        match (key, value) {
+            (Some(key), Some(value)) => properties.push((key.clone(), value.clone())),
+            (None, _) => return Err(DecodeError::Tag { index: index * 2 }),
+            (_, None) => {
+                return Err(DecodeError::Tag {
+                    index: index * 2 + 1,
+                })
+            }
+        }
+    }
+
+    Ok(Feature {
+        id,
+        properties,
+        geometry: decode_geometry(geometry_type, &commands)?,
+    })
+}
+
+fn decode_value(data: &[u8]) -> Result<Value, DecodeError> {
+    // An empty value message isn't valid but is tolerated as an empty string.
+    let mut value = Value::String(String::new());
+    let mut reader = Reader::new(data);
+    while reader.next_field()? {
+        value = match reader.field {
+            1 => Value::String(reader.string()?),
+            2 => Value::Float(f32::from_bits(reader.fixed32()?)),
+            3 => Value::Double(f64::from_bits(reader.fixed64()?)),
+            4 => Value::Int(reader.varint()? as i64),
+            5 => Value::UInt(reader.varint()?),
+            6 => {
+                let v = reader.varint()?;
+                Value::Int((v >> 1) as i64 ^ -((v & 1) as i64))
+            }
+            7 => Value::Bool(reader.varint()? != 0),
+            _ => {
+                reader.skip()?;
+                continue;
+            }
+        };
+    }
+
+    Ok(value)
+}
+
+const MOVE_TO: u32 = 1;
+const LINE_TO: u32 = 2;
+const CLOSE_PATH: u32 = 7;
+
+/// Decodes the geometry commands of a feature.
+///
+/// Each move-to command starts a new point, line string or ring. Line strings with
+/// fewer than two points and rings without area are dropped. Rings with a positive
+/// signed area start a new polygon and the following rings with a negative signed area
+/// are its holes. Holes that don't follow an exterior ring are dropped.
+pub fn decode_geometry(
+    geometry_type: GeometryType,
+    commands: &[u32],
+) -> Result<Geometry, DecodeError> {
+    if geometry_type == GeometryType::Unknown {
+        return Ok(Geometry::Unknown);
+    }
+
+    let mut sub_paths: Vec<Vec<Point>> = Vec::new();
+    let (mut x, mut y) = (0i64, 0i64);
+    let mut idx = 0;
+    while idx < commands.len() {
+        let command = commands[idx];
+        let (id, count) = (command & 0x7, (command >> 3) as usize);
+        let invalid = DecodeError::Command {
+            command,
+            index: idx,
+        };
+        idx += 1;
+
+        let valid = match id {
+            MOVE_TO => true,
+            LINE_TO => geometry_type != GeometryType::Point && !sub_paths.is_empty(),
+            CLOSE_PATH => geometry_type == GeometryType::Polygon,
+            _ => false,
+        };
+        if !valid {
+            return Err(invalid);
+        }
+
+        if id == CLOSE_PATH {
+            // Rings are always closed.
+            continue;
+        }
+
+        if (commands.len() - idx) / 2 < count {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        for _ in 0..count {
+            x += zigzag(commands[idx]);
+            y += zigzag(commands[idx + 1]);
+            idx += 2;
+
+            if id == MOVE_TO {
+                sub_paths.push(Vec::new());
+            }
+            sub_paths
+                .last_mut()
+                .unwrap()
+                .push(point(x as f64, y as f64));
+        }
+    }
+
+    Ok(match geometry_type {
+        GeometryType::Point => Geometry::Points(sub_paths.into_iter().flatten().collect()),
+        GeometryType::LineString => {
+            sub_paths.retain(|line| line.len() >= 2);
+            Geometry::LineStrings(sub_paths)
+        }
+        _ => {
+            let mut polygons: Vec<ComplexPolygon> = Vec::new();
+            for ring in sub_paths {
+                let area = signed_area(FromPolyline::closed(ring.iter().cloned()));
+                if area > 0.0 {
+                    polygons.push(ComplexPolygon {
+                        exterior: ring,
+                        holes: Vec::new(),
+                    });
+                } else if area < 0.0 {
+                    if let Some(polygon) = polygons.last_mut() {
+                        polygon.holes.push(ring);
+                    }
+                }
+            }
+
+            Geometry::Polygons(polygons)
+        }
+    })
+}
+
+fn zigzag(v: u32) -> i64 {
+    (v >> 1) as i64 ^ -((v & 1) as i64)
+}
+
+const VARINT: u8 = 0;
+const FIXED64: u8 = 1;
+const LEN: u8 = 2;
+const FIXED32: u8 = 5;
+
+// Reads the fields of a protobuf message.
+struct Reader<'l> {
+    data: &'l [u8],
+    // The number and wire type of the current field.
+    field: u32,
+    wire_type: u8,
+}
+
+impl<'l> Reader<'l> {
+    fn new(data: &'l [u8]) -> Self {
+        Reader {
+            data,
+            field: 0,
+            wire_type: 0,
+        }
+    }
+
+    // Reads the key of the next field, returns false at the end of the message.
+    fn next_field(&mut self) -> Result<bool, DecodeError> {
+        if self.data.is_empty() {
+            return Ok(false);
+        }
+
+        let key = self.read_varint()?;
+        self.field = (key >> 3) as u32;
+        self.wire_type = (key & 0x7) as u8;
+
+        Ok(true)
+    }
+
+    fn expect(&self, wire_type: u8) -> Result<(), DecodeError> {
+        if self.wire_type != wire_type {
+            return Err(DecodeError::WireType {
+                field: self.field,
+                wire_type: self.wire_type,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn varint(&mut self) -> Result<u64, DecodeError> {
+        self.expect(VARINT)?;
+        self.read_varint()
+    }
+
+    fn fixed32(&mut self) -> Result<u32, DecodeError> {
+        self.expect(FIXED32)?;
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn fixed64(&mut self) -> Result<u64, DecodeError> {
+        self.expect(FIXED64)?;
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn bytes(&mut self) -> Result<&'l [u8], DecodeError> {
+        self.expect(LEN)?;
+        let len = self.read_varint()?;
+        if len > self.data.len() as u64 {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        self.take(len as usize)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let bytes = self.bytes()?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::Utf8)
+    }
+
+    // Reads a repeated integer field, either packed or as a single value.
+    fn packed(&mut self, output: &mut Vec<u32>) -> Result<(), DecodeError> {
+        if self.wire_type == VARINT {
+            output.push(self.read_varint()? as u32);
+            return Ok(());
+        }
+
+        let mut packed = Reader::new(self.bytes()?);
+        while !packed.data.is_empty() {
+            output.push(packed.read_varint()? as u32);
+        }
+
+        Ok(())
+    }
+
+    fn skip(&mut self) -> Result<(), DecodeError> {
+        match self.wire_type {
+            VARINT => {
+                self.read_varint()?;
+            }
+            FIXED64 => {
+                self.take(8)?;
+            }
+            LEN => {
+                self.bytes()?;
+            }
+            FIXED32 => {
+                self.take(4)?;
+            }
+            _ => {
+                return Err(DecodeError::WireType {
+                    field: self.field,
+                    wire_type: self.wire_type,
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_varint(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0;
+        for i in 0..10 {
+            let byte = *self.take(1)?.first().unwrap();
+            value |= ((byte & 0x7F) as u64) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(DecodeError::Varint)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'l [u8], DecodeError> {
+        if len > self.data.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+fn encode_varint(output: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        output.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    output.push(v as u8);
+}
+
+#[cfg(test)]
+fn encode_message(output: &mut Vec<u8>, field: u32, message: &[u8]) {
+    encode_varint(output, ((field << 3) | LEN as u32) as u64);
+    encode_varint(output, message.len() as u64);
+    output.extend_from_slice(message);
+}
+
+#[cfg(test)]
+fn encode_packed(output: &mut Vec<u8>, field: u32, values: &[u32]) {
+    let mut packed = Vec::new();
+    for v in values {
+        encode_varint(&mut packed, *v as u64);
+    }
+    encode_message(output, field, &packed);
+}
+
+#[test]
+fn geometry_commands() {
+    // The examples of the specification.
+    assert_eq!(
+        decode_geometry(GeometryType::Point, &[17, 10, 14, 3, 9]),
+        Ok(Geometry::Points(vec![point(5.0, 7.0), point(3.0, 2.0)]))
+    );
+
+    assert_eq!(
+        decode_geometry(
+            GeometryType::LineString,
+            &[9, 4, 4, 18, 0, 16, 16, 0, 9, 17, 17, 10, 4, 8]
+        ),
+        Ok(Geometry::LineStrings(vec![
+            vec![point(2.0, 2.0), point(2.0, 10.0), point(10.0, 10.0)],
+            vec![point(1.0, 1.0), point(3.0, 5.0)],
+        ]))
+    );
+
+    let multi_polygon = [
+        9, 0, 0, 26, 20, 0, 0, 20, 19, 0, 15, 9, 22, 2, 26, 18, 0, 0, 18, 17, 0, 15, 9, 4, 13, 26,
+        0, 8, 8, 0, 0, 7, 15,
+    ];
+    assert_eq!(
+        decode_geometry(GeometryType::Polygon, &multi_polygon),
+        Ok(Geometry::Polygons(vec![
+            ComplexPolygon {
+                exterior: vec![
+                    point(0.0, 0.0),
+                    point(10.0, 0.0),
+                    point(10.0, 10.0),
+                    point(0.0, 10.0)
+                ],
+                holes: Vec::new(),
+            },
+            ComplexPolygon {
+                exterior: vec![
+                    point(11.0, 11.0),
+                    point(20.0, 11.0),
+                    point(20.0, 20.0),
+                    point(11.0, 20.0)
+                ],
+                holes: vec![vec![
+                    point(13.0, 13.0),
+                    point(13.0, 17.0),
+                    point(17.0, 17.0),
+                    point(17.0, 13.0)
+                ]],
+            },
+        ]))
+    );
+    let path = decode_geometry(GeometryType::Polygon, &multi_polygon)
+        .unwrap()
+        .to_path();
+    assert!((signed_area(path.iter()) - (100.0 + 81.0 - 16.0)).abs() < 1e-9);
+
+    // Line-to without a move-to, close-path in a line string and truncated parameters.
+    assert_eq!(
+        decode_geometry(GeometryType::LineString, &[10, 4, 4]),
+        Err(DecodeError::Command {
+            command: 10,
+            index: 0
+        })
+    );
+    assert_eq!(
+        decode_geometry(GeometryType::LineString, &[9, 4, 4, 15]),
+        Err(DecodeError::Command {
+            command: 15,
+            index: 3
+        })
+    );
+    assert_eq!(
+        decode_geometry(GeometryType::Point, &[17, 10, 14, 3]),
+        Err(DecodeError::UnexpectedEnd)
+    );
+}
+
+#[test]
+fn tile() {
+    let mut value = Vec::new();
+    encode_message(&mut value, 1, b"lake");
+
+    let mut feature = Vec::new();
+    encode_varint(&mut feature, 1 << 3);
+    encode_varint(&mut feature, 7);
+    encode_packed(&mut feature, 2, &[0, 0]);
+    encode_varint(&mut feature, 3 << 3);
+    encode_varint(&mut feature, 3);
+    encode_packed(&mut feature, 4, &[9, 6, 12, 18, 10, 12, 24, 44, 15]);
+
+    let mut layer = Vec::new();
+    encode_varint(&mut layer, 15 << 3);
+    encode_varint(&mut layer, 2);
+    encode_message(&mut layer, 1, b"water");
+    encode_message(&mut layer, 2, &feature);
+    encode_message(&mut layer, 3, b"name");
+    encode_message(&mut layer, 4, &value);
+    // An unknown fixed32 field.
+    encode_varint(&mut layer, (9 << 3) | FIXED32 as u64);
+    layer.extend_from_slice(&[0, 0, 0, 0]);
+
+    let mut data = Vec::new();
+    encode_message(&mut data, 3, &layer);
+
+    let tile = decode_tile(&data).unwrap();
+    assert_eq!(tile.layers.len(), 1);
+    let layer = &tile.layers[0];
+    assert_eq!(layer.name, "water");
+    assert_eq!(layer.version, 2);
+    assert_eq!(layer.extent, 4096);
+    assert_eq!(layer.features.len(), 1);
+    let feature = &layer.features[0];
+    assert_eq!(feature.id, Some(7));
+    assert_eq!(
+        feature.properties,
+        vec![("name".to_string(), Value::String("lake".to_string()))]
+    );
+    assert!(matches!(&feature.geometry, Geometry::Polygons(p) if p.len() == 1));
+
+    assert_eq!(
+        decode_tile(&data[..data.len() - 1]),
+        Err(DecodeError::UnexpectedEnd)
+    );
+
+    // A tag referring to a missing value.
+    let mut feature = Vec::new();
+    encode_packed(&mut feature, 2, &[0, 1]);
+    let mut layer = Vec::new();
+    encode_message(&mut layer, 2, &feature);
+    encode_message(&mut layer, 3, b"name");
+    let mut data = Vec::new();
+    encode_message(&mut data, 3, &layer);
+    assert_eq!(decode_tile(&data), Err(DecodeError::Tag { index: 1 }));
+}
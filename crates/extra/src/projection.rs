@@ -0,0 +1,214 @@
+//! Project geographic coordinates onto a plane.
+//!
+//! Geographic data (for example decoded with the `wkb` module) is usually expressed as
+//! longitudes and latitudes in degrees, which must be projected before it can be
+//! tessellated and rendered. The points are `point(longitude, latitude)` and the
+//! projected coordinates are in meters, with the y axis pointing north.
+//!
+//! Both projections preserve the orientation of the shapes, so the winding of the
+//! rings stays the same.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_extra::projection::Projection;
+//! use lyon_extra::math::point;
+//!
+//! let mut ring = [
+//!     point(2.25, 48.81),
+//!     point(2.42, 48.81),
+//!     point(2.42, 48.91),
+//!     point(2.25, 48.91),
+//! ];
+//! Projection::WebMercator.project_points(&mut ring);
+//!
+//! let lon_lat = Projection::WebMercator.unproject(ring[0]);
+//! assert!((lon_lat - point(2.25, 48.81)).length() < 1e-9);
+//! ```
+
+use path::math::{point, Point};
+use path::{Path, PathEvent};
+
+/// The radius of the sphere used by the projections, in meters.
+///
+/// This is the equatorial radius of the WGS 84 ellipsoid, used by the Web Mercator
+/// projection.
+pub const EARTH_RADIUS: f64 = 6_378_137.0;
+
+/// The latitude beyond which the Web Mercator projection is clamped, in degrees.
+///
+/// The projection of the world at this latitude is a square.
+pub const MAX_MERCATOR_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// A map projection.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Projection {
+    /// The spherical Mercator projection used by web maps (EPSG:3857).
+    ///
+    /// Latitudes are clamped to `MAX_MERCATOR_LATITUDE`, since the poles are infinitely
+    /// far away.
+    WebMercator,
+    /// The equirectangular projection, with longitudes scaled by the cosine of the
+    /// standard parallel (in degrees) so that distances are correct along it.
+    ///
+    /// A standard parallel of zero is the plate carrée projection.
+    Equirectangular { standard_parallel: f64 },
+}
+
+impl Projection {
+    /// Projects a point from its longitude and latitude in degrees.
+    pub fn project(&self, lon_lat: Point) -> Point {
+        let lon = lon_lat.x.to_radians();
+        match *self {
+            Projection::WebMercator => {
+                let lat = lon_lat
+                    .y
+                    .clamp(-MAX_MERCATOR_LATITUDE, MAX_MERCATOR_LATITUDE)
+                    .to_radians();
+                point(EARTH_RADIUS * lon, EARTH_RADIUS * lat.tan().asinh())
+            }
+            Projection::Equirectangular { standard_parallel } => point(
+                EARTH_RADIUS * lon * standard_parallel.to_radians().cos(),
+                EARTH_RADIUS * lon_lat.y.to_radians(),
+            ),
+        }
+    }
+
+    /// Returns the longitude and latitude in degrees of a projected point.
+    pub fn unproject(&self, p: Point) -> Point {
+        match *self {
+            Projection::WebMercator => point(
+                (p.x / EARTH_RADIUS).to_degrees(),
+                (p.y / EARTH_RADIUS).sinh().atan().to_degrees(),
+            ),
+            Projection::Equirectangular { standard_parallel } => point(
+                (p.x / (EARTH_RADIUS * standard_parallel.to_radians().cos())).to_degrees(),
+                (p.y / EARTH_RADIUS).to_degrees(),
+            ),
+        }
+    }
+
+    /// Projects points in place.
+    pub fn project_points(&self, points: &mut [Point]) {
+        for p in points {
+            *p = self.project(*p);
+        }
+    }
+
+    /// Builds a path with the projection of each endpoint and control point of a path.
+    ///
+    /// Segments are not subdivided, so long ones don't follow the curvature that
+    /// the projection gives to lines of constant longitude or latitude. Geographic
+    /// data is usually dense enough for this not to matter.
+    pub fn project_path<Iter>(&self, path: Iter) -> Path
+    where
+        Iter: IntoIterator<Item = PathEvent>,
+    {
+        let mut builder = Path::builder();
+        for evt in path {
+            builder.path_event(match evt {
+                PathEvent::Begin { at } => PathEvent::Begin {
+                    at: self.project(at),
+                },
+                PathEvent::Line { from, to } => PathEvent::Line {
+                    from: self.project(from),
+                    to: self.project(to),
+                },
+                PathEvent::Quadratic { from, ctrl, to } => PathEvent::Quadratic {
+                    from: self.project(from),
+                    ctrl: self.project(ctrl),
+                    to: self.project(to),
+                },
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => PathEvent::Cubic {
+                    from: self.project(from),
+                    ctrl1: self.project(ctrl1),
+                    ctrl2: self.project(ctrl2),
+                    to: self.project(to),
+                },
+                PathEvent::End { last, first, close } => PathEvent::End {
+                    last: self.project(last),
+                    first: self.project(first),
+                    close,
+                },
+            });
+        }
+
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+use std::f64::consts::PI;
+
+#[test]
+fn projections() {
+    // The half-width of the Web Mercator square.
+    let half_width = 20_037_508.342_789_244;
+
+    let mercator = Projection::WebMercator;
+    let p = mercator.project(point(180.0, MAX_MERCATOR_LATITUDE));
+    assert!((p.x - half_width).abs() < 1e-6);
+    assert!((p.y - half_width).abs() < 1e-6);
+    assert_eq!(
+        mercator.project(point(-180.0, -90.0)),
+        mercator.project(point(-180.0, -MAX_MERCATOR_LATITUDE))
+    );
+    assert_eq!(mercator.project(point(0.0, 0.0)), point(0.0, 0.0));
+
+    let equirectangular = Projection::Equirectangular {
+        standard_parallel: 60.0,
+    };
+    let p = equirectangular.project(point(180.0, 90.0));
+    assert!((p.x - EARTH_RADIUS * PI * 0.5).abs() < 1e-6);
+    assert!((p.y - EARTH_RADIUS * PI * 0.5).abs() < 1e-6);
+
+    for projection in &[mercator, equirectangular] {
+        for lon_lat in &[point(2.35, 48.85), point(-122.4, 37.8), point(151.2, -33.9)] {
+            let p = projection.unproject(projection.project(*lon_lat));
+            assert!((p - *lon_lat).length() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn projected_path() {
+    use algorithms::winding::compute_winding;
+    use path::Winding;
+
+    let mut builder = Path::builder();
+    builder.begin(point(-10.0, 40.0));
+    builder.line_to(point(10.0, 40.0));
+    builder.line_to(point(10.0, 60.0));
+    builder.line_to(point(-10.0, 60.0));
+    builder.close();
+    let path = builder.build();
+
+    let projection = Projection::WebMercator;
+    let projected = projection.project_path(path.iter());
+    let endpoints: Vec<Point> = projected
+        .iter()
+        .filter_map(|evt| match evt {
+            PathEvent::Begin { at } => Some(at),
+            PathEvent::Line { to, .. } => Some(to),
+            _ => None,
+        })
+        .collect();
+    let mut expected = [
+        point(-10.0, 40.0),
+        point(10.0, 40.0),
+        point(10.0, 60.0),
+        point(-10.0, 60.0),
+    ];
+    projection.project_points(&mut expected);
+    assert_eq!(endpoints, expected);
+
+    assert_eq!(
+        compute_winding(&mut projected.iter()),
+        Some(Winding::Positive)
+    );
+}
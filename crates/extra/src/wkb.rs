@@ -0,0 +1,723 @@
+//! Decode the geometry of Well-Known Binary and ESRI shapefile data.
+//!
+//! [Well-Known Binary](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry)
+//! is the binary encoding of geometry used by spatial databases and formats like
+//! GeoPackage. Both the ISO and the extended (PostGIS) variants are supported. The
+//! records of ESRI shapefiles (the `.shp` files) use a similar layout and can be
+//! decoded into the same geometry.
+//!
+//! Only the x and y coordinates are kept, the z and m coordinates are ignored.
+//!
+//! The orientation of the rings is corrected so that the exterior of the polygons have
+//! a positive signed area and their holes a negative one, as the formats don't all
+//! agree on it and many files don't follow their own format's convention anyway.
+//!
+//! ## Example
+//!
+//! ```
+//! use lyon_extra::wkb::{decode_wkb, Geometry};
+//! use lyon_extra::math::point;
+//!
+//! // POINT(1 2) in little endian.
+//! let data = [
+//!     1, 1, 0, 0, 0,
+//!     0, 0, 0, 0, 0, 0, 240, 63,
+//!     0, 0, 0, 0, 0, 0, 0, 64,
+//! ];
+//! let geometry = decode_wkb(&data).unwrap();
+//! assert_eq!(geometry, Geometry::Points(vec![point(1.0, 2.0)]));
+//!
+//! // The path can be given to the fill or stroke tessellators.
+//! let _path = geometry.to_path();
+//! ```
+
+use algorithms::area::signed_area;
+use algorithms::hit_test::hit_test_path;
+pub use algorithms::polygon::ComplexPolygon;
+use path::iterator::FromPolyline;
+use path::math::{point, Point};
+use path::{FillRule, Path, Polygon};
+
+extern crate thiserror;
+
+use self::thiserror::Error;
+
+#[non_exhaustive]
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    #[error("Unexpected end of the data.")]
+    UnexpectedEnd,
+    #[error("Invalid byte order {byte_order}.")]
+    ByteOrder { byte_order: u8 },
+    #[error("Unsupported geometry type {geometry_type}.")]
+    GeometryType { geometry_type: u32 },
+    #[error("Unsupported shape type {shape_type}.")]
+    ShapeType { shape_type: i32 },
+    #[error("Invalid shapefile header.")]
+    Header,
+    #[error("Invalid index of the first point of part {part}.")]
+    Part { part: usize },
+    #[error("Geometry collections are nested more than {MAX_NESTING} levels deep.")]
+    Nesting,
+}
+
+/// Decoded geometry.
+///
+/// Single and multi geometries are not distinguished.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Geometry {
+    Points(Vec<Point>),
+    LineStrings(Vec<Vec<Point>>),
+    /// Polygons whose exterior have a positive signed area and holes a negative one.
+    Polygons(Vec<ComplexPolygon>),
+    Collection(Vec<Geometry>),
+}
+
+impl Geometry {
+    /// Builds a path with an open sub-path per line string and a closed sub-path per
+    /// ring of the polygons.
+    ///
+    /// Points don't cover any area or length and are not part of the path.
+    pub fn to_path(&self) -> Path {
+        let mut builder = Path::builder();
+        add_to_path(self, &mut builder);
+
+        builder.build()
+    }
+}
+
+fn add_to_path(geometry: &Geometry, builder: &mut path::path::Builder) {
+    match geometry {
+        Geometry::Points(_) => {}
+        Geometry::LineStrings(lines) => {
+            for line in lines {
+                builder.add_polygon(Polygon {
+                    points: line,
+                    closed: false,
+                });
+            }
+        }
+        Geometry::Polygons(polygons) => {
+            for polygon in polygons {
+                let rings = core::iter::once(&polygon.exterior).chain(polygon.holes.iter());
+                for ring in rings {
+                    builder.add_polygon(Polygon {
+                        points: ring,
+                        closed: true,
+                    });
+                }
+            }
+        }
+        Geometry::Collection(geometries) => {
+            for geometry in geometries {
+                add_to_path(geometry, builder);
+            }
+        }
+    }
+}
+
+const POINT: u32 = 1;
+const LINE_STRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTI_POINT: u32 = 4;
+const MULTI_LINE_STRING: u32 = 5;
+const MULTI_POLYGON: u32 = 6;
+const GEOMETRY_COLLECTION: u32 = 7;
+
+// The flags of the extended format.
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// The maximum nesting depth of the multi-geometries and geometry collections
+/// decoded by `decode_wkb`.
+pub const MAX_NESTING: usize = 64;
+
+/// Decodes a geometry from its Well-Known Binary representation.
+///
+/// The first ring of each polygon is its exterior and the following ones are its
+/// holes. Empty points, line strings with fewer than two points and rings without
+/// area are dropped.
+///
+/// Geometries nested more than `MAX_NESTING` levels deep are rejected.
+pub fn decode_wkb(data: &[u8]) -> Result<Geometry, DecodeError> {
+    let mut reader = Reader::new(data);
+    decode_wkb_geometry(&mut reader, None, 0)
+}
+
+// Decodes a geometry, which must be of the expected type if any, inside of `depth`
+// multi-geometries or collections.
+fn decode_wkb_geometry(
+    reader: &mut Reader,
+    expected: Option<u32>,
+    depth: usize,
+) -> Result<Geometry, DecodeError> {
+    if depth > MAX_NESTING {
+        return Err(DecodeError::Nesting);
+    }
+
+    reader.big_endian = match reader.take(1)?[0] {
+        0 => true,
+        1 => false,
+        byte_order => return Err(DecodeError::ByteOrder { byte_order }),
+    };
+
+    let code = reader.u32()?;
+    if code & EWKB_SRID != 0 {
+        reader.u32()?;
+    }
+    let flags = code & (EWKB_Z | EWKB_M | EWKB_SRID);
+    let iso = code & !(EWKB_Z | EWKB_M | EWKB_SRID);
+    let (geometry_type, iso_dimensions) = (iso % 1000, iso / 1000);
+    let has_z = flags & EWKB_Z != 0 || iso_dimensions == 1 || iso_dimensions == 3;
+    let has_m = flags & EWKB_M != 0 || iso_dimensions == 2 || iso_dimensions == 3;
+    let dimensions = 2 + has_z as usize + has_m as usize;
+
+    let invalid = DecodeError::GeometryType {
+        geometry_type: code,
+    };
+    if iso_dimensions > 3 || expected.is_some_and(|expected| expected != geometry_type) {
+        return Err(invalid);
+    }
+
+    Ok(match geometry_type {
+        POINT => {
+            let p = reader.point(dimensions)?;
+            // Empty points are encoded with NaN coordinates.
+            let points = if p.x.is_nan() && p.y.is_nan() {
+                Vec::new()
+            } else {
+                vec![p]
+            };
+
+            Geometry::Points(points)
+        }
+        LINE_STRING => {
+            let line = reader.points(dimensions)?;
+            let lines = if line.len() >= 2 {
+                vec![line]
+            } else {
+                Vec::new()
+            };
+
+            Geometry::LineStrings(lines)
+        }
+        POLYGON => {
+            let mut rings = Vec::new();
+            for _ in 0..reader.count(4)? {
+                rings.push(reader.points(dimensions)?);
+            }
+
+            Geometry::Polygons(polygon_from_rings(rings).into_iter().collect())
+        }
+        MULTI_POINT | MULTI_LINE_STRING | MULTI_POLYGON | GEOMETRY_COLLECTION => {
+            let count = reader.count(5)?;
+            let element = match geometry_type {
+                GEOMETRY_COLLECTION => None,
+                _ => Some(geometry_type - 3),
+            };
+            let mut elements = Vec::with_capacity(count);
+            for _ in 0..count {
+                elements.push(decode_wkb_geometry(reader, element, depth + 1)?);
+            }
+
+            match geometry_type {
+                GEOMETRY_COLLECTION => Geometry::Collection(elements),
+                _ => merge(elements, geometry_type),
+            }
+        }
+        _ => return Err(invalid),
+    })
+}
+
+// Merges the single geometries of a multi geometry.
+fn merge(elements: Vec<Geometry>, geometry_type: u32) -> Geometry {
+    let mut points = Vec::new();
+    let mut lines = Vec::new();
+    let mut polygons = Vec::new();
+    for element in elements {
+        match element {
+            Geometry::Points(p) => points.extend(p),
+            Geometry::LineStrings(l) => lines.extend(l),
+            Geometry::Polygons(p) => polygons.extend(p),
+            Geometry::Collection(_) => unreachable!(),
+        }
+    }
+
+    match geometry_type {
+        MULTI_POINT => Geometry::Points(points),
+        MULTI_LINE_STRING => Geometry::LineStrings(lines),
+        _ => Geometry::Polygons(polygons),
+    }
+}
+
+// Builds a polygon from its exterior ring followed by its holes, fixing their
+// orientation.
+fn polygon_from_rings(rings: Vec<Vec<Point>>) -> Option<ComplexPolygon> {
+    let mut rings = rings.into_iter();
+    let mut exterior = rings.next()?;
+    if !orient(&mut exterior, true) {
+        return None;
+    }
+
+    let mut holes = Vec::new();
+    for mut ring in rings {
+        if orient(&mut ring, false) {
+            holes.push(ring);
+        }
+    }
+
+    Some(ComplexPolygon { exterior, holes })
+}
+
+// Removes the repeated first point at the end of a ring and reverses it if needed so
+// that its signed area is positive or negative. Returns false if the ring doesn't have
+// any area.
+fn orient(ring: &mut Vec<Point>, positive: bool) -> bool {
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+
+    let area = ring_area(ring);
+    if area == 0.0 || area.is_nan() {
+        return false;
+    }
+    if (area > 0.0) != positive {
+        ring.reverse();
+    }
+
+    true
+}
+
+fn ring_area(ring: &[Point]) -> f64 {
+    signed_area(FromPolyline::closed(ring.iter().cloned()))
+}
+
+const SHP_NULL: i32 = 0;
+const SHP_POINT: i32 = 1;
+const SHP_POLY_LINE: i32 = 3;
+const SHP_POLYGON: i32 = 5;
+const SHP_MULTI_POINT: i32 = 8;
+
+/// Decodes the geometry of the records of an ESRI shapefile (`.shp`).
+///
+/// The result has an element per record, which is `None` for null shapes. The
+/// attributes of the records in the corresponding `.dbf` file are in the same order.
+///
+/// See `decode_shape`.
+pub fn decode_shapefile(data: &[u8]) -> Result<Vec<Option<Geometry>>, DecodeError> {
+    let mut reader = Reader::new(data);
+    reader.big_endian = true;
+    if data.len() < 100 || reader.u32()? != 9994 {
+        return Err(DecodeError::Header);
+    }
+    // The length of the file, in 16 bits words.
+    reader.take(20)?;
+    let len = (reader.u32()? as usize).saturating_mul(2);
+    if len < 100 || len > data.len() {
+        return Err(DecodeError::Header);
+    }
+    let mut reader = Reader::new(&data[100..len]);
+
+    let mut shapes = Vec::new();
+    while !reader.data.is_empty() {
+        reader.big_endian = true;
+        let _record_number = reader.u32()?;
+        let content_len = (reader.u32()? as usize).saturating_mul(2);
+        shapes.push(decode_shape(reader.take(content_len)?)?);
+    }
+
+    Ok(shapes)
+}
+
+/// Decodes the content of a record of an ESRI shapefile.
+///
+/// Returns `None` for null shapes.
+///
+/// Polylines are converted into line strings, and multi-points into points. The x and
+/// y coordinates of the z and m variants of the shapes are decoded as well. The
+/// multi-patches of 3d models are not supported.
+///
+/// The rings of polygons are not ordered. Rings that are inside of an odd number of
+/// rings are holes of the smallest one that contains them, the others are exteriors.
+pub fn decode_shape(data: &[u8]) -> Result<Option<Geometry>, DecodeError> {
+    let mut reader = Reader::new(data);
+    let shape_type = reader.u32()? as i32;
+    // The z and m variants have the same layout followed by the additional coordinates.
+    let base_type = match shape_type {
+        11 | 21 => SHP_POINT,
+        13 | 23 => SHP_POLY_LINE,
+        15 | 25 => SHP_POLYGON,
+        18 | 28 => SHP_MULTI_POINT,
+        _ => shape_type,
+    };
+
+    Ok(Some(match base_type {
+        SHP_NULL => return Ok(None),
+        SHP_POINT => Geometry::Points(vec![reader.point(2)?]),
+        SHP_MULTI_POINT => {
+            reader.take(32)?;
+            let count = reader.count(16)?;
+            let mut points = Vec::with_capacity(count);
+            for _ in 0..count {
+                points.push(reader.point(2)?);
+            }
+
+            Geometry::Points(points)
+        }
+        SHP_POLY_LINE | SHP_POLYGON => {
+            reader.take(32)?;
+            let num_parts = reader.count(4)?;
+            let num_points = reader.u32()? as usize;
+            let mut parts = Vec::with_capacity(num_parts + 1);
+            for _ in 0..num_parts {
+                parts.push(reader.u32()? as usize);
+            }
+            parts.push(num_points);
+
+            let num_points = reader.count_of(num_points, 16)?;
+            let mut points = Vec::with_capacity(num_points);
+            for _ in 0..num_points {
+                points.push(reader.point(2)?);
+            }
+
+            let mut sub_paths = Vec::with_capacity(num_parts);
+            for part in 0..num_parts {
+                let (start, end) = (parts[part], parts[part + 1]);
+                if start > end || end > points.len() {
+                    return Err(DecodeError::Part { part });
+                }
+                sub_paths.push(points[start..end].to_vec());
+            }
+
+            if base_type == SHP_POLY_LINE {
+                sub_paths.retain(|line| line.len() >= 2);
+                Geometry::LineStrings(sub_paths)
+            } else {
+                Geometry::Polygons(polygons_from_unordered_rings(sub_paths))
+            }
+        }
+        _ => return Err(DecodeError::ShapeType { shape_type }),
+    }))
+}
+
+// Finds out which rings are exteriors and holes from how they are nested.
+fn polygons_from_unordered_rings(mut rings: Vec<Vec<Point>>) -> Vec<ComplexPolygon> {
+    rings.retain_mut(|ring| orient(ring, true));
+    let areas: Vec<f64> = rings.iter().map(|ring| ring_area(ring)).collect();
+
+    // The rings that contain each ring.
+    let parents: Vec<Vec<usize>> = (0..rings.len())
+        .map(|idx| {
+            (0..rings.len())
+                .filter(|other| {
+                    *other != idx
+                        && areas[*other] > areas[idx]
+                        && hit_test_path(
+                            &rings[idx][0],
+                            FromPolyline::closed(rings[*other].iter().cloned()),
+                            FillRule::EvenOdd,
+                            0.0,
+                        )
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut polygons = Vec::new();
+    let mut polygon_indices = vec![None; rings.len()];
+    let is_hole = |idx: usize| parents[idx].len() % 2 == 1;
+    for idx in 0..rings.len() {
+        if !is_hole(idx) {
+            polygon_indices[idx] = Some(polygons.len());
+            polygons.push(ComplexPolygon {
+                exterior: rings[idx].clone(),
+                holes: Vec::new(),
+            });
+        }
+    }
+
+    for idx in 0..rings.len() {
+        if !is_hole(idx) {
+            continue;
+        }
+
+        // The smallest ring that contains a hole is its exterior.
+        let exterior = parents[idx]
+            .iter()
+            .min_by(|a, b| areas[**a].partial_cmp(&areas[**b]).unwrap())
+            .unwrap();
+        let mut hole = rings[idx].clone();
+        hole.reverse();
+        if let Some(polygon) = polygon_indices[*exterior] {
+            polygons[polygon].holes.push(hole);
+        }
+    }
+
+    polygons
+}
+
+// Reads the values of binary geometry.
+struct Reader<'l> {
+    data: &'l [u8],
+    big_endian: bool,
+}
+
+impl<'l> Reader<'l> {
+    fn new(data: &'l [u8]) -> Self {
+        Reader {
+            data,
+            big_endian: false,
+        }
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        let bytes = self.take(4)?;
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Ok(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        let bits = if self.big_endian {
+            u64::from_be_bytes(bytes)
+        } else {
+            u64::from_le_bytes(bytes)
+        };
+
+        Ok(f64::from_bits(bits))
+    }
+
+    // Reads a point and skips its other coordinates.
+    fn point(&mut self, dimensions: usize) -> Result<Point, DecodeError> {
+        let p = point(self.f64()?, self.f64()?);
+        self.take((dimensions - 2) * 8)?;
+
+        Ok(p)
+    }
+
+    fn points(&mut self, dimensions: usize) -> Result<Vec<Point>, DecodeError> {
+        let count = self.count(dimensions * 8)?;
+        let mut points = Vec::with_capacity(count);
+        for _ in 0..count {
+            points.push(self.point(dimensions)?);
+        }
+
+        Ok(points)
+    }
+
+    // Reads a number of items, making sure that the data is long enough for them
+    // before they are allocated.
+    fn count(&mut self, item_size: usize) -> Result<usize, DecodeError> {
+        let count = self.u32()? as usize;
+        self.count_of(count, item_size)
+    }
+
+    fn count_of(&self, count: usize, item_size: usize) -> Result<usize, DecodeError> {
+        if count.saturating_mul(item_size) > self.data.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        Ok(count)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'l [u8], DecodeError> {
+        if len > self.data.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+fn square(min: f64, max: f64, counter_clockwise: bool) -> Vec<Point> {
+    let mut ring = vec![
+        point(min, min),
+        point(max, min),
+        point(max, max),
+        point(min, max),
+        point(min, min),
+    ];
+    if !counter_clockwise {
+        ring.reverse();
+    }
+
+    ring
+}
+
+#[cfg(test)]
+fn assert_area(area: f64, expected: f64) {
+    assert!((area - expected).abs() < 1e-9, "{} != {}", area, expected);
+}
+
+#[cfg(test)]
+fn encode_points(output: &mut Vec<u8>, points: &[Point]) {
+    output.extend_from_slice(&(points.len() as u32).to_be_bytes());
+    for p in points {
+        output.extend_from_slice(&p.x.to_be_bytes());
+        output.extend_from_slice(&p.y.to_be_bytes());
+    }
+}
+
+#[test]
+fn wkb() {
+    // A big endian multi-polygon with a clockwise exterior and a hole with the same
+    // orientation, followed by a little endian point with z and m coordinates.
+    let mut polygon = vec![0];
+    polygon.extend_from_slice(&POLYGON.to_be_bytes());
+    polygon.extend_from_slice(&2u32.to_be_bytes());
+    encode_points(&mut polygon, &square(0.0, 10.0, false));
+    encode_points(&mut polygon, &square(4.0, 6.0, false));
+
+    let mut data = vec![0];
+    data.extend_from_slice(&MULTI_POLYGON.to_be_bytes());
+    data.extend_from_slice(&2u32.to_be_bytes());
+    data.extend_from_slice(&polygon);
+    data.extend_from_slice(&polygon);
+
+    let geometry = decode_wkb(&data).unwrap();
+    let polygons = match &geometry {
+        Geometry::Polygons(polygons) => polygons,
+        _ => panic!(),
+    };
+    assert_eq!(polygons.len(), 2);
+    assert_eq!(polygons[0].exterior.len(), 4);
+    assert_area(ring_area(&polygons[0].exterior), 100.0);
+    assert_area(ring_area(&polygons[0].holes[0]), -4.0);
+    assert_area(signed_area(geometry.to_path().iter()), 192.0);
+
+    let mut point_zm = vec![1];
+    point_zm.extend_from_slice(&(POINT + 3000).to_le_bytes());
+    for v in &[1.0f64, 2.0, 3.0, 4.0] {
+        point_zm.extend_from_slice(&v.to_le_bytes());
+    }
+    assert_eq!(
+        decode_wkb(&point_zm),
+        Ok(Geometry::Points(vec![point(1.0, 2.0)]))
+    );
+
+    let mut collection = vec![1];
+    collection.extend_from_slice(&(GEOMETRY_COLLECTION | EWKB_SRID).to_le_bytes());
+    collection.extend_from_slice(&4326u32.to_le_bytes());
+    collection.extend_from_slice(&2u32.to_le_bytes());
+    collection.extend_from_slice(&point_zm);
+    collection.extend_from_slice(&data);
+    assert!(matches!(
+        decode_wkb(&collection),
+        Ok(Geometry::Collection(ref geometries)) if geometries.len() == 2
+    ));
+
+    assert_eq!(
+        decode_wkb(&data[..data.len() - 1]),
+        Err(DecodeError::UnexpectedEnd)
+    );
+    let mut invalid = data.clone();
+    invalid[4] = MULTI_POINT as u8;
+    assert_eq!(
+        decode_wkb(&invalid),
+        Err(DecodeError::GeometryType {
+            geometry_type: POLYGON
+        })
+    );
+
+    // Collections nested in a loop until the end of the data.
+    let mut nested = Vec::new();
+    for _ in 0..100_000 {
+        nested.push(1);
+        nested.extend_from_slice(&GEOMETRY_COLLECTION.to_le_bytes());
+        nested.extend_from_slice(&1u32.to_le_bytes());
+    }
+    assert_eq!(decode_wkb(&nested), Err(DecodeError::Nesting));
+    assert_eq!(
+        decode_wkb(&nested[..9 * MAX_NESTING]),
+        Err(DecodeError::UnexpectedEnd)
+    );
+}
+
+#[test]
+fn shapefile() {
+    // A polygon with an exterior, a hole and an island in the hole, all
+    // counter-clockwise, and a polyline.
+    let rings = [
+        square(0.0, 10.0, true),
+        square(2.0, 8.0, true),
+        square(4.0, 6.0, true),
+    ];
+    let mut polygon = Vec::new();
+    polygon.extend_from_slice(&SHP_POLYGON.to_le_bytes());
+    polygon.extend_from_slice(&[0; 32]);
+    polygon.extend_from_slice(&3u32.to_le_bytes());
+    polygon.extend_from_slice(&15u32.to_le_bytes());
+    for part in &[0u32, 5, 10] {
+        polygon.extend_from_slice(&part.to_le_bytes());
+    }
+    for p in rings.iter().flatten() {
+        polygon.extend_from_slice(&p.x.to_le_bytes());
+        polygon.extend_from_slice(&p.y.to_le_bytes());
+    }
+
+    let mut line = Vec::new();
+    line.extend_from_slice(&SHP_POLY_LINE.to_le_bytes());
+    line.extend_from_slice(&[0; 32]);
+    line.extend_from_slice(&1u32.to_le_bytes());
+    line.extend_from_slice(&2u32.to_le_bytes());
+    line.extend_from_slice(&0u32.to_le_bytes());
+    for v in &[0.0f64, 0.0, 3.0, 4.0] {
+        line.extend_from_slice(&v.to_le_bytes());
+    }
+
+    let mut records = Vec::new();
+    for (idx, content) in [&polygon, &SHP_NULL.to_le_bytes().to_vec(), &line]
+        .iter()
+        .enumerate()
+    {
+        records.extend_from_slice(&(idx as u32 + 1).to_be_bytes());
+        records.extend_from_slice(&(content.len() as u32 / 2).to_be_bytes());
+        records.extend_from_slice(content);
+    }
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&9994u32.to_be_bytes());
+    data.extend_from_slice(&[0; 20]);
+    data.extend_from_slice(&((100 + records.len()) as u32 / 2).to_be_bytes());
+    data.extend_from_slice(&1000u32.to_le_bytes());
+    data.extend_from_slice(&SHP_POLYGON.to_le_bytes());
+    data.extend_from_slice(&[0; 64]);
+    data.extend_from_slice(&records);
+
+    let shapes = decode_shapefile(&data).unwrap();
+    assert_eq!(shapes.len(), 3);
+    let polygons = match &shapes[0] {
+        Some(Geometry::Polygons(polygons)) => polygons,
+        _ => panic!(),
+    };
+    assert_eq!(polygons.len(), 2);
+    assert_area(ring_area(&polygons[0].exterior), 100.0);
+    assert_eq!(polygons[0].holes.len(), 1);
+    assert_area(ring_area(&polygons[0].holes[0]), -36.0);
+    assert_area(ring_area(&polygons[1].exterior), 4.0);
+    assert!(polygons[1].holes.is_empty());
+    assert_eq!(shapes[1], None);
+    assert_eq!(
+        shapes[2],
+        Some(Geometry::LineStrings(vec![vec![
+            point(0.0, 0.0),
+            point(3.0, 4.0)
+        ]]))
+    );
+
+    assert_eq!(decode_shapefile(&data[..50]), Err(DecodeError::Header));
+    assert_eq!(
+        decode_shape(&31u32.to_le_bytes()),
+        Err(DecodeError::ShapeType { shape_type: 31 })
+    );
+}
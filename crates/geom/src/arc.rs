@@ -291,6 +291,36 @@ impl<S: Scalar> Arc<S> {
         arc
     }
 
+    /// Returns true if the arc is a section of a circle.
+    pub fn is_circular(&self) -> bool {
+        self.radii.x == self.radii.y
+    }
+
+    /// Returns the arc at a constant distance from this one.
+    ///
+    /// Positive distances move the arc away from its center and negative distances
+    /// towards it, so the two sides of a stroke of width `w` are given by
+    /// `arc.offset(w / 2.0)` and `arc.offset(-w / 2.0)`.
+    ///
+    /// Unlike offsetting a flattened approximation, the result is exact, however it is
+    /// only defined for circular arcs: this returns `None` if the radii are not equal
+    /// or if the offset arc would have a negative radius.
+    pub fn offset(&self, distance: S) -> Option<Self> {
+        if !self.is_circular() {
+            return None;
+        }
+
+        let radius = self.radii.x + distance;
+        if radius < S::ZERO {
+            return None;
+        }
+
+        Some(Arc {
+            radii: vector(radius, radius),
+            ..*self
+        })
+    }
+
     /// Approximates the curve with sequence of line segments.
     ///
     /// The `tolerance` parameter defines the maximum distance between the curve and
@@ -1176,3 +1206,40 @@ fn negative_flattening_step() {
 
     assert!(flattened.len() > 1);
 }
+
+#[test]
+fn circular_arc_offset() {
+    use euclid::approxeq::ApproxEq;
+
+    let arc = Arc {
+        center: point(1.0f64, 2.0),
+        radii: vector(10.0, 10.0),
+        start_angle: Angle::radians(0.5),
+        sweep_angle: Angle::radians(2.0),
+        x_rotation: Angle::radians(0.3),
+    };
+    assert!(arc.is_circular());
+
+    for &distance in &[5.0, -5.0, 0.0] {
+        let offset = arc.offset(distance).unwrap();
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            // The points move along the radius of the circle.
+            let radial = arc.sample(t) - arc.center;
+            let expected = arc.sample(t) + radial.normalize() * distance;
+            assert!(offset
+                .sample(t)
+                .approx_eq_eps(&expected, &point(1e-9, 1e-9)));
+        }
+    }
+
+    assert_eq!(arc.offset(-10.0).unwrap().radii, vector(0.0, 0.0));
+    assert!(arc.offset(-11.0).is_none());
+
+    let ellipse = Arc {
+        radii: vector(10.0, 5.0),
+        ..arc
+    };
+    assert!(!ellipse.is_circular());
+    assert!(ellipse.offset(1.0).is_none());
+}
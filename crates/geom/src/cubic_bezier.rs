@@ -1,5 +1,5 @@
 use crate::cubic_bezier_intersections::cubic_bezier_intersections_t;
-use crate::scalar::Scalar;
+use crate::scalar::{cast, Scalar};
 use crate::segment::{BoundingBox, Segment};
 use crate::traits::Transformation;
 use crate::utils::{cubic_polynomial_roots, min_max};
@@ -147,6 +147,31 @@ impl<S: Scalar> CubicBezierSegment<S> {
         self.from.y * c0 + self.ctrl1.y * c1 + self.ctrl2.y * c2 + self.to.y * c3
     }
 
+    /// Sample the curve's second derivative at t (expecting t between 0 and 1).
+    pub fn second_derivative(&self, t: S) -> Vector<S> {
+        let a = self.ctrl2.to_vector() - self.ctrl1.to_vector() * S::TWO + self.from.to_vector();
+        let b = self.to.to_vector() - self.ctrl2.to_vector() * S::TWO + self.ctrl1.to_vector();
+
+        (a * (S::ONE - t) + b * t) * S::SIX
+    }
+
+    /// Sample the signed curvature of the curve at t (expecting t between 0 and 1).
+    ///
+    /// The curvature is the inverse of the radius of the osculating circle. It is positive
+    /// where the curve turns in the direction of the positive angles (for example from the
+    /// x axis towards the y axis) and changes sign at inflection points.
+    ///
+    /// Returns zero where the derivative vanishes.
+    pub fn curvature(&self, t: S) -> S {
+        let d = self.derivative(t);
+        let speed = d.length();
+        if speed == S::ZERO {
+            return S::ZERO;
+        }
+
+        d.cross(self.second_derivative(t)) / (speed * speed * speed)
+    }
+
     /// Return the sub-curve inside a given range of t.
     ///
     /// This is equivalent to splitting at the range's end points.
@@ -594,6 +619,112 @@ impl<S: Scalar> CubicBezierSegment<S> {
         length
     }
 
+    /// Compute the arc length of the segment using adaptive Gauss-Legendre quadrature.
+    ///
+    /// This is more precise than `approximate_length` for a given tolerance. The
+    /// `tolerance` parameter is the maximum allowed error.
+    pub fn arc_length(&self, tolerance: S) -> S {
+        let whole = self.gauss_legendre_length(S::ZERO, S::ONE);
+
+        self.adaptive_length(S::ZERO, S::ONE, whole, tolerance, 0)
+    }
+
+    fn adaptive_length(&self, t0: S, t1: S, whole: S, tolerance: S, depth: u32) -> S {
+        let mid = (t0 + t1) * S::HALF;
+        let left = self.gauss_legendre_length(t0, mid);
+        let right = self.gauss_legendre_length(mid, t1);
+        let sum = left + right;
+
+        if depth >= 16 || (sum - whole).abs() <= tolerance {
+            return sum;
+        }
+
+        let tolerance = tolerance * S::HALF;
+
+        self.adaptive_length(t0, mid, left, tolerance, depth + 1)
+            + self.adaptive_length(mid, t1, right, tolerance, depth + 1)
+    }
+
+    // 5 points Gauss-Legendre quadrature of the speed of the curve over a range of t.
+    fn gauss_legendre_length(&self, t0: S, t1: S) -> S {
+        const NODES: [(f64, f64); 5] = [
+            (0.0, 0.5688888888888889),
+            (-0.5384693101056831, 0.4786286704993665),
+            (0.5384693101056831, 0.4786286704993665),
+            (-0.906179845938664, 0.2369268850561891),
+            (0.906179845938664, 0.2369268850561891),
+        ];
+
+        let half = (t1 - t0) * S::HALF;
+        let mid = t0 + half;
+        let mut sum = S::ZERO;
+        for &(x, w) in &NODES {
+            let t = mid + half * cast::<f64, S>(x).unwrap();
+            sum += self.derivative(t).length() * cast::<f64, S>(w).unwrap();
+        }
+
+        sum * half
+    }
+
+    /// Find the value of t that corresponds to the closest point on the curve to `pos`.
+    ///
+    /// The curve is first sampled to find an approximate solution which is then refined
+    /// using Newton's method.
+    pub fn closest_point(&self, pos: Point<S>) -> S {
+        const NUM_SAMPLES: u32 = 16;
+
+        let mut best_t = S::ZERO;
+        let mut best_sq_dist = (self.from - pos).square_length();
+        for i in 1..=NUM_SAMPLES {
+            let t = cast::<u32, S>(i).unwrap() / cast::<u32, S>(NUM_SAMPLES).unwrap();
+            let d = (self.sample(t) - pos).square_length();
+            if d < best_sq_dist {
+                best_sq_dist = d;
+                best_t = t;
+            }
+        }
+
+        // Minimize |B(t) - pos|² by finding a root of its derivative
+        // f(t) = (B(t) - pos) . B'(t).
+        let mut t = best_t;
+        for _ in 0..8 {
+            let v = self.sample(t) - pos;
+            let d1 = self.derivative(t);
+            let d2 = self.second_derivative(t);
+            let numerator = v.dot(d1);
+            let denominator = d1.dot(d1) + v.dot(d2);
+            if denominator.abs() < S::EPSILON {
+                break;
+            }
+
+            let new_t = (t - numerator / denominator).max(S::ZERO).min(S::ONE);
+            let converged = (new_t - t).abs() < S::EPSILON;
+            t = new_t;
+            if converged {
+                break;
+            }
+        }
+
+        if (self.sample(t) - pos).square_length() < best_sq_dist {
+            t
+        } else {
+            best_t
+        }
+    }
+
+    /// Returns the shortest distance between this segment and a point.
+    pub fn distance_to_point(&self, pos: Point<S>) -> S {
+        (self.sample(self.closest_point(pos)) - pos).length()
+    }
+
+    /// Returns the shortest squared distance between this segment and a point.
+    ///
+    /// May be useful to avoid the cost of a square root when comparing against a distance
+    /// that can be squared instead.
+    pub fn square_distance_to_point(&self, pos: Point<S>) -> S {
+        (self.sample(self.closest_point(pos)) - pos).square_length()
+    }
+
     /// Invokes a callback at each inflection point if any.
     pub fn for_each_inflection_t<F>(&self, cb: &mut F)
     where
@@ -2061,3 +2192,93 @@ fn test_cubic_to_quadratics() {
     assert!(count < 10);
     assert!(count > 4);
 }
+
+#[test]
+fn arc_length() {
+    let line = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(1.0, 0.0),
+        ctrl2: point(2.0, 0.0),
+        to: point(3.0, 0.0),
+    };
+    assert!((line.arc_length(1e-9) - 3.0).abs() < 1e-9);
+
+    let curve = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(10.0, 20.0),
+        ctrl2: point(-5.0, 15.0),
+        to: point(10.0, 0.0),
+    };
+    let reference = curve.approximate_length(1e-6);
+    assert!((curve.arc_length(1e-6) - reference).abs() < 1e-4);
+
+    let (a, b) = curve.split(0.3);
+    let sum = a.arc_length(1e-6) + b.arc_length(1e-6);
+    assert!((curve.arc_length(1e-6) - sum).abs() < 1e-5);
+}
+
+#[test]
+fn cubic_closest_point() {
+    let curve = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(0.0, 10.0),
+        ctrl2: point(10.0, 10.0),
+        to: point(10.0, 0.0),
+    };
+
+    // The curve is symmetric so the closest point to a point on the axis of
+    // symmetry is in the middle.
+    let t = curve.closest_point(point(5.0, 20.0));
+    assert!((t - 0.5).abs() < 1e-6);
+    assert!((curve.distance_to_point(point(5.0, 20.0)) - 12.5).abs() < 1e-6);
+
+    assert_eq!(curve.closest_point(point(-1.0, -1.0)), 0.0);
+    assert_eq!(curve.closest_point(point(11.0, -1.0)), 1.0);
+
+    let p = curve.sample(0.37);
+    assert!((curve.closest_point(p) - 0.37).abs() < 1e-6);
+    assert!(curve.square_distance_to_point(p) < 1e-12);
+}
+
+#[test]
+fn cubic_second_derivative() {
+    let curve = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(1.0, 3.0),
+        ctrl2: point(5.0, -2.0),
+        to: point(6.0, 1.0),
+    };
+
+    let h = 1e-5;
+    for &t in &[0.1, 0.5, 0.9] {
+        let expected = (curve.derivative(t + h) - curve.derivative(t - h)) / (2.0 * h);
+        assert!((curve.second_derivative(t) - expected).length() < 1e-4);
+    }
+}
+
+#[test]
+fn cubic_curvature() {
+    // Approximation of a quarter of the unit circle.
+    let k = 0.5522847498f64;
+    let arc = CubicBezierSegment {
+        from: point(1.0, 0.0),
+        ctrl1: point(1.0, k),
+        ctrl2: point(k, 1.0),
+        to: point(0.0, 1.0),
+    };
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        assert!((arc.curvature(t) - 1.0).abs() < 0.05);
+    }
+
+    // The curvature changes sign at the inflection point.
+    let s_curve = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(1.0, 1.0),
+        ctrl2: point(2.0, -1.0),
+        to: point(3.0, 0.0),
+    };
+    assert!(s_curve.curvature(0.1) < 0.0);
+    assert!(s_curve.curvature(0.9) > 0.0);
+    assert!(s_curve.curvature(0.5).abs() < 1e-12);
+}
@@ -91,6 +91,7 @@ pub mod arc;
 pub mod cubic_bezier;
 mod cubic_bezier_intersections;
 mod line;
+pub mod predicates;
 pub mod quadratic_bezier;
 mod triangle;
 pub mod utils;
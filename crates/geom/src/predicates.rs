@@ -0,0 +1,279 @@
+//! Robust geometric predicates.
+//!
+//! The predicates first evaluate their determinant with regular floating point
+//! arithmetic and check the result against an error bound. When the sign can't be
+//! trusted, the determinant is evaluated again with exact arithmetic, representing
+//! values as sums of non-overlapping floats (expansions), as described in
+//! Jonathan Shewchuk's "Adaptive Precision Floating-Point Arithmetic and Fast
+//! Robust Geometric Predicates".
+//!
+//! The sign of the returned values is always exact (assuming no overflow or
+//! underflow), while their magnitude is only an approximation of the determinant.
+
+use crate::scalar::Scalar;
+use crate::Point;
+use arrayvec::ArrayVec;
+
+/// Returns a positive value if `a`, `b` and `c` are in counter-clockwise order in
+/// a y-up coordinate system, a negative value if they are in clockwise order and
+/// zero if they are aligned.
+///
+/// The value approximates `(b - a).cross(c - a)`, twice the signed area of the
+/// triangle.
+pub fn orient2d<S: Scalar>(a: Point<S>, b: Point<S>, c: Point<S>) -> S {
+    let det_left = (a.x - c.x) * (b.y - c.y);
+    let det_right = (a.y - c.y) * (b.x - c.x);
+    let det = det_left - det_right;
+
+    let eps = epsilon::<S>();
+    let error_bound = (S::THREE + S::value(16.0) * eps) * eps * (det_left.abs() + det_right.abs());
+    if det.abs() > error_bound {
+        return det;
+    }
+
+    orient2d_exact(a, b, c)
+}
+
+/// Returns a positive value if `d` is inside of the circle passing through `a`, `b`
+/// and `c`, a negative value if it is outside and zero if the four points are on
+/// the same circle.
+///
+/// `a`, `b` and `c` must be in counter-clockwise order in a y-up coordinate system
+/// (see `orient2d`), otherwise the sign of the result is reversed.
+pub fn incircle<S: Scalar>(a: Point<S>, b: Point<S>, c: Point<S>, d: Point<S>) -> S {
+    let ad = a - d;
+    let bd = b - d;
+    let cd = c - d;
+
+    let bc = bd.x * cd.y;
+    let cb = cd.x * bd.y;
+    let a_lift = ad.x * ad.x + ad.y * ad.y;
+
+    let ca = cd.x * ad.y;
+    let ac = ad.x * cd.y;
+    let b_lift = bd.x * bd.x + bd.y * bd.y;
+
+    let ab = ad.x * bd.y;
+    let ba = bd.x * ad.y;
+    let c_lift = cd.x * cd.x + cd.y * cd.y;
+
+    let det = a_lift * (bc - cb) + b_lift * (ca - ac) + c_lift * (ab - ba);
+
+    let permanent = (bc.abs() + cb.abs()) * a_lift
+        + (ca.abs() + ac.abs()) * b_lift
+        + (ab.abs() + ba.abs()) * c_lift;
+
+    let eps = epsilon::<S>();
+    let error_bound = (S::TEN + S::value(96.0) * eps) * eps * permanent;
+    if det.abs() > error_bound {
+        return det;
+    }
+
+    incircle_exact(a, b, c, d)
+}
+
+fn orient2d_exact<S: Scalar>(a: Point<S>, b: Point<S>, c: Point<S>) -> S {
+    let mut det: Expansion<S, 12> = Expansion::new();
+    for &(u, v, sign) in &[
+        (a.x, b.y, S::ONE),
+        (a.x, c.y, -S::ONE),
+        (a.y, b.x, -S::ONE),
+        (a.y, c.x, S::ONE),
+        (b.x, c.y, S::ONE),
+        (b.y, c.x, -S::ONE),
+    ] {
+        let (hi, lo) = two_product(u, v);
+        det.add(hi * sign);
+        det.add(lo * sign);
+    }
+
+    det.estimate()
+}
+
+// Evaluates the determinant of the 4x4 matrix which rows are `[x, y, x² + y², 1]`
+// for each point, which has the same sign as the translated determinant used in
+// `incircle`. Each of its 48 terms is the product of four coordinates, computed
+// exactly as the sum of eight floats.
+fn incircle_exact<S: Scalar>(a: Point<S>, b: Point<S>, c: Point<S>, d: Point<S>) -> S {
+    let points = [a, b, c, d];
+    let mut det: Expansion<S, 384> = Expansion::new();
+    for i in 0..4 {
+        for j in 0..4 {
+            for k in 0..4 {
+                if i == j || j == k || k == i {
+                    continue;
+                }
+                // The remaining row, multiplied by the column of ones.
+                let m = 6 - i - j - k;
+                let sign = if permutation_is_even([i, j, k, m]) {
+                    S::ONE
+                } else {
+                    -S::ONE
+                };
+
+                let (x, y, l) = (points[i].x, points[j].y, points[k]);
+                for &lift in &[l.x, l.y] {
+                    let (hi, lo) = two_product(x, y);
+                    for &p in &[hi, lo] {
+                        let (hi, lo) = two_product(p, lift);
+                        for &p in &[hi, lo] {
+                            let (hi, lo) = two_product(p, lift);
+                            det.add(hi * sign);
+                            det.add(lo * sign);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    det.estimate()
+}
+
+fn permutation_is_even(mut p: [usize; 4]) -> bool {
+    let mut even = true;
+    for i in 0..4 {
+        while p[i] != i {
+            let j = p[i];
+            p.swap(i, j);
+            even = !even;
+        }
+    }
+
+    even
+}
+
+// Half of the distance between 1 and the next float, which bounds the relative
+// error of a rounded operation.
+#[inline]
+fn epsilon<S: Scalar>() -> S {
+    S::epsilon() * S::HALF
+}
+
+#[inline]
+fn two_sum<S: Scalar>(a: S, b: S) -> (S, S) {
+    let x = a + b;
+    let bv = x - a;
+    let av = x - bv;
+
+    (x, (a - av) + (b - bv))
+}
+
+#[inline]
+fn two_product<S: Scalar>(a: S, b: S) -> (S, S) {
+    let x = a * b;
+
+    (x, a.mul_add(b, -x))
+}
+
+// A sum of non-overlapping floats ordered by increasing magnitude, without zeroes.
+struct Expansion<S, const N: usize> {
+    components: ArrayVec<S, N>,
+}
+
+impl<S: Scalar, const N: usize> Expansion<S, N> {
+    fn new() -> Self {
+        Expansion {
+            components: ArrayVec::new(),
+        }
+    }
+
+    // Adds a float to the expansion, growing it by at most one component.
+    fn add(&mut self, value: S) {
+        let mut q = value;
+        let mut len = 0;
+        for i in 0..self.components.len() {
+            let (sum, error) = two_sum(q, self.components[i]);
+            q = sum;
+            if error != S::ZERO {
+                self.components[len] = error;
+                len += 1;
+            }
+        }
+        self.components.truncate(len);
+        if q != S::ZERO {
+            self.components.push(q);
+        }
+    }
+
+    // The sum of the components, which has the sign of the largest one.
+    fn estimate(&self) -> S {
+        self.components.iter().fold(S::ZERO, |sum, &c| sum + c)
+    }
+}
+
+#[cfg(test)]
+use crate::point;
+
+#[cfg(test)]
+fn sign(v: f64) -> f64 {
+    if v == 0.0 {
+        0.0
+    } else {
+        v.signum()
+    }
+}
+
+#[test]
+fn orientation() {
+    let a = point(0.0f64, 0.0);
+    let b = point(1.0, 0.0);
+    let c = point(0.0, 1.0);
+    assert!(orient2d(a, b, c) > 0.0);
+    assert!(orient2d(a, c, b) < 0.0);
+    assert_eq!(orient2d(a, b, point(2.0, 0.0)), 0.0);
+    assert_eq!(orient2d_exact(a, b, c), 1.0);
+    assert_eq!(orient2d_exact(a, c, b), -1.0);
+
+    // Nearly aligned points for which the naive formula gets the sign wrong.
+    let a = point(0.5f64, 0.5);
+    let b = point(12.0, 12.0);
+    let c = point(24.0, 24.0);
+    let mut wrong = 0;
+    for i in 0..128 {
+        for j in 0..128 {
+            let p = point(a.x + i as f64 * f64::EPSILON, a.y + j as f64 * f64::EPSILON);
+            let expected = (j as i64 - i as i64).signum() as f64;
+            let naive = (b - p).cross(c - p);
+            if sign(naive) != expected {
+                wrong += 1;
+            }
+            let robust = orient2d(p, b, c);
+            assert_eq!(sign(robust), expected);
+            // The predicate is consistent under permutation.
+            assert_eq!(sign(orient2d(b, c, p)), expected);
+            assert_eq!(sign(orient2d(b, p, c)), -expected);
+        }
+    }
+    assert!(wrong > 0);
+
+    let a = point(0.0f32, 0.0);
+    assert!(orient2d(a, point(1.0, 0.0), point(0.0, 1.0)) > 0.0);
+}
+
+#[test]
+fn in_circle() {
+    let a = point(0.0f64, 0.0);
+    let b = point(1.0, 0.0);
+    let c = point(0.0, 1.0);
+    assert!(incircle(a, b, c, point(0.25, 0.25)) > 0.0);
+    assert!(incircle(a, b, c, point(2.0, 2.0)) < 0.0);
+    assert!(incircle(a, c, b, point(0.25, 0.25)) < 0.0);
+    assert_eq!(incircle(a, b, c, point(1.0, 1.0)), 0.0);
+
+    assert!(incircle_exact(a, b, c, point(0.25, 0.25)) > 0.0);
+    assert!(incircle_exact(a, b, c, point(2.0, 2.0)) < 0.0);
+    assert!(incircle_exact(a, c, b, point(0.25, 0.25)) < 0.0);
+    assert_eq!(incircle_exact(a, b, c, point(1.0, 1.0)), 0.0);
+
+    // Points very close to the circle of radius 1 centered on (1, 1).
+    let (a, b, c) = (point(0.0f64, 1.0), point(1.0, 0.0), point(2.0, 1.0));
+    let d = point(1.0, 2.0);
+    assert_eq!(incircle(a, b, c, d), 0.0);
+    let inside = point(1.0, 2.0 - f64::EPSILON);
+    let outside = point(1.0, 2.0 + 2.0 * f64::EPSILON);
+    assert!(incircle(a, b, c, inside) > 0.0);
+    assert!(incircle(a, b, c, outside) < 0.0);
+    assert!(incircle(b, c, a, inside) > 0.0);
+    assert!(incircle(c, a, b, outside) < 0.0);
+}
@@ -72,6 +72,46 @@ impl<S: Scalar> QuadraticBezierSegment<S> {
         self.from.y * c0 + self.ctrl.y * c1 + self.to.y * c2
     }
 
+    /// Return the curve's second derivative, which is constant for quadratic bézier curves.
+    pub fn second_derivative(&self) -> Vector<S> {
+        (self.from.to_vector() - self.ctrl.to_vector() * S::TWO + self.to.to_vector()) * S::TWO
+    }
+
+    /// Sample the signed curvature of the curve at t (expecting t between 0 and 1).
+    ///
+    /// The curvature is the inverse of the radius of the osculating circle. It is positive
+    /// where the curve turns in the direction of the positive angles (for example from the
+    /// x axis towards the y axis).
+    ///
+    /// Returns zero where the derivative vanishes.
+    pub fn curvature(&self, t: S) -> S {
+        let d = self.derivative(t);
+        let speed = d.length();
+        if speed == S::ZERO {
+            return S::ZERO;
+        }
+
+        d.cross(self.second_derivative()) / (speed * speed * speed)
+    }
+
+    /// Find the advancement of the point of maximum curvature along the curve.
+    ///
+    /// A quadratic bézier curve is a section of a parabola, its curvature is maximal at the
+    /// vertex of the parabola if it is part of the curve, or at the endpoint closest to it
+    /// otherwise. Quadratic bézier curves don't have inflection points.
+    pub fn max_curvature_t(&self) -> S {
+        // The derivative is 2 * (a + b * t), the curvature is maximal where its length is
+        // minimal.
+        let a = self.ctrl - self.from;
+        let b = self.from.to_vector() - self.ctrl.to_vector() * S::TWO + self.to.to_vector();
+        let b2 = b.square_length();
+        if b2 == S::ZERO {
+            return S::ZERO;
+        }
+
+        (-a.dot(b) / b2).max(S::ZERO).min(S::ONE)
+    }
+
     /// Swap the beginning and the end of the segment.
     pub fn flip(&self) -> Self {
         QuadraticBezierSegment {
@@ -702,6 +742,65 @@ impl<S: Scalar> QuadraticBezierSegment<S> {
         result
     }
 
+    /// Computes the intersections (if any) between this segment and another quadratic
+    /// bézier segment.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the
+    /// curves. To get the intersection points, sample the curves at the corresponding values.
+    ///
+    /// Returns endpoint intersections where an endpoint intersects the interior of the other
+    /// curve, but not endpoint/endpoint intersections.
+    ///
+    /// Returns no intersections if either curve is a point.
+    pub fn quadratic_intersections_t(
+        &self,
+        curve: &QuadraticBezierSegment<S>,
+    ) -> ArrayVec<(S, S), 4> {
+        // Degree elevation does not change the parameterization of the curves so the
+        // parameters computed on the cubic curves apply to the quadratic ones.
+        let mut result = ArrayVec::new();
+        for t in self.to_cubic().cubic_intersections_t(&curve.to_cubic()) {
+            if result.try_push(t).is_err() {
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Computes the intersection points (if any) between this segment and another quadratic
+    /// bézier segment.
+    pub fn quadratic_intersections(
+        &self,
+        curve: &QuadraticBezierSegment<S>,
+    ) -> ArrayVec<Point<S>, 4> {
+        let mut result = ArrayVec::new();
+        for (t, _) in self.quadratic_intersections_t(curve) {
+            result.push(self.sample(t));
+        }
+
+        result
+    }
+
+    /// Computes the intersections (if any) between this segment and a cubic bézier segment.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the
+    /// curves. To get the intersection points, sample the curves at the corresponding values.
+    ///
+    /// Returns endpoint intersections where an endpoint intersects the interior of the other
+    /// curve, but not endpoint/endpoint intersections.
+    ///
+    /// Returns no intersections if either curve is a point.
+    pub fn cubic_intersections_t(&self, curve: &CubicBezierSegment<S>) -> ArrayVec<(S, S), 9> {
+        self.to_cubic().cubic_intersections_t(curve)
+    }
+
+    /// Computes the intersection points (if any) between this segment and a cubic bézier
+    /// segment.
+    pub fn cubic_intersections(&self, curve: &CubicBezierSegment<S>) -> ArrayVec<Point<S>, 9> {
+        self.to_cubic().cubic_intersections(curve)
+    }
+
     #[inline]
     pub fn from(&self) -> Point<S> {
         self.from
@@ -1540,3 +1639,68 @@ fn arc_length() {
         );
     }
 }
+
+#[test]
+fn quadratic_intersections() {
+    // Two parabolas crossing twice.
+    let a = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(5.0, 10.0),
+        to: point(10.0, 0.0),
+    };
+    let b = QuadraticBezierSegment {
+        from: point(0.0f64, 5.0),
+        ctrl: point(5.0, -5.0),
+        to: point(10.0, 5.0),
+    };
+
+    let intersections = a.quadratic_intersections_t(&b);
+    assert_eq!(intersections.len(), 2);
+    for (t1, t2) in intersections {
+        assert!((a.sample(t1) - b.sample(t2)).length() < 1e-6);
+        // By symmetry the intersections are at y = 2.5.
+        assert!((a.sample(t1).y - 2.5).abs() < 1e-6);
+    }
+
+    let points = a.quadratic_intersections(&b);
+    assert_eq!(points.len(), 2);
+
+    let far = QuadraticBezierSegment {
+        from: point(0.0f64, 50.0),
+        ctrl: point(5.0, 60.0),
+        to: point(10.0, 50.0),
+    };
+    assert!(a.quadratic_intersections_t(&far).is_empty());
+
+    let cubic = b.to_cubic();
+    assert_eq!(a.cubic_intersections_t(&cubic).len(), 2);
+    assert_eq!(a.cubic_intersections(&cubic).len(), 2);
+}
+
+#[test]
+fn quadratic_curvature() {
+    // The parabola y = x².
+    let curve = QuadraticBezierSegment {
+        from: point(-1.0f64, 1.0),
+        ctrl: point(0.0, -1.0),
+        to: point(1.0, 1.0),
+    };
+
+    assert_eq!(curve.max_curvature_t(), 0.5);
+    assert!((curve.curvature(0.5) - 2.0).abs() < 1e-12);
+    // At x = 1 the curvature of y = x² is 2 / 5^(3/2).
+    assert!((curve.curvature(1.0) - 2.0 / 5.0f64.powf(1.5)).abs() < 1e-12);
+    assert!((curve.flip().curvature(0.5) + 2.0).abs() < 1e-12);
+
+    // The vertex is outside of the curve.
+    let (_, right) = curve.split(0.75);
+    assert_eq!(right.max_curvature_t(), 0.0);
+
+    let line = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(1.0, 1.0),
+        to: point(2.0, 2.0),
+    };
+    assert_eq!(line.curvature(0.3), 0.0);
+    assert_eq!(line.max_curvature_t(), 0.0);
+}
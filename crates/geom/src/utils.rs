@@ -1,4 +1,5 @@
 use crate::scalar::{Float, Scalar};
+use crate::traits::Transformation;
 use crate::{vector, Point, Vector};
 use arrayvec::ArrayVec;
 
@@ -59,6 +60,22 @@ pub fn directed_angle2<S: Scalar>(center: Point<S>, a: Point<S>, b: Point<S>) ->
     directed_angle(a - center, b - center)
 }
 
+/// Returns the largest factor by which a transformation can scale distances.
+///
+/// This is the largest singular value of the linear part of the transformation,
+/// which accounts for non-uniform scales. A tolerance threshold divided by this
+/// factor still holds after applying the transformation.
+pub fn max_scale_factor<S: Scalar, T: Transformation<S>>(transform: &T) -> S {
+    let x = transform.transform_vector(vector(S::ONE, S::ZERO));
+    let y = transform.transform_vector(vector(S::ZERO, S::ONE));
+
+    let sum = x.square_length() + y.square_length();
+    let det = x.cross(y);
+    let discriminant = (sum * sum - S::FOUR * det * det).max(S::ZERO);
+
+    S::sqrt((sum + S::sqrt(discriminant)) * S::HALF)
+}
+
 pub fn cubic_polynomial_roots<S: Scalar>(a: S, b: S, c: S, d: S) -> ArrayVec<S, 3> {
     let mut result = ArrayVec::new();
 
@@ -175,3 +192,24 @@ fn cubic_polynomial() {
     // Constant.
     assert_approx_eq(cubic_polynomial_roots(0.0, 0.0, 0.0, 0.0), &[], 0.00005);
 }
+
+#[test]
+fn test_max_scale_factor() {
+    use crate::{Rotation, Scale, Transform};
+    use euclid::Angle;
+
+    let eps = 1e-12;
+    assert!((max_scale_factor(&Transform::<f64>::identity()) - 1.0).abs() < eps);
+    assert!((max_scale_factor(&Scale::new(3.0f64)) - 3.0).abs() < eps);
+    assert!((max_scale_factor(&Rotation::new(Angle::radians(1.0f64))) - 1.0).abs() < eps);
+
+    // Non-uniform scale followed by a rotation.
+    let transform = Transform::scale(2.0f64, -5.0).then_rotate(Angle::radians(0.7));
+    assert!((max_scale_factor(&transform) - 5.0).abs() < eps);
+
+    let skew = Transform::new(1.0f64, 0.0, 1.0, 1.0, 0.0, 0.0);
+    // The golden ratio.
+    assert!((max_scale_factor(&skew) - (1.0 + 5.0f64.sqrt()) / 2.0).abs() < eps);
+
+    assert_eq!(max_scale_factor(&Transform::scale(0.0f64, 0.0)), 0.0);
+}
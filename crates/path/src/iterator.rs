@@ -92,10 +92,40 @@ pub trait PathIterator: Iterator<Item = PathEvent> + Sized {
         Flattened::new(tolerance, self)
     }
 
+    /// Returns an iterator that turns curves into line segments, with a tolerance
+    /// threshold that holds after applying a transform.
+    ///
+    /// The events are not transformed. Instead, the tolerance is scaled by the largest
+    /// amount the transform can stretch distances (including with non-uniform scales),
+    /// so that a path flattened once renders smoothly with this transform applied.
+    fn flattened_for_transform<T: Transformation<f64>>(
+        self,
+        tolerance: f64,
+        transform: &T,
+    ) -> Flattened<Self> {
+        let scale = crate::geom::utils::max_scale_factor(transform);
+        let tolerance = if scale > 0.0 && scale.is_finite() {
+            tolerance / scale
+        } else {
+            tolerance
+        };
+
+        Flattened::new(tolerance, self)
+    }
+
     /// Returns an iterator applying a 2D transform to all of its events.
     fn transformed<T: Transformation<f64>>(self, mat: &T) -> Transformed<Self, T> {
         Transformed::new(mat, self)
     }
+
+    /// Returns an iterator that approximates cubic bézier curves with sequences of
+    /// quadratic bézier curves.
+    ///
+    /// The `tolerance` parameter defines the maximum distance between the cubic curves
+    /// and their approximation. Other events are forwarded unchanged.
+    fn cubics_to_quadratics(self, tolerance: f64) -> CubicsToQuadratics<Self> {
+        CubicsToQuadratics::new(tolerance, self)
+    }
 }
 
 impl<Iter> PathIterator for Iter where Iter: Iterator<Item = PathEvent> {}
@@ -224,6 +254,90 @@ where
     }
 }
 
+/// An iterator that consumes a `PathEvent` iterator and yields path events where cubic
+/// bézier curves are approximated with quadratic ones.
+pub struct CubicsToQuadratics<Iter> {
+    it: Iter,
+    current_curve: Option<CubicBezierSegment<f64>>,
+    current_index: u32,
+    num_quadratics: u32,
+    tolerance: f64,
+}
+
+impl<Iter: Iterator<Item = PathEvent>> CubicsToQuadratics<Iter> {
+    /// Create the iterator.
+    pub fn new(tolerance: f64, it: Iter) -> Self {
+        CubicsToQuadratics {
+            it,
+            current_curve: None,
+            current_index: 0,
+            num_quadratics: 0,
+            tolerance,
+        }
+    }
+}
+
+impl<Iter> Iterator for CubicsToQuadratics<Iter>
+where
+    Iter: Iterator<Item = PathEvent>,
+{
+    type Item = PathEvent;
+    fn next(&mut self) -> Option<PathEvent> {
+        if let Some(curve) = self.current_curve {
+            let n = self.num_quadratics;
+            let i = self.current_index;
+            let t0 = i as f64 / n as f64;
+            // Make sure the last quadratic curve ends at t = 1.0 exactly.
+            let t1 = if i + 1 == n {
+                1.0
+            } else {
+                (i + 1) as f64 / n as f64
+            };
+
+            self.current_index += 1;
+            if self.current_index == n {
+                self.current_curve = None;
+            }
+
+            let quad = curve.split_range(t0..t1).to_quadratic();
+            return Some(PathEvent::Quadratic {
+                from: quad.from,
+                ctrl: quad.ctrl,
+                to: quad.to,
+            });
+        }
+
+        match self.it.next() {
+            Some(PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            }) => {
+                let curve = CubicBezierSegment {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                };
+                self.num_quadratics = curve.num_quadratics(self.tolerance).max(1);
+                self.current_index = 0;
+                self.current_curve = Some(curve);
+                self.next()
+            }
+            evt => evt,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.current_curve {
+            Some(_) => (self.num_quadratics - self.current_index) as usize,
+            None => 0,
+        };
+        (self.it.size_hint().0 + remaining, None)
+    }
+}
+
 /// Applies a 2D transform to a path iterator and yields the resulting path iterator.
 pub struct Transformed<'l, I, T> {
     it: I,
@@ -434,3 +548,88 @@ fn test_from_polyline_closed() {
     );
     assert_eq!(evts.next(), None);
 }
+
+#[test]
+fn test_cubics_to_quadratics() {
+    let mut builder = crate::Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.cubic_bezier_to(point(20.0, 0.0), point(20.0, 10.0), point(10.0, 10.0));
+    builder.quadratic_bezier_to(point(0.0, 10.0), point(0.0, 0.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let cubic = CubicBezierSegment {
+        from: point(10.0, 0.0),
+        ctrl1: point(20.0, 0.0),
+        ctrl2: point(20.0, 10.0),
+        to: point(10.0, 10.0),
+    };
+    let tolerance = 0.01;
+    let n = cubic.num_quadratics(tolerance) as usize;
+    assert!(n > 1);
+
+    let events: alloc::vec::Vec<PathEvent> = path.iter().cubics_to_quadratics(tolerance).collect();
+    // begin, line, n quadratics, the original quadratic, end.
+    assert_eq!(events.len(), n + 4);
+    assert!(!events
+        .iter()
+        .any(|evt| matches!(evt, PathEvent::Cubic { .. })));
+
+    let mut position = point(10.0, 0.0);
+    for (i, evt) in events[2..(n + 2)].iter().enumerate() {
+        match *evt {
+            PathEvent::Quadratic { from, ctrl, to } => {
+                // The sub-curves are contiguous.
+                assert_eq!(from, position);
+                position = to;
+                let quad = QuadraticBezierSegment { from, ctrl, to };
+                let t = (i as f64 + 0.5) / n as f64;
+                assert!((quad.sample(0.5) - cubic.sample(t)).length() < tolerance);
+            }
+            _ => panic!("unexpected event {:?}", evt),
+        }
+    }
+    assert_eq!(position, point(10.0, 10.0));
+
+    assert_eq!(
+        events[n + 2],
+        PathEvent::Quadratic {
+            from: point(10.0, 10.0),
+            ctrl: point(0.0, 10.0),
+            to: point(0.0, 0.0),
+        }
+    );
+}
+
+#[test]
+fn test_flattened_for_transform() {
+    let mut builder = crate::Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.quadratic_bezier_to(point(1.0, 1.0), point(2.0, 0.0));
+    builder.end(false);
+    let path = builder.build();
+
+    let identity = Transform::identity();
+    let zoom = Transform::scale(1.0, 100.0);
+
+    let num_identity = path.iter().flattened_for_transform(0.01, &identity).count();
+    let num_default = path.iter().flattened(0.01).count();
+    let num_zoom = path.iter().flattened_for_transform(0.01, &zoom).count();
+    assert_eq!(num_identity, num_default);
+    assert!(num_zoom > num_identity);
+
+    // The approximation stays within the tolerance after applying the transform.
+    let curve = QuadraticBezierSegment {
+        from: point(0.0, 0.0),
+        ctrl: point(1.0, 1.0),
+        to: point(2.0, 0.0),
+    }
+    .transformed(&zoom);
+    for evt in path.iter().flattened_for_transform(0.01, &zoom) {
+        if let PathEvent::Line { from, to } = evt {
+            let mid = zoom.transform_point(from.lerp(to, 0.5));
+            assert!(curve.distance_to_point(mid) <= 0.01);
+        }
+    }
+}
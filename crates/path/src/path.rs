@@ -16,7 +16,7 @@ use core::fmt;
 use core::iter::{FromIterator, IntoIterator};
 use core::u32;
 
-use alloc::boxed::Box;
+use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
 
@@ -71,11 +71,17 @@ pub(crate) enum Verb {
 /// |_________|__________|_________|__________|_________|_________|__________|_
 /// ```
 ///
+/// # Sharing
+///
+/// The buffers are reference counted and immutable, so cloning a path is cheap and
+/// paths can be shared between threads. Modifying a path (for example with
+/// [`set_endpoint`](struct.Path.html#method.set_endpoint)) copies the buffers
+/// first if they are shared with another path.
 #[derive(Clone, Default)]
 #[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
 pub struct Path {
-    points: Box<[Point]>,
-    verbs: Box<[Verb]>,
+    points: Arc<[Point]>,
+    verbs: Arc<[Verb]>,
     num_attributes: usize,
 }
 
@@ -111,8 +117,8 @@ impl Path {
     #[inline]
     pub fn new() -> Path {
         Path {
-            points: Box::new([]),
-            verbs: Box::new([]),
+            points: Arc::new([]),
+            verbs: Arc::new([]),
             num_attributes: 0,
         }
     }
@@ -120,8 +126,8 @@ impl Path {
     #[inline]
     pub fn with_attributes(num_attributes: usize) -> Path {
         Path {
-            points: Box::new([]),
-            verbs: Box::new([]),
+            points: Arc::new([]),
+            verbs: Arc::new([]),
             num_attributes,
         }
     }
@@ -182,34 +188,59 @@ impl Path {
         self.as_slice().last_endpoint()
     }
 
+    /// Moves an endpoint.
+    ///
+    /// The storage is copied first if it is shared with other paths.
+    pub fn set_endpoint(&mut self, id: EndpointId, position: Point) {
+        self.points_mut()[id.to_usize()] = position;
+    }
+
+    /// Moves a control point.
+    ///
+    /// The storage is copied first if it is shared with other paths.
+    pub fn set_control_point(&mut self, id: ControlPointId, position: Point) {
+        self.points_mut()[id.to_usize()] = position;
+    }
+
+    /// Returns true if both paths share the same storage, in which case they are
+    /// equal.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Path) -> bool {
+        Arc::ptr_eq(&self.points, &other.points) && Arc::ptr_eq(&self.verbs, &other.verbs)
+    }
+
+    // Returns the points, copying them if they are shared.
+    fn points_mut(&mut self) -> &mut [Point] {
+        if Arc::get_mut(&mut self.points).is_none() {
+            self.points = self.points[..].into();
+        }
+
+        Arc::get_mut(&mut self.points).unwrap()
+    }
+
     fn apply_transform<T: Transformation<f64>>(&mut self, transform: &T) {
-        let iter = IdIter::new(self.num_attributes, &self.verbs[..]);
+        let verbs = self.verbs.clone();
+        let iter = IdIter::new(self.num_attributes, &verbs[..]);
+        let points = self.points_mut();
 
         for evt in iter {
             match evt {
                 IdEvent::Begin { at } => {
-                    self.points[at.to_usize()] =
-                        transform.transform_point(self.points[at.to_usize()]);
+                    points[at.to_usize()] = transform.transform_point(points[at.to_usize()]);
                 }
                 IdEvent::Line { to, .. } => {
-                    self.points[to.to_usize()] =
-                        transform.transform_point(self.points[to.to_usize()]);
+                    points[to.to_usize()] = transform.transform_point(points[to.to_usize()]);
                 }
                 IdEvent::Quadratic { ctrl, to, .. } => {
-                    self.points[ctrl.to_usize()] =
-                        transform.transform_point(self.points[ctrl.to_usize()]);
-                    self.points[to.to_usize()] =
-                        transform.transform_point(self.points[to.to_usize()]);
+                    points[ctrl.to_usize()] = transform.transform_point(points[ctrl.to_usize()]);
+                    points[to.to_usize()] = transform.transform_point(points[to.to_usize()]);
                 }
                 IdEvent::Cubic {
                     ctrl1, ctrl2, to, ..
                 } => {
-                    self.points[ctrl1.to_usize()] =
-                        transform.transform_point(self.points[ctrl1.to_usize()]);
-                    self.points[ctrl2.to_usize()] =
-                        transform.transform_point(self.points[ctrl2.to_usize()]);
-                    self.points[to.to_usize()] =
-                        transform.transform_point(self.points[to.to_usize()]);
+                    points[ctrl1.to_usize()] = transform.transform_point(points[ctrl1.to_usize()]);
+                    points[ctrl2.to_usize()] = transform.transform_point(points[ctrl2.to_usize()]);
+                    points[to.to_usize()] = transform.transform_point(points[to.to_usize()]);
                 }
                 IdEvent::End { .. } => {}
             }
@@ -604,8 +635,8 @@ impl Build for BuilderImpl {
     fn build(self) -> Path {
         self.validator.build();
         Path {
-            points: self.points.into_boxed_slice(),
-            verbs: self.verbs.into_boxed_slice(),
+            points: self.points.into(),
+            verbs: self.verbs.into(),
             num_attributes: 0,
         }
     }
@@ -744,8 +775,8 @@ impl BuilderWithAttributes {
     pub fn build(self) -> Path {
         self.builder.validator.build();
         Path {
-            points: self.builder.points.into_boxed_slice(),
-            verbs: self.builder.verbs.into_boxed_slice(),
+            points: self.builder.points.into(),
+            verbs: self.builder.verbs.into(),
             num_attributes: self.num_attributes,
         }
     }
@@ -2200,3 +2231,31 @@ fn id_events() {
 
     assert_eq!(iter.next(), None);
 }
+
+#[test]
+fn shared_path() {
+    let mut builder = Path::builder();
+    let a = builder.begin(point(0.0, 0.0));
+    builder.quadratic_bezier_to(point(1.0, 0.0), point(1.0, 1.0));
+    builder.end(true);
+    let path = builder.build();
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    assert_send_sync(&path);
+
+    let mut copy = path.clone();
+    assert!(copy.ptr_eq(&path));
+
+    // Editing a shared path leaves the original untouched.
+    copy.set_endpoint(a, point(-1.0, 0.0));
+    copy.set_control_point(ControlPointId(1), point(2.0, 0.0));
+    assert!(!copy.ptr_eq(&path));
+    assert_eq!(path[a], point(0.0, 0.0));
+    assert_eq!(path[ControlPointId(1)], point(1.0, 0.0));
+    assert_eq!(copy[a], point(-1.0, 0.0));
+    assert_eq!(copy[ControlPointId(1)], point(2.0, 0.0));
+
+    let transformed = path.clone().transformed(&Translation::new(1.0, 0.0));
+    assert_eq!(transformed[a], point(1.0, 0.0));
+    assert_eq!(path[a], point(0.0, 0.0));
+}
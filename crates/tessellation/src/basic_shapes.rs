@@ -9,6 +9,13 @@ use std::f64::consts::PI;
 pub fn fill_rectangle(rect: &Box2D, output: &mut dyn FillGeometryBuilder) -> TessellationResult {
     output.begin_geometry();
 
+    // Like a path with no area, an empty or inverted rectangle doesn't produce any
+    // triangle.
+    if rect.is_empty() {
+        output.end_geometry();
+        return Ok(());
+    }
+
     let dummy_queue = EventQueue::new();
 
     let vertex = &mut |position| {
@@ -18,6 +25,7 @@ pub fn fill_rectangle(rect: &Box2D, output: &mut dyn FillGeometryBuilder) -> Tes
             current_event: INVALID_EVENT_ID,
             attrib_store: None,
             attrib_buffer: &mut [],
+            alpha: 1.0,
         })
     };
 
@@ -63,6 +71,7 @@ pub fn fill_circle(
             current_event,
             attrib_store,
             attrib_buffer: &mut [],
+            alpha: 1.0,
         })?,
         output.add_fill_vertex(FillVertex {
             position: center + (up * radius),
@@ -70,6 +79,7 @@ pub fn fill_circle(
             current_event,
             attrib_store,
             attrib_buffer: &mut [],
+            alpha: 1.0,
         })?,
         output.add_fill_vertex(FillVertex {
             position: center + (right * radius),
@@ -77,6 +87,7 @@ pub fn fill_circle(
             current_event,
             attrib_store,
             attrib_buffer: &mut [],
+            alpha: 1.0,
         })?,
         output.add_fill_vertex(FillVertex {
             position: center + (down * radius),
@@ -84,6 +95,7 @@ pub fn fill_circle(
             current_event,
             attrib_store,
             attrib_buffer: &mut [],
+            alpha: 1.0,
         })?,
     ];
 
@@ -177,6 +189,7 @@ fn fill_border_radius(
         current_event: INVALID_EVENT_ID,
         attrib_store: None,
         attrib_buffer: &mut [],
+        alpha: 1.0,
     })?;
 
     output.add_triangle(vb, vertex, va);
@@ -251,3 +264,42 @@ fn basic_shapes() {
         }
     }
 }
+
+#[test]
+fn degenerate_basic_shapes() {
+    use crate::geometry_builder::{simple_builder, VertexBuffers};
+
+    let mut tess = crate::FillTessellator::new();
+    let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+
+    tess.tessellate_rectangle(
+        &Box2D {
+            min: point(1.0, 1.0),
+            max: point(1.0, 5.0),
+        },
+        &FillOptions::DEFAULT,
+        &mut simple_builder(&mut buffers),
+    )
+    .unwrap();
+
+    tess.tessellate_rectangle(
+        &Box2D {
+            min: point(5.0, 1.0),
+            max: point(1.0, 5.0),
+        },
+        &FillOptions::DEFAULT,
+        &mut simple_builder(&mut buffers),
+    )
+    .unwrap();
+
+    tess.tessellate_circle(
+        point(1.0, 2.0),
+        0.0,
+        &FillOptions::DEFAULT,
+        &mut simple_builder(&mut buffers),
+    )
+    .unwrap();
+
+    assert!(buffers.vertices.is_empty());
+    assert!(buffers.indices.is_empty());
+}
@@ -0,0 +1,366 @@
+//! Decompose shapes into convex polygons, for example to build collision shapes.
+
+use crate::geometry_builder::{BuffersBuilder, VertexBuffers};
+use crate::math::{point, Point};
+use crate::path::{PathEvent, Polygon};
+use crate::{FillOptions, FillTessellator, FillVertex, TessellationError};
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// A convex polygon produced by `convex_decomposition`.
+///
+/// The points are expressed relative to the centroid, which is the usual
+/// representation of convex shapes in physics engines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexPolygon {
+    /// Position of the centroid of the polygon.
+    pub centroid: Point,
+    /// The vertices of the polygon relative to the centroid, in counter-clockwise
+    /// order in a y-up coordinate system (positive signed area).
+    pub points: Vec<Point>,
+    /// The area of the polygon.
+    pub area: f64,
+}
+
+impl ConvexPolygon {
+    /// Iterates over the vertices of the polygon in the coordinate space of the
+    /// original shape.
+    pub fn world_points(&self) -> impl Iterator<Item = Point> + '_ {
+        let offset = self.centroid.to_vector();
+        self.points.iter().map(move |p| *p + offset)
+    }
+}
+
+/// Decomposes the fill of a path into convex polygons.
+///
+/// The path is tessellated with the provided fill options and triangles are
+/// merged across their shared edges as long as the result is convex
+/// (Hertel-Mehlhorn), from the longest edge to the shortest. The result has at
+/// most four times as many pieces as an optimal decomposition, and usually much
+/// fewer than the number of triangles.
+///
+/// Vertices between two aligned edges are removed from the output.
+pub fn convex_decomposition(
+    path: impl IntoIterator<Item = PathEvent>,
+    options: &FillOptions,
+) -> Result<Vec<ConvexPolygon>, TessellationError> {
+    let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
+    FillTessellator::new().tessellate(
+        path,
+        options,
+        &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| vertex.position()),
+    )?;
+
+    let positions = &buffers.vertices;
+    let signed_area = |a: u32, b: u32, c: u32| {
+        let (a, b, c) = (
+            positions[a as usize],
+            positions[b as usize],
+            positions[c as usize],
+        );
+        (b - a).cross(c - a)
+    };
+
+    // Degenerate triangles don't cover anything and would share edges with the
+    // triangles around them.
+    let mut polygons: Vec<Option<Vec<u32>>> = Vec::with_capacity(buffers.indices.len() / 3);
+    for tri in buffers.indices.chunks_exact(3) {
+        let (a, b, c) = (tri[0], tri[1], tri[2]);
+        let area = signed_area(a, b, c);
+        if area > 0.0 {
+            polygons.push(Some(vec![a, b, c]));
+        } else if area < 0.0 {
+            polygons.push(Some(vec![a, c, b]));
+        }
+    }
+
+    // The polygon on the left of each directed edge. Overlapping triangles, for
+    // example around self-intersections, can have the same directed edge. The
+    // polygons are never merged across these edges.
+    let mut edges = HashMap::with_capacity(polygons.len() * 3);
+    let mut shared_edges = HashSet::new();
+    for (idx, polygon) in polygons.iter().enumerate() {
+        let polygon = polygon.as_ref().unwrap();
+        for k in 0..3 {
+            let edge = (polygon[k], polygon[(k + 1) % 3]);
+            if edges.insert(edge, idx).is_some() {
+                shared_edges.insert(edge);
+            }
+        }
+    }
+
+    let mut diagonals: Vec<(f64, u32, u32)> = edges
+        .keys()
+        .filter(|&&(a, b)| {
+            a < b
+                && edges.contains_key(&(b, a))
+                && !shared_edges.contains(&(a, b))
+                && !shared_edges.contains(&(b, a))
+        })
+        .map(|&(a, b)| {
+            let length = (positions[a as usize] - positions[b as usize]).square_length();
+            (length, a, b)
+        })
+        .collect();
+    // Break ties with the vertex ids so that the result does not depend on the
+    // iteration order of the hash map.
+    diagonals.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(Ordering::Equal)
+            .then((a.1, a.2).cmp(&(b.1, b.2)))
+    });
+
+    let mut merged = Vec::new();
+    for &(_, a, b) in &diagonals {
+        let (p, q) = match (edges.get(&(a, b)), edges.get(&(b, a))) {
+            (Some(&p), Some(&q)) if p != q => (p, q),
+            _ => {
+                continue;
+            }
+        };
+
+        let (p_vertices, q_vertices) = match (&polygons[p], &polygons[q]) {
+            (Some(p_vertices), Some(q_vertices)) => (p_vertices, q_vertices),
+            _ => continue,
+        };
+        // The edge must go from a to b in p and from b to a in q.
+        let (p_start, q_start) = match (find_edge(p_vertices, a, b), find_edge(q_vertices, b, a)) {
+            (Some(p_start), Some(q_start)) => (p_start, q_start),
+            _ => continue,
+        };
+
+        // Walk p from b back to a, then q from a to b.
+        merged.clear();
+        let start = (p_start + 1) % p_vertices.len();
+        for k in 0..p_vertices.len() {
+            merged.push(p_vertices[(start + k) % p_vertices.len()]);
+        }
+        let start = (q_start + 1) % q_vertices.len();
+        for k in 1..(q_vertices.len() - 1) {
+            merged.push(q_vertices[(start + k) % q_vertices.len()]);
+        }
+
+        // Only the corners at the endpoints of the removed edge change.
+        let n = merged.len();
+        let a_idx = p_vertices.len() - 1;
+        let is_convex =
+            |i: usize| signed_area(merged[(i + n - 1) % n], merged[i], merged[(i + 1) % n]) >= 0.0;
+        if !is_convex(0) || !is_convex(a_idx) {
+            continue;
+        }
+
+        // Polygons made of overlapping triangles can go around more than once while
+        // all of their corners are convex.
+        let is_simple = (1..n - 1).all(|i| signed_area(merged[0], merged[i], merged[i + 1]) >= 0.0)
+            && (1..n).all(|i| !merged[..i].contains(&merged[i]));
+        if !is_simple {
+            continue;
+        }
+
+        for k in 0..n {
+            edges.insert((merged[k], merged[(k + 1) % n]), p);
+        }
+        edges.remove(&(a, b));
+        edges.remove(&(b, a));
+        polygons[p] = Some(merged.clone());
+        polygons[q] = None;
+    }
+
+    let mut output = Vec::new();
+    for polygon in polygons.iter().flatten() {
+        let points: Vec<Point> = polygon.iter().map(|&v| positions[v as usize]).collect();
+
+        // Compute the centroid relative to the first point for better precision.
+        let origin = points[0];
+        let mut area = 0.0;
+        let mut centroid = point(0.0, 0.0);
+        for i in 1..(points.len() - 1) {
+            let b = points[i] - origin;
+            let c = points[i + 1] - origin;
+            let tri_area = b.cross(c) * 0.5;
+            area += tri_area;
+            centroid += (b + c) * (tri_area / 3.0);
+        }
+        if area <= 0.0 {
+            continue;
+        }
+        let centroid = origin + centroid.to_vector() / area;
+
+        // Drop the flat corners from the points relative to the centroid, since
+        // nearby points can become equal or aligned once they are moved. Removing a
+        // corner changes the neighbors of the next ones, so this is repeated until
+        // all of the corners are convex.
+        let mut points: Vec<Point> = points.iter().map(|p| (*p - centroid).to_point()).collect();
+        loop {
+            let n = points.len();
+            let mut kept: Vec<Point> = Vec::with_capacity(n);
+            for i in 0..n {
+                let prev = kept.last().copied().unwrap_or(points[(i + n - 1) % n]);
+                if (points[i] - prev).cross(points[(i + 1) % n] - points[i]) > 0.0 {
+                    kept.push(points[i]);
+                }
+            }
+            points = kept;
+            if points.len() < 3 || points.len() == n {
+                break;
+            }
+        }
+
+        if points.len() < 3 {
+            continue;
+        }
+
+        output.push(ConvexPolygon {
+            centroid,
+            points,
+            area,
+        });
+    }
+
+    Ok(output)
+}
+
+// The index of the vertex `from` if it is followed by `to` in the polygon.
+fn find_edge(polygon: &[u32], from: u32, to: u32) -> Option<usize> {
+    let n = polygon.len();
+    (0..n).find(|&k| polygon[k] == from && polygon[(k + 1) % n] == to)
+}
+
+/// Decomposes a polygon into convex polygons, for example to create collision
+/// shapes for a physics engine.
+///
+/// See `convex_decomposition`.
+pub fn collision_shapes_from_polygon(
+    polygon: Polygon<Point>,
+    options: &FillOptions,
+) -> Result<Vec<ConvexPolygon>, TessellationError> {
+    convex_decomposition(polygon.path_events(), options)
+}
+
+#[cfg(test)]
+fn check_convex(shapes: &[ConvexPolygon]) {
+    for shape in shapes {
+        let n = shape.points.len();
+        assert!(n >= 3);
+        for i in 0..n {
+            let a = shape.points[i];
+            let b = shape.points[(i + 1) % n];
+            let c = shape.points[(i + 2) % n];
+            assert!((b - a).cross(c - b) > 0.0);
+        }
+        assert!(shape.area > 0.0);
+    }
+}
+
+#[test]
+fn l_shape_decomposition() {
+    let points = [
+        point(0.0, 0.0),
+        point(2.0, 0.0),
+        point(2.0, 1.0),
+        point(1.0, 1.0),
+        point(1.0, 2.0),
+        point(0.0, 2.0),
+    ];
+    let shapes = collision_shapes_from_polygon(
+        Polygon {
+            points: &points,
+            closed: true,
+        },
+        &FillOptions::default(),
+    )
+    .unwrap();
+
+    check_convex(&shapes);
+    // The tessellation has 4 triangles, the optimal decomposition has 2 parts.
+    assert!(shapes.len() <= 3);
+    let area: f64 = shapes.iter().map(|shape| shape.area).sum();
+    assert!((area - 3.0).abs() < 1e-12);
+
+    // The centroids of the parts average to the centroid of the shape.
+    let mut centroid = point(0.0, 0.0);
+    for shape in &shapes {
+        centroid += shape.centroid.to_vector() * (shape.area / area);
+        for p in shape.world_points() {
+            assert!(points.contains(&p));
+        }
+    }
+    assert!((centroid - point(5.0 / 6.0, 5.0 / 6.0)).length() < 1e-12);
+}
+
+#[test]
+fn decomposition_with_holes() {
+    use crate::path::{Path, Winding};
+
+    let mut builder = Path::builder();
+    builder.add_circle(point(0.0, 0.0), 10.0, Winding::Positive);
+    builder.add_circle(point(0.0, 0.0), 5.0, Winding::Negative);
+    let path = builder.build();
+
+    let buffers = crate::test_utils::fill(&path, &FillOptions::tolerance(0.01));
+    let num_triangles = buffers.indices.len() / 3;
+
+    let shapes = convex_decomposition(&path, &FillOptions::tolerance(0.01)).unwrap();
+    check_convex(&shapes);
+    assert!(shapes.len() < num_triangles / 2);
+
+    let area: f64 = shapes.iter().map(|shape| shape.area).sum();
+    assert!((area - crate::test_utils::area(&buffers, |p| *p)).abs() < 1e-6);
+
+    // Nothing covers the hole.
+    for shape in &shapes {
+        assert!(shape.centroid.to_vector().length() > 5.0);
+    }
+}
+
+#[test]
+fn overlapping_triangles() {
+    use crate::path::Path;
+
+    // Self-intersecting sub-paths with repeated points. With the non-zero fill rule
+    // the tessellation has overlapping triangles that share directed edges.
+    let sub_paths: [&[(f64, f64)]; 2] = [
+        &[
+            (2.0, 3.0),
+            (5.0, 1.0),
+            (3.0, 0.0),
+            (5.0, 2.0),
+            (1.0, 3.0),
+            (5.0, 2.0),
+            (5.0, 2.0),
+            (0.0, 1.0),
+            (0.0, 3.0),
+            (2.0, 4.0),
+            (3.0, 2.0),
+        ],
+        &[
+            (5.0, 1.0),
+            (3.0, 2.0),
+            (3.0, 3.0),
+            (4.0, 1.0),
+            (4.0, 5.0),
+            (5.0, 0.0),
+            (0.0, 1.0),
+            (0.0, 4.0),
+            (3.0, 3.0),
+            (5.0, 3.0),
+            (5.0, 3.0),
+        ],
+    ];
+    let mut builder = Path::builder();
+    for sub_path in &sub_paths {
+        builder.begin(point(sub_path[0].0, sub_path[0].1));
+        for &(x, y) in &sub_path[1..] {
+            builder.line_to(point(x, y));
+        }
+        builder.close();
+    }
+    let path = builder.build();
+
+    for options in &[FillOptions::even_odd(), FillOptions::non_zero()] {
+        let shapes = convex_decomposition(&path, options).unwrap();
+        check_convex(&shapes);
+        assert!(!shapes.is_empty());
+    }
+}
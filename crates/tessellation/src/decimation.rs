@@ -0,0 +1,338 @@
+//! Reduce the number of triangles of a tessellation.
+
+use crate::geometry_builder::VertexBuffers;
+use crate::math::Point;
+use crate::triangle_adjacency::{edge_key, triangles};
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Reduces the number of triangles of a fill tessellation by collapsing edges.
+///
+/// Edges are collapsed from the shortest to the longest. Vertices inside of the
+/// shape can always be removed as long as it does not flip or degenerate the
+/// surrounding triangles, which doesn't change the area covered by the geometry.
+/// Vertices on the boundary of the shape (including holes) are only removed if
+/// the boundary moves by less than `tolerance`. A tolerance of zero only removes
+/// the boundary vertices that are between two aligned boundary edges.
+///
+/// The per-vertex attributes of the removed vertices are lost, so this is mostly
+/// useful for shapes that are rendered with a uniform color, for example when they
+/// are zoomed out.
+///
+/// `position` provides the position of a vertex. Only `buffers.indices` is modified:
+/// removed vertices remain in `buffers.vertices` but are no longer referenced.
+///
+/// Returns the number of triangles that were removed.
+///
+/// The triangles are expected to [share their vertices](index.html#shared-vertices).
+pub fn decimate<OutputVertex, OutputIndex>(
+    buffers: &mut VertexBuffers<OutputVertex, OutputIndex>,
+    tolerance: f64,
+    position: impl Fn(&OutputVertex) -> Point,
+) -> usize
+where
+    OutputIndex: Copy + Into<u32>,
+{
+    let num_triangles = buffers.indices.len() / 3;
+    if num_triangles == 0 {
+        return 0;
+    }
+
+    let mut mesh = Mesh {
+        positions: buffers.vertices.iter().map(&position).collect(),
+        triangles: triangles(&buffers.indices),
+        removed: vec![false; num_triangles],
+        vertex_triangles: vec![Vec::new(); buffers.vertices.len()],
+        removed_boundary_points: HashMap::new(),
+    };
+
+    for (idx, tri) in mesh.triangles.iter().enumerate() {
+        for &v in tri {
+            mesh.vertex_triangles[v as usize].push(idx);
+        }
+    }
+
+    // The original index values, so that the output doesn't need to convert
+    // vertex ids back into indices.
+    let mut index_values = vec![None; buffers.vertices.len()];
+    for &idx in &buffers.indices {
+        index_values[idx.into() as usize] = Some(idx);
+    }
+
+    let mut edges = Vec::new();
+    loop {
+        edges.clear();
+        for (idx, tri) in mesh.triangles.iter().enumerate() {
+            if mesh.removed[idx] {
+                continue;
+            }
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                let length =
+                    (mesh.positions[a as usize] - mesh.positions[b as usize]).square_length();
+                edges.push((length, a, b));
+            }
+        }
+        edges.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        let mut collapsed_any = false;
+        for &(_, a, b) in &edges {
+            // Try to collapse in both directions.
+            if mesh.try_collapse(a, b, tolerance) || mesh.try_collapse(b, a, tolerance) {
+                collapsed_any = true;
+            }
+        }
+
+        if !collapsed_any {
+            break;
+        }
+    }
+
+    let mut indices = Vec::with_capacity(buffers.indices.len());
+    let mut removed = 0;
+    for (idx, tri) in mesh.triangles.iter().enumerate() {
+        if mesh.removed[idx] {
+            removed += 1;
+            continue;
+        }
+        for &v in tri {
+            indices.push(index_values[v as usize].unwrap());
+        }
+    }
+
+    buffers.indices = indices;
+
+    removed
+}
+
+struct Mesh {
+    positions: Vec<Point>,
+    triangles: Vec<[u32; 3]>,
+    removed: Vec<bool>,
+    vertex_triangles: Vec<Vec<usize>>,
+    // The original boundary points that were removed between the two
+    // endpoints of a boundary edge.
+    removed_boundary_points: HashMap<(u32, u32), Vec<Point>>,
+}
+
+impl Mesh {
+    // Collapses vertex `from` into vertex `to` if it preserves the shape.
+    fn try_collapse(&mut self, from: u32, to: u32, tolerance: f64) -> bool {
+        if from == to {
+            return false;
+        }
+
+        let fan = &self.vertex_triangles[from as usize];
+        if !fan.iter().any(|&t| self.triangles[t].contains(&to)) {
+            // The edge was removed by a previous collapse.
+            return false;
+        }
+
+        // Count the triangles using each edge around `from` to find the boundary edges.
+        let mut neighbors: Vec<(u32, u32)> = Vec::new();
+        for &t in fan {
+            for &v in &self.triangles[t] {
+                if v == from {
+                    continue;
+                }
+                match neighbors.iter_mut().find(|(n, _)| *n == v) {
+                    Some((_, count)) => *count += 1,
+                    None => neighbors.push((v, 1)),
+                }
+            }
+        }
+
+        let boundary: Vec<u32> = neighbors
+            .iter()
+            .filter(|(_, count)| *count == 1)
+            .map(|(v, _)| *v)
+            .collect();
+
+        let other = match boundary.len() {
+            0 => None,
+            2 if boundary.contains(&to) => {
+                // Collapsing along the boundary replaces the two boundary edges
+                // around `from` with a single one.
+                let other = if boundary[0] == to {
+                    boundary[1]
+                } else {
+                    boundary[0]
+                };
+                let segment = crate::geom::LineSegment {
+                    from: self.positions[other as usize],
+                    to: self.positions[to as usize],
+                };
+                let too_far = |p: &Point| segment.distance_to_point(*p) > tolerance;
+                if too_far(&self.positions[from as usize]) {
+                    return false;
+                }
+                for key in &[edge_key(other, from), edge_key(from, to)] {
+                    if let Some(points) = self.removed_boundary_points.get(key) {
+                        if points.iter().any(too_far) {
+                            return false;
+                        }
+                    }
+                }
+
+                Some(other)
+            }
+            _ => {
+                return false;
+            }
+        };
+
+        // The vertices shared by the neighborhoods of both vertices must be exactly the
+        // ones opposite of the collapsed edge, otherwise the mesh would fold onto itself.
+        let shared_edge_triangles = fan
+            .iter()
+            .filter(|&&t| self.triangles[t].contains(&to))
+            .count();
+        let to_neighbors_count = neighbors
+            .iter()
+            .filter(|(v, _)| {
+                *v != to
+                    && self.vertex_triangles[to as usize]
+                        .iter()
+                        .any(|&t| self.triangles[t].contains(v))
+            })
+            .count();
+        if to_neighbors_count != shared_edge_triangles {
+            return false;
+        }
+
+        // The remaining triangles must not flip or degenerate.
+        let to_position = self.positions[to as usize];
+        for &t in fan {
+            let tri = self.triangles[t];
+            if tri.contains(&to) {
+                continue;
+            }
+            let [a, b, c] = tri.map(|v| self.positions[v as usize]);
+            let [na, nb, nc] = tri.map(|v| {
+                if v == from {
+                    to_position
+                } else {
+                    self.positions[v as usize]
+                }
+            });
+            let before = (b - a).cross(c - a);
+            let after = (nb - na).cross(nc - na);
+            if before * after <= 0.0 {
+                return false;
+            }
+        }
+
+        if let Some(other) = other {
+            let mut points = vec![self.positions[from as usize]];
+            for key in &[edge_key(other, from), edge_key(from, to)] {
+                if let Some(removed) = self.removed_boundary_points.remove(key) {
+                    points.extend(removed);
+                }
+            }
+            self.removed_boundary_points
+                .insert(edge_key(other, to), points);
+        }
+
+        let fan = std::mem::take(&mut self.vertex_triangles[from as usize]);
+        for t in fan {
+            if self.triangles[t].contains(&to) {
+                self.removed[t] = true;
+                for &v in &self.triangles[t] {
+                    if v != from {
+                        self.vertex_triangles[v as usize].retain(|&other| other != t);
+                    }
+                }
+            } else {
+                for v in &mut self.triangles[t] {
+                    if *v == from {
+                        *v = to;
+                    }
+                }
+                self.vertex_triangles[to as usize].push(t);
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+use crate::math::point;
+#[cfg(test)]
+use crate::path::Path;
+#[cfg(test)]
+use crate::test_utils::{area, fill, fill_with_steiner_points};
+#[cfg(test)]
+use crate::FillOptions;
+#[cfg(test)]
+use std::f64::consts::PI;
+
+#[test]
+fn decimate_interior_vertices() {
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(5.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.close();
+    let path = builder.build();
+
+    let mut steiner_points = Vec::new();
+    for i in 1..10 {
+        for j in 1..10 {
+            steiner_points.push(point(i as f64, j as f64 + 0.1 * i as f64 / 10.0));
+        }
+    }
+
+    let mut buffers = fill_with_steiner_points(&path, &steiner_points, &FillOptions::default());
+    let num_triangles = buffers.indices.len() / 3;
+
+    // All interior vertices and the aligned vertex on the boundary are removed.
+    let removed = decimate(&mut buffers, 0.0, |p| *p);
+    assert_eq!(removed, num_triangles - 2);
+    assert!((area(&buffers, |p| *p) - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn decimate_boundary() {
+    use crate::path::Winding;
+
+    let mut builder = Path::builder();
+    builder.add_circle(point(0.0, 0.0), 100.0, Winding::Positive);
+    builder.add_circle(point(0.0, 0.0), 50.0, Winding::Negative);
+    let path = builder.build();
+
+    let mut buffers = fill(&path, &FillOptions::tolerance(0.01));
+    let num_triangles = buffers.indices.len() / 3;
+    let initial_area = area(&buffers, |p| *p);
+
+    let mut copy = buffers.clone();
+    assert_eq!(decimate(&mut copy, 0.0, |p| *p), 0);
+
+    let removed = decimate(&mut buffers, 1.0, |p| *p);
+    assert!(removed > num_triangles / 2);
+    // The boundary moved by less than the tolerance.
+    assert!((area(&buffers, |p| *p) - initial_area).abs() < 2.0 * PI * 150.0);
+    let mut sides = HashMap::new();
+    for tri in buffers.indices.chunks(3) {
+        for k in 0..3 {
+            let (a, b) = (tri[k] as u32, tri[(k + 1) % 3] as u32);
+            *sides.entry(edge_key(a, b)).or_insert(0) += 1;
+        }
+    }
+    for (&(a, b), &count) in &sides {
+        if count > 1 {
+            continue;
+        }
+        let edge = crate::geom::LineSegment {
+            from: buffers.vertices[a as usize],
+            to: buffers.vertices[b as usize],
+        };
+        // Boundary edges connect vertices of the same circle.
+        let radius = edge.from.to_vector().length();
+        assert!((radius - edge.to.to_vector().length()).abs() < 0.1);
+        assert!(radius - edge.sample(0.5).to_vector().length() < 1.0 + 0.1);
+    }
+}
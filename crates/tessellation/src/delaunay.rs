@@ -0,0 +1,232 @@
+//! Improve the quality of the triangles of a tessellation.
+
+use crate::geom::predicates::{incircle, orient2d};
+use crate::geometry_builder::VertexBuffers;
+use crate::math::Point;
+use crate::triangle_adjacency::{edge_key, TriangleAdjacency};
+
+use std::collections::HashMap;
+
+/// Flips the edges of a fill tessellation until the triangulation satisfies the
+/// Delaunay criterion: no vertex is inside of the circumcircle of a triangle that
+/// shares an edge with it.
+///
+/// The monotone decomposition of the fill tessellator tends to produce long and thin
+/// triangles, which look bad when per-vertex attributes like gradients or lighting
+/// are interpolated over them. Flipping maximizes the minimum angle of the triangles
+/// without changing the vertices or the area covered by the geometry.
+///
+/// Only the edges shared by exactly two triangles are flipped, so the boundary of the
+/// shape is preserved. Constraint segments (see
+/// `FillTessellator::tessellate_with_constraints`) and anti-aliasing fringes are not
+/// distinguished from other interior edges and can be flipped.
+///
+/// `position` provides the position of a vertex. Only `buffers.indices` is modified.
+/// The triangles that are flipped are in counter-clockwise order in a y-up coordinate
+/// system.
+///
+/// Returns the number of edges that were flipped.
+///
+/// The triangles are expected to [share their vertices](index.html#shared-vertices).
+pub fn flip_to_delaunay<OutputVertex, OutputIndex>(
+    buffers: &mut VertexBuffers<OutputVertex, OutputIndex>,
+    position: impl Fn(&OutputVertex) -> Point,
+) -> usize
+where
+    OutputIndex: Copy + Into<u32>,
+{
+    let positions: Vec<Point> = buffers.vertices.iter().map(&position).collect();
+    let pos = |v: u32| positions[v as usize];
+
+    let adjacency = TriangleAdjacency::new(&buffers.indices);
+
+    // The triangles in counter-clockwise order, degenerate ones are never flipped.
+    let mut triangles: Vec<Option<[u32; 3]>> = adjacency
+        .triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            let orientation = orient2d(pos(a), pos(b), pos(c));
+            if orientation > 0.0 {
+                Some([a, b, c])
+            } else if orientation < 0.0 {
+                Some([a, c, b])
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // The edges shared by exactly two non-degenerate triangles. They are visited in
+    // the order of the triangles rather than the order of the hash map so that the
+    // output doesn't depend on the latter.
+    let mut interior_edges: HashMap<(u32, u32), [usize; 2]> = HashMap::new();
+    let mut stack = Vec::new();
+    for tri in triangles.iter().flatten() {
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            if let &[t1, t2] = adjacency.edge_triangles(a, b) {
+                if triangles[t1].is_some() && triangles[t2].is_some() {
+                    interior_edges.insert(edge_key(a, b), [t1, t2]);
+                    stack.push(edge_key(a, b));
+                }
+            }
+        }
+    }
+
+    let mut flipped = vec![false; triangles.len()];
+    let mut num_flips = 0;
+    while let Some(edge) = stack.pop() {
+        let [t1, t2] = match interior_edges.get(&edge) {
+            Some(tris) => *tris,
+            None => continue,
+        };
+
+        // Name the vertices so that t1 is (a, b, c) and t2 is (b, a, d).
+        let tri1 = triangles[t1].unwrap();
+        let k = (0..3)
+            .find(|k| edge_key(tri1[*k], tri1[(*k + 1) % 3]) == edge)
+            .unwrap();
+        let (a, b, c) = (tri1[k], tri1[(k + 1) % 3], tri1[(k + 2) % 3]);
+        let d = *triangles[t2]
+            .unwrap()
+            .iter()
+            .find(|v| **v != a && **v != b)
+            .unwrap();
+
+        if incircle(pos(a), pos(b), pos(c), pos(d)) <= 0.0 {
+            continue;
+        }
+
+        // The new triangles must not be degenerate or flipped, which happens if the
+        // quadrilateral is not convex.
+        if orient2d(pos(a), pos(d), pos(c)) <= 0.0 || orient2d(pos(d), pos(b), pos(c)) <= 0.0 {
+            continue;
+        }
+
+        triangles[t1] = Some([a, d, c]);
+        triangles[t2] = Some([d, b, c]);
+        flipped[t1] = true;
+        flipped[t2] = true;
+        num_flips += 1;
+
+        interior_edges.remove(&edge);
+        interior_edges.insert(edge_key(c, d), [t1, t2]);
+        // The edge (a, d) moved from t2 to t1 and (b, c) from t1 to t2.
+        for &(edge, from, to) in &[(edge_key(a, d), t2, t1), (edge_key(b, c), t1, t2)] {
+            if let Some(tris) = interior_edges.get_mut(&edge) {
+                for t in tris.iter_mut() {
+                    if *t == from {
+                        *t = to;
+                    }
+                }
+            }
+        }
+
+        for &(u, v) in &[(a, d), (d, b), (b, c), (c, a)] {
+            if interior_edges.contains_key(&edge_key(u, v)) {
+                stack.push(edge_key(u, v));
+            }
+        }
+    }
+
+    if num_flips == 0 {
+        return 0;
+    }
+
+    // The original index values, so that the output doesn't need to convert
+    // vertex ids back into indices.
+    let mut index_values = vec![None; buffers.vertices.len()];
+    for &idx in &buffers.indices {
+        index_values[idx.into() as usize] = Some(idx);
+    }
+
+    for (idx, tri) in triangles.iter().enumerate() {
+        if !flipped[idx] {
+            continue;
+        }
+        for (k, &v) in tri.unwrap().iter().enumerate() {
+            buffers.indices[idx * 3 + k] = index_values[v as usize].unwrap();
+        }
+    }
+
+    num_flips
+}
+
+#[cfg(test)]
+use crate::math::point;
+#[cfg(test)]
+use crate::path::Path;
+#[cfg(test)]
+use crate::test_utils::{area, fill_with_steiner_points};
+#[cfg(test)]
+use crate::FillOptions;
+
+#[test]
+fn delaunay_flips() {
+    use crate::path::Winding;
+
+    let mut builder = Path::builder();
+    builder.add_ellipse(
+        point(0.0, 0.0),
+        crate::math::vector(100.0, 20.0),
+        crate::math::Angle::radians(0.3),
+        Winding::Positive,
+    );
+    let path = builder.build();
+
+    let mut steiner_points = Vec::new();
+    for i in -4..5 {
+        steiner_points.push(point(i as f64 * 15.0, i as f64 * 4.0));
+    }
+
+    let mut buffers =
+        fill_with_steiner_points(&path, &steiner_points, &FillOptions::tolerance(0.05));
+
+    let triangles = |buffers: &VertexBuffers<Point, u16>| -> Vec<[Point; 3]> {
+        buffers
+            .indices
+            .chunks(3)
+            .map(|tri| [tri[0], tri[1], tri[2]].map(|v| buffers.vertices[v as usize]))
+            .collect()
+    };
+    let min_angle = |tris: &[[Point; 3]]| -> f64 {
+        let mut min = f64::MAX;
+        for tri in tris {
+            for k in 0..3 {
+                let (a, b, c) = (tri[k], tri[(k + 1) % 3], tri[(k + 2) % 3]);
+                min = min.min((b - a).angle_to(c - a).radians.abs());
+            }
+        }
+        min
+    };
+
+    let before = triangles(&buffers);
+    let area_before = area(&buffers, |p| *p);
+    let num_flips = flip_to_delaunay(&mut buffers, |p| *p);
+    let after = triangles(&buffers);
+
+    assert!(num_flips > 0);
+    assert_eq!(before.len(), after.len());
+    assert!((area(&buffers, |p| *p) - area_before).abs() < 1e-6);
+    assert!(min_angle(&after) > min_angle(&before));
+
+    // No vertex is inside of the circumcircle of a neighbor triangle.
+    for tri in &after {
+        let [a, b, c] = if orient2d(tri[0], tri[1], tri[2]) > 0.0 {
+            *tri
+        } else {
+            [tri[0], tri[2], tri[1]]
+        };
+        for other in &after {
+            let shared = other.iter().filter(|p| tri.contains(p)).count();
+            if shared != 2 {
+                continue;
+            }
+            let d = other.iter().find(|p| !tri.contains(p)).unwrap();
+            assert!(incircle(a, b, c, *d) <= 0.0);
+        }
+    }
+
+    // Flipping again doesn't do anything.
+    assert_eq!(flip_to_delaunay(&mut buffers, |p| *p), 0);
+}
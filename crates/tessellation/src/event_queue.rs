@@ -651,6 +651,59 @@ impl EventQueueBuilder {
         });
     }
 
+    /// Adds a vertex that is not connected to any edge.
+    ///
+    /// If it is inside of the shape, the fill tessellator will use the vertex
+    /// in its triangulation.
+    pub fn steiner_point(&mut self, sweep_orientation: Orientation, at: Point) {
+        let at = match sweep_orientation {
+            Orientation::Vertical => at,
+            Orientation::Horizontal => reorient(at),
+        };
+        self.vertex_event(at, EndpointId::INVALID);
+    }
+
+    /// Adds an edge that does not affect the winding number.
+    ///
+    /// Parts of the edge that are inside of the shape are edges of the triangles
+    /// produced by the fill tessellator.
+    pub fn constraint_segment(
+        &mut self,
+        sweep_orientation: Orientation,
+        segment: &LineSegment<f64>,
+    ) {
+        let segment = match sweep_orientation {
+            Orientation::Vertical => *segment,
+            Orientation::Horizontal => LineSegment {
+                from: reorient(segment.from),
+                to: reorient(segment.to),
+            },
+        };
+
+        if segment.from == segment.to {
+            return self.steiner_point(Orientation::Vertical, segment.from);
+        }
+
+        let (upper, lower, range) = if is_after(segment.from, segment.to) {
+            (segment.to, segment.from, 1.0..0.0)
+        } else {
+            (segment.from, segment.to, 0.0..1.0)
+        };
+
+        self.queue.push_unsorted(upper);
+        self.queue.edge_data.push(EdgeData {
+            to: lower,
+            range,
+            winding: 0,
+            is_edge: true,
+            from_id: EndpointId::INVALID,
+            to_id: EndpointId::INVALID,
+        });
+
+        // The lower endpoint doesn't have an edge below it.
+        self.vertex_event(lower, EndpointId::INVALID);
+    }
+
     fn vertex_event_on_curve(&mut self, at: Point, t: f64, from_id: EndpointId, to_id: EndpointId) {
         self.queue.push_unsorted(at);
         self.queue.edge_data.push(EdgeData {
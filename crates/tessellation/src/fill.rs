@@ -1,5 +1,8 @@
 use crate::event_queue::*;
+use crate::fringe::FringeBuilder;
+use crate::geom::predicates::orient2d;
 use crate::geom::LineSegment;
+use crate::geometry_builder::NoOutput;
 use crate::math::*;
 use crate::monotone::*;
 use crate::path::polygon::Polygon;
@@ -63,7 +66,7 @@ fn slope(v: Vector) -> f64 {
     v.x / (v.y.max(f64::MIN))
 }
 
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, not(feature = "log")))]
 macro_rules! tess_log {
     ($obj:ident, $fmt:expr) => (
         if $obj.log {
@@ -77,6 +80,22 @@ macro_rules! tess_log {
     );
 }
 
+// With the log feature the output goes through the `log` facade so that
+// applications can filter and redirect it.
+#[cfg(all(debug_assertions, feature = "log"))]
+macro_rules! tess_log {
+    ($obj:ident, $fmt:expr) => (
+        if $obj.log {
+            log::debug!(target: "lyon_tessellation::fill", $fmt);
+        }
+    );
+    ($obj:ident, $fmt:expr, $($arg:tt)*) => (
+        if $obj.log {
+            log::debug!(target: "lyon_tessellation::fill", $fmt, $($arg)*);
+        }
+    );
+}
+
 #[cfg(not(debug_assertions))]
 macro_rules! tess_log {
     ($obj:ident, $fmt:expr) => {};
@@ -117,6 +136,7 @@ struct ActiveEdgeScan {
     merge_event: bool,
     split_event: bool,
     merge_split_event: bool,
+    isolated_event: bool,
     above: Range<ActiveEdgeIdx>,
     winding_before_point: WindingState,
 }
@@ -130,6 +150,7 @@ impl ActiveEdgeScan {
             merge_event: false,
             split_event: false,
             merge_split_event: false,
+            isolated_event: false,
             above: 0..0,
             winding_before_point: WindingState::new(),
         }
@@ -142,6 +163,7 @@ impl ActiveEdgeScan {
         self.merge_event = false;
         self.split_event = false;
         self.merge_split_event = false;
+        self.isolated_event = false;
         self.above = 0..0;
         self.winding_before_point = WindingState::new();
     }
@@ -203,6 +225,15 @@ struct Span {
     /// We store `MonotoneTesselator` behind a `Box` for performance purposes.
     /// For more info, see [Issue #621](https://github.com/nical/lyon/pull/621).
     tess: Option<Box<MonotoneTessellator>>,
+    /// Only recorded by `FillTessellator::decompose_into_monotone`.
+    outline: Option<Box<SpanOutline>>,
+}
+
+/// The vertices of the monotone polygon of a span.
+struct SpanOutline {
+    first: Point,
+    left: Vec<Point>,
+    right: Vec<Point>,
 }
 
 impl Span {
@@ -225,6 +256,11 @@ struct Spans {
     /// For more info, see [Issue #621](https://github.com/nical/lyon/pull/621).
     #[allow(clippy::vec_box)]
     pool: Vec<Box<MonotoneTessellator>>,
+
+    /// If true, the outlines of the spans are recorded into `monotone_polygons`
+    /// when they end.
+    record_outlines: bool,
+    monotone_polygons: Vec<MonotonePolygon>,
 }
 
 impl Spans {
@@ -235,8 +271,35 @@ impl Spans {
             .unwrap_or_else(|| Box::new(MonotoneTessellator::new()));
         tess.begin(*position, vertex);
 
-        self.spans
-            .insert(span_idx as usize, Span { tess: Some(tess) });
+        let outline = if self.record_outlines {
+            Some(Box::new(SpanOutline {
+                first: *position,
+                left: Vec::new(),
+                right: Vec::new(),
+            }))
+        } else {
+            None
+        };
+
+        self.spans.insert(
+            span_idx as usize,
+            Span {
+                tess: Some(tess),
+                outline,
+            },
+        );
+    }
+
+    fn vertex(&mut self, span_idx: SpanIdx, position: &Point, vertex: VertexId, side: Side) {
+        let span = &mut self.spans[span_idx as usize];
+        if let Some(outline) = span.outline.as_mut() {
+            match side {
+                Side::Left => outline.left.push(*position),
+                Side::Right => outline.right.push(*position),
+            }
+        }
+
+        span.tess().vertex(*position, vertex, side);
     }
 
     fn end_span(
@@ -258,6 +321,19 @@ impl Spans {
             debug_assert!(false);
             unreachable!();
         }
+
+        if let Some(outline) = span.outline.take() {
+            // Go down the right side and back up the left side, which is
+            // counter-clockwise in a y-up coordinate system.
+            let mut points = Vec::with_capacity(outline.left.len() + outline.right.len() + 2);
+            points.push(outline.first);
+            points.extend_from_slice(&outline.right);
+            points.push(*position);
+            points.extend(outline.left.iter().rev());
+            if points.len() >= 3 {
+                self.monotone_polygons.push(MonotonePolygon { points });
+            }
+        }
     }
 
     fn merge_spans(
@@ -276,17 +352,8 @@ impl Spans {
 
         let right_span_idx = left_span_idx + 1;
 
-        self.spans[left_span_idx as usize].tess().vertex(
-            *merge_position,
-            merge_vertex,
-            Side::Right,
-        );
-
-        self.spans[right_span_idx as usize].tess().vertex(
-            *merge_position,
-            merge_vertex,
-            Side::Left,
-        );
+        self.vertex(left_span_idx, merge_position, merge_vertex, Side::Right);
+        self.vertex(right_span_idx, merge_position, merge_vertex, Side::Left);
 
         self.end_span(left_span_idx, current_position, current_vertex, output);
     }
@@ -347,6 +414,9 @@ struct PendingEdge {
 ///
 /// The tessellator does not handle `NaN` values in any of its inputs.
 ///
+/// Degenerate geometry such as sub-paths with collinear or coincident points has no
+/// area: it is tessellated successfully and doesn't produce any triangle.
+///
 /// ## Associating custom attributes with vertices.
 ///
 /// It is sometimes useful to be able to link vertices generated by the tessellator back
@@ -562,6 +632,8 @@ impl FillTessellator {
             fill: Spans {
                 spans: Vec::new(),
                 pool: Vec::new(),
+                record_outlines: false,
+                monotone_polygons: Vec::new(),
             },
             log,
             assume_no_intersection: false,
@@ -593,6 +665,70 @@ impl FillTessellator {
         self.tessellate_impl(options, None, output)
     }
 
+    /// Compute the tessellation from a path iterator and a set of interior points
+    /// that must be vertices of the resulting triangulation.
+    ///
+    /// Additional interior vertices (often called steiner points) are typically
+    /// useful to avoid long and thin triangles or to get a denser mesh for
+    /// effects that need vertices inside of the shape.
+    ///
+    /// Points that are outside of the shape are added to the output geometry but
+    /// are not part of any triangle. Points that are on an edge (within the tolerance
+    /// threshold) split it and points that are at the position of an endpoint are
+    /// merged with it.
+    ///
+    /// The source of a vertex created for a steiner point is
+    /// `VertexSource::Endpoint { id: EndpointId::INVALID }`.
+    pub fn tessellate_with_steiner_points(
+        &mut self,
+        path: impl IntoIterator<Item = PathEvent>,
+        steiner_points: &[Point],
+        options: &FillOptions,
+        output: &mut dyn FillGeometryBuilder,
+    ) -> TessellationResult {
+        self.tessellate_with_constraints(path, steiner_points, &[], options, output)
+    }
+
+    /// Compute the tessellation from a path iterator, a set of interior points and
+    /// a set of constraint segments that must be edges of the resulting triangulation.
+    ///
+    /// Constraint segments (for example crease lines) don't affect the winding number,
+    /// they only split the triangles they go through. Parts of the segments that
+    /// are outside of the shape are ignored. Constraint segments that intersect the
+    /// path or each-other are only properly handled if `handle_intersections` is
+    /// enabled in the fill options.
+    ///
+    /// See `tessellate_with_steiner_points` about steiner points. Vertices created at
+    /// the endpoints of constraint segments have the same source as steiner points.
+    /// Vertices created along constraint segments have a
+    /// `VertexSource::Edge { from: EndpointId::INVALID, to: EndpointId::INVALID, .. }`
+    /// source.
+    pub fn tessellate_with_constraints(
+        &mut self,
+        path: impl IntoIterator<Item = PathEvent>,
+        steiner_points: &[Point],
+        constraint_segments: &[LineSegment<f64>],
+        options: &FillOptions,
+        output: &mut dyn FillGeometryBuilder,
+    ) -> TessellationResult {
+        let event_queue = std::mem::take(&mut self.events);
+        let mut queue_builder = event_queue.into_builder(options.tolerance);
+
+        queue_builder.set_path(options.tolerance, options.sweep_orientation, path);
+
+        for &p in steiner_points {
+            queue_builder.steiner_point(options.sweep_orientation, p);
+        }
+
+        for segment in constraint_segments {
+            queue_builder.constraint_segment(options.sweep_orientation, segment);
+        }
+
+        self.events = queue_builder.build();
+
+        self.tessellate_impl(options, None, output)
+    }
+
     /// Compute the tessellation using an iterator over endpoint and control
     /// point ids, storage for the positions and, optionally, storage for
     /// custom endpoint attributes.
@@ -652,9 +788,16 @@ impl FillTessellator {
     pub fn tessellate_rectangle(
         &mut self,
         rect: &Box2D,
-        _options: &FillOptions,
+        options: &FillOptions,
         output: &mut dyn FillGeometryBuilder,
     ) -> TessellationResult {
+        if options.antialiasing_fringe.is_some() {
+            let mut builder = self.builder(options, output);
+            builder.add_rectangle(rect, Winding::Positive);
+
+            return builder.build();
+        }
+
         crate::basic_shapes::fill_rectangle(rect, output)
     }
 
@@ -666,6 +809,13 @@ impl FillTessellator {
         options: &FillOptions,
         output: &mut dyn FillGeometryBuilder,
     ) -> TessellationResult {
+        if options.antialiasing_fringe.is_some() {
+            let mut builder = self.builder(options, output);
+            builder.add_circle(center, radius, Winding::Positive);
+
+            return builder.build();
+        }
+
         crate::basic_shapes::fill_circle(center, radius, options, output)
     }
 
@@ -687,6 +837,45 @@ impl FillTessellator {
         builder.build()
     }
 
+    /// Decompose the fill of a path into monotone polygons, without triangulating them.
+    ///
+    /// The polygons are the ones that the tessellator triangulates internally. They are
+    /// monotone in the direction of the sweep (see `FillOptions::sweep_orientation`),
+    /// which is the y axis by default: any horizontal line intersects their interior at
+    /// most once. This is useful to triangulate the shape differently or to decompose
+    /// it into trapezoids.
+    ///
+    /// The vertices of the polygons are in counter-clockwise order in a y-up coordinate
+    /// system (positive signed area). Neighbor polygons share vertices at the same
+    /// positions. `FillOptions::antialiasing_fringe` is ignored.
+    pub fn decompose_into_monotone(
+        &mut self,
+        path: impl IntoIterator<Item = PathEvent>,
+        options: &FillOptions,
+    ) -> Result<Vec<MonotonePolygon>, TessellationError> {
+        let options = FillOptions {
+            antialiasing_fringe: None,
+            ..*options
+        };
+
+        self.fill.record_outlines = true;
+        self.fill.monotone_polygons.clear();
+        let result = self.tessellate(path, &options, &mut NoOutput::new());
+        self.fill.record_outlines = false;
+        let mut polygons = mem::take(&mut self.fill.monotone_polygons);
+        result?;
+
+        if self.orientation == Orientation::Horizontal {
+            for polygon in &mut polygons {
+                for p in &mut polygon.points {
+                    *p = reorient(*p);
+                }
+            }
+        }
+
+        Ok(polygons)
+    }
+
     /// Tessellate directly from a sequence of `PathBuilder` commands, without
     /// creating an intermediate path data structure.
     ///
@@ -771,11 +960,21 @@ impl FillTessellator {
 
         builder.begin_geometry();
 
-        let mut scan = mem::replace(&mut self.scan, ActiveEdgeScan::new());
-
-        let result = self.tessellator_loop(attrib_store, &mut scan, builder);
-
-        mem::swap(&mut self.scan, &mut scan);
+        let result = match options.antialiasing_fringe {
+            Some(width) => {
+                let mut fringe = FringeBuilder::new(builder);
+                self.tessellate_geometry(attrib_store, &mut fringe)
+                    .and_then(|()| {
+                        fringe.add_fringe(
+                            width,
+                            &self.events,
+                            attrib_store,
+                            &mut self.attrib_buffer,
+                        )
+                    })
+            }
+            None => self.tessellate_geometry(attrib_store, builder),
+        };
 
         if let Err(e) = result {
             tess_log!(self, "Tessellation failed with error: {}.", e);
@@ -784,6 +983,24 @@ impl FillTessellator {
             return Err(e);
         }
 
+        builder.end_geometry();
+
+        Ok(())
+    }
+
+    fn tessellate_geometry(
+        &mut self,
+        attrib_store: Option<&dyn AttributeStore>,
+        builder: &mut dyn FillGeometryBuilder,
+    ) -> TessellationResult {
+        let mut scan = mem::replace(&mut self.scan, ActiveEdgeScan::new());
+
+        let result = self.tessellator_loop(attrib_store, &mut scan, builder);
+
+        mem::swap(&mut self.scan, &mut scan);
+
+        result?;
+
         if !self.assume_no_intersection {
             debug_assert!(self.active.edges.is_empty());
             debug_assert!(self.fill.spans.is_empty());
@@ -800,13 +1017,15 @@ impl FillTessellator {
 
         self.fill.spans.clear();
 
-        builder.end_geometry();
-
         Ok(())
     }
 
     /// Enable/disable some verbose logging during the tessellation, for
     /// debugging purposes.
+    ///
+    /// The output is printed to stdout, or sent to the `log` facade at the debug
+    /// level with the `lyon_tessellation::fill` target if the `log` feature is
+    /// enabled. Logging is only available in debug builds.
     pub fn set_logging(&mut self, is_enabled: bool) {
         #[cfg(debug_assertions)]
         let forced = env::var("LYON_FORCE_LOGGING").is_ok();
@@ -883,6 +1102,7 @@ impl FillTessellator {
             current_event,
             attrib_store,
             attrib_buffer: &mut self.attrib_buffer,
+            alpha: 1.0,
         })?;
 
         let mut current_sibling = current_event;
@@ -1070,6 +1290,9 @@ impl FillTessellator {
                 } else {
                     let ex = active_edge.solve_x_for_y(self.current_position.y);
 
+                    // Points within the tolerance of the edge are on it. The tolerance
+                    // is much larger than the rounding errors, so unlike the sorting of
+                    // the edges below, this doesn't need an exact predicate.
                     if (ex - current_x).abs() <= self.tolerance {
                         connecting_edges = true;
                         false
@@ -1141,6 +1364,14 @@ impl FillTessellator {
         //  ../ \..
         scan.split_event = !connecting_edges && winding.is_in && !scan.merge_split_event;
 
+        // A vertex with no edge at all inside of the shape (a steiner point).
+        // It is handled as a split event immediately followed by a merge event.
+        //
+        //  .......
+        //  ...x...
+        //  ...:...
+        scan.isolated_event = !connecting_edges && winding.is_in && self.edges_below.is_empty();
+
         // Step 2 - Iterate over edges connecting with the current point.
 
         tess_log!(
@@ -1369,8 +1600,9 @@ impl FillTessellator {
                 side,
                 self.current_vertex
             );
-            self.fill.spans[span_index as usize].tess().vertex(
-                self.current_position,
+            self.fill.vertex(
+                span_index,
+                &self.current_position,
                 self.current_vertex,
                 side,
             );
@@ -1536,6 +1768,23 @@ impl FillTessellator {
                 range_end: edge.range_end,
             }),
         );
+
+        if scan.isolated_event {
+            // The vertex was added to the spans on each side of it, insert a
+            // merge edge to resolve them with the next vertex below.
+            self.active.edges.insert(
+                scan.above.start,
+                ActiveEdge {
+                    from,
+                    to: from,
+                    winding: 0,
+                    is_merge: true,
+                    from_id,
+                    src_edge: self.current_event_id,
+                    range_end: 0.0,
+                },
+            );
+        }
     }
 
     fn split_event(&mut self, left_enclosing_edge_idx: ActiveEdgeIdx, left_span_idx: SpanIdx) {
@@ -1573,13 +1822,15 @@ impl FillTessellator {
         self.fill
             .begin_span(new_span_idx, &upper_position, upper_id);
 
-        self.fill.spans[left_span_idx as usize].tess().vertex(
-            self.current_position,
+        self.fill.vertex(
+            left_span_idx,
+            &self.current_position,
             self.current_vertex,
             Side::Right,
         );
-        self.fill.spans[right_span_idx as usize].tess().vertex(
-            self.current_position,
+        self.fill.vertex(
+            right_span_idx,
+            &self.current_position,
             self.current_vertex,
             Side::Left,
         );
@@ -1984,8 +2235,22 @@ impl FillTessellator {
 
     #[cfg_attr(feature = "profiling", inline(never))]
     fn sort_edges_below(&mut self) {
-        self.edges_below
-            .sort_unstable_by(|a, b| a.sort_key.partial_cmp(&b.sort_key).unwrap());
+        let current = self.current_position;
+        // The edges below all point downwards (or horizontally), so the sign of the
+        // orientation of their endpoints around the current position gives the order
+        // of their slopes. Unlike the rounded slopes, this is exact.
+        // Only aligned edges going in opposite (horizontal) directions fall back to
+        // the sort key.
+        self.edges_below.sort_unstable_by(|a, b| {
+            let orientation = orient2d(current, a.to, b.to);
+            if orientation < 0.0 {
+                Ordering::Less
+            } else if orientation > 0.0 {
+                Ordering::Greater
+            } else {
+                a.sort_key.partial_cmp(&b.sort_key).unwrap()
+            }
+        });
     }
 
     #[cfg_attr(feature = "profiling", inline(never))]
@@ -2131,6 +2396,23 @@ fn reorient(p: Point) -> Point {
     point(p.y, -p.x)
 }
 
+/// A monotone polygon produced by `FillTessellator::decompose_into_monotone`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonotonePolygon {
+    /// The vertices of the polygon in counter-clockwise order in a y-up coordinate
+    /// system (positive signed area).
+    pub points: Vec<Point>,
+}
+
+impl MonotonePolygon {
+    pub fn as_polygon(&self) -> Polygon<'_, Point> {
+        Polygon {
+            points: &self.points,
+            closed: true,
+        }
+    }
+}
+
 /// Extra vertex information from the `FillTessellator`, accessible when building vertices.
 pub struct FillVertex<'l> {
     pub(crate) position: Point,
@@ -2138,6 +2420,7 @@ pub struct FillVertex<'l> {
     pub(crate) current_event: TessEventId,
     pub(crate) attrib_buffer: &'l mut [f64],
     pub(crate) attrib_store: Option<&'l dyn AttributeStore>,
+    pub(crate) alpha: f64,
 }
 
 impl<'l> FillVertex<'l> {
@@ -2145,6 +2428,14 @@ impl<'l> FillVertex<'l> {
         self.position
     }
 
+    /// The coverage of the shape at this vertex.
+    ///
+    /// This is zero for the outer vertices of the anti-aliasing fringe
+    /// (see `FillOptions::antialiasing_fringe`) and one for all other vertices.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
     /// Return an iterator over the sources of the vertex.
     pub fn sources(&self) -> VertexSourceIterator {
         VertexSourceIterator {
@@ -3002,3 +3293,86 @@ fn fill_builder_vertex_source() {
         }
     }
 }
+
+#[test]
+fn monotone_decomposition() {
+    use crate::path::Path;
+
+    // A U shape and a square hole in a rectangle.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(30.0, 0.0));
+    builder.line_to(point(30.0, 30.0));
+    builder.line_to(point(20.0, 30.0));
+    builder.line_to(point(20.0, 10.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(10.0, 30.0));
+    builder.line_to(point(0.0, 30.0));
+    builder.close();
+    builder.begin(point(40.0, 0.0));
+    builder.line_to(point(60.0, 0.0));
+    builder.line_to(point(60.0, 20.0));
+    builder.line_to(point(40.0, 20.0));
+    builder.close();
+    builder.begin(point(45.0, 5.0));
+    builder.line_to(point(55.0, 5.0));
+    builder.line_to(point(55.0, 15.0));
+    builder.line_to(point(45.0, 15.0));
+    builder.close();
+    let path = builder.build();
+
+    fn signed_area(points: &[Point]) -> f64 {
+        let mut area = 0.0;
+        for i in 0..points.len() {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            area += a.x * b.y - b.x * a.y;
+        }
+        area * 0.5
+    }
+
+    // The number of times the direction along an axis changes around the polygon.
+    fn direction_changes(points: &[Point], axis: impl Fn(Point) -> f64) -> usize {
+        let mut directions = Vec::new();
+        for i in 0..points.len() {
+            let d = axis(points[(i + 1) % points.len()]) - axis(points[i]);
+            if d != 0.0 {
+                directions.push(d > 0.0);
+            }
+        }
+        (0..directions.len())
+            .filter(|i| directions[*i] != directions[(i + 1) % directions.len()])
+            .count()
+    }
+
+    let mut tess = FillTessellator::new();
+    for orientation in [Orientation::Vertical, Orientation::Horizontal] {
+        let options = FillOptions::default().with_sweep_orientation(orientation);
+        let polygons = tess.decompose_into_monotone(path.iter(), &options).unwrap();
+        assert!(polygons.len() >= 4);
+
+        let mut total_area = 0.0;
+        for polygon in &polygons {
+            let area = signed_area(&polygon.points);
+            assert!(area > 0.0);
+            total_area += area;
+
+            let changes = match orientation {
+                Orientation::Vertical => direction_changes(&polygon.points, |p| p.y),
+                Orientation::Horizontal => direction_changes(&polygon.points, |p| p.x),
+            };
+            assert!(changes <= 2);
+            assert_eq!(polygon.as_polygon().points.len(), polygon.points.len());
+        }
+        assert!((total_area - (700.0 + 300.0)).abs() < 1e-9);
+    }
+
+    // The tessellator still produces triangles afterwards.
+    let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+    tess.tessellate_path(
+        &path,
+        &FillOptions::default(),
+        &mut simple_builder(&mut buffers),
+    )
+    .unwrap();
+    assert!(!buffers.indices.is_empty());
+}
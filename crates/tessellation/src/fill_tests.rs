@@ -279,7 +279,7 @@ fn test_degenerate_same_position() {
     path.line_to(point(0.0, 0.0));
     path.end(true);
 
-    test_path_with_rotations(path.build(), 0.001, None);
+    test_path_with_rotations(path.build(), 0.001, Some(0));
 }
 
 #[test]
@@ -706,7 +706,7 @@ fn test_colinear_1() {
 
     let path = builder.build();
 
-    test_path_with_rotations(path, 0.01, None);
+    test_path_with_rotations(path, 0.01, Some(0));
 }
 
 #[test]
@@ -719,7 +719,7 @@ fn test_colinear_2() {
 
     let path = builder.build();
 
-    test_path_with_rotations(path, 0.01, None);
+    test_path_with_rotations(path, 0.01, Some(0));
 }
 
 #[test]
@@ -735,7 +735,7 @@ fn test_colinear_3() {
 
     let path = builder.build();
 
-    test_path(path.as_slice());
+    test_path_and_count_triangles(path.as_slice(), 0);
 }
 
 #[test]
@@ -750,7 +750,7 @@ fn test_colinear_4() {
 
     let path = builder.build();
 
-    test_path(path.as_slice());
+    test_path_and_count_triangles(path.as_slice(), 0);
 }
 
 #[test]
@@ -2497,3 +2497,176 @@ fn test_triangle_winding() {
     )
     .unwrap();
 }
+
+#[test]
+fn test_steiner_points() {
+    use crate::geometry_builder::{simple_builder, VertexBuffers};
+    use crate::math::{point, Point};
+    use crate::Orientation;
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.close();
+    // A hole in the middle.
+    builder.begin(point(6.0, 4.0));
+    builder.line_to(point(6.0, 6.0));
+    builder.line_to(point(8.0, 6.0));
+    builder.line_to(point(8.0, 4.0));
+    builder.close();
+    let path = builder.build();
+
+    let interior = [
+        point(2.0, 2.0),
+        point(4.0, 2.0),
+        point(5.0, 5.0),
+        point(2.0, 8.0),
+        point(7.0, 8.0),
+        point(3.0, 8.5),
+    ];
+    let ignored = [point(7.0, 5.0), point(20.0, 5.0), point(-1.0, 1.0)];
+
+    let mut points = interior.to_vec();
+    points.extend_from_slice(&ignored);
+
+    for &orientation in &[Orientation::Vertical, Orientation::Horizontal] {
+        let options = FillOptions::tolerance(0.01).with_sweep_orientation(orientation);
+        let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+        FillTessellator::new()
+            .tessellate_with_steiner_points(
+                &path,
+                &points,
+                &options,
+                &mut simple_builder(&mut buffers),
+            )
+            .unwrap();
+
+        assert!((crate::test_utils::area(&buffers, |p| *p) - 96.0).abs() < 1e-9);
+
+        // Each point inside of the shape is used in the triangulation.
+        for p in &interior {
+            let idx = buffers.vertices.iter().position(|v| v == p).unwrap() as u16;
+            assert!(buffers.indices.contains(&idx));
+        }
+
+        // Points outside of the shape produce vertices that aren't used.
+        for p in &ignored {
+            if let Some(idx) = buffers.vertices.iter().position(|v| v == p) {
+                assert!(!buffers.indices.contains(&(idx as u16)));
+            }
+        }
+
+        // A polygon with a hole made of 8 vertices has 8 triangles, each
+        // additional interior point adds two triangles.
+        assert_eq!(buffers.indices.len() / 3, 8 + 2 * interior.len());
+    }
+}
+
+#[test]
+fn test_steiner_points_on_boundary() {
+    use crate::geometry_builder::{simple_builder, VertexBuffers};
+    use crate::math::{point, Point};
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.close();
+    let path = builder.build();
+
+    // On an edge, on an endpoint and directly below a merge vertex.
+    let points = [
+        point(0.0, 5.0),
+        point(10.0, 10.0),
+        point(5.0, 5.0),
+        point(5.0, 7.0),
+    ];
+
+    let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate_with_steiner_points(
+            &path,
+            &points,
+            &FillOptions::default(),
+            &mut simple_builder(&mut buffers),
+        )
+        .unwrap();
+
+    // The point on the endpoint is merged with it.
+    assert_eq!(buffers.vertices.len(), 7);
+    // 5 boundary vertices and 2 interior ones.
+    assert_eq!(buffers.indices.len() / 3, 5 + 2 * 2 - 2);
+}
+
+#[test]
+fn test_constraint_segments() {
+    use crate::geom::LineSegment;
+    use crate::geometry_builder::{simple_builder, VertexBuffers};
+    use crate::math::{point, Point};
+    use crate::Orientation;
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.close();
+    let path = builder.build();
+
+    let constraints = [
+        // Fully inside.
+        LineSegment {
+            from: point(2.0, 8.0),
+            to: point(8.0, 2.0),
+        },
+        // Crossing the boundary of the shape.
+        LineSegment {
+            from: point(-5.0, 1.0),
+            to: point(5.0, 3.0),
+        },
+        // Fully outside.
+        LineSegment {
+            from: point(20.0, -1.0),
+            to: point(25.0, 8.0),
+        },
+    ];
+
+    for &orientation in &[Orientation::Vertical, Orientation::Horizontal] {
+        let options = FillOptions::tolerance(0.01).with_sweep_orientation(orientation);
+        let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+        FillTessellator::new()
+            .tessellate_with_constraints(
+                &path,
+                &[point(5.0, 8.0)],
+                &constraints,
+                &options,
+                &mut simple_builder(&mut buffers),
+            )
+            .unwrap();
+
+        let mut area = 0.0;
+        for tri in buffers.indices.chunks(3) {
+            let a = buffers.vertices[tri[0] as usize];
+            let b = buffers.vertices[tri[1] as usize];
+            let c = buffers.vertices[tri[2] as usize];
+            area += (b - a).cross(c - a).abs() * 0.5;
+
+            // No triangle crosses the constraints.
+            let edge_side = |from: Point, to: Point, p: Point| (to - from).cross(p - from);
+            let is_inside = |p: Point| {
+                let s = [edge_side(a, b, p), edge_side(b, c, p), edge_side(c, a, p)];
+                s.iter().all(|&x| x > 1e-9) || s.iter().all(|&x| x < -1e-9)
+            };
+            for segment in &constraints {
+                for i in 0..=100 {
+                    assert!(!is_inside(segment.sample(i as f64 / 100.0)));
+                }
+            }
+        }
+        assert!((area - 100.0).abs() < 1e-9);
+        assert!(buffers.indices.len() / 3 > 8);
+    }
+}
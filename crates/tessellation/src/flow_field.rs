@@ -0,0 +1,315 @@
+//! Distance and flow fields over the triangles of a fill tessellation.
+
+use crate::geometry_builder::VertexBuffers;
+use crate::math::{Point, Vector};
+use crate::triangle_adjacency::TriangleAdjacency;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// A flow field leading to a target through the interior of a fill tessellation.
+///
+/// The field is defined per triangle. Agents in a triangle move along its direction
+/// to reach the next triangle on the shortest path (going through the centroids of
+/// the triangles and the middle of their shared edges) to the target.
+///
+/// See `flow_field`.
+#[derive(Clone, Debug)]
+pub struct FlowField {
+    positions: Vec<Point>,
+    triangles: Vec<[u32; 3]>,
+    /// Length of the path from the centroid of each triangle to the target,
+    /// or infinity if the target can't be reached from the triangle.
+    pub distances: Vec<f64>,
+    /// Unit direction to follow from the centroid of each triangle, or zero if the
+    /// target can't be reached from the triangle.
+    pub directions: Vec<Vector>,
+    // The point on the boundary of each triangle (or the target) through which
+    // the path goes.
+    waypoints: Vec<Point>,
+}
+
+impl FlowField {
+    /// Returns the index of the triangle containing a point.
+    ///
+    /// This tests every triangle and is not suitable for a large number of queries.
+    pub fn triangle_at(&self, p: Point) -> Option<usize> {
+        triangle_at(&self.positions, &self.triangles, p)
+    }
+
+    /// The unit direction to follow at a given position, or `None` if the position is
+    /// outside of the tessellation.
+    ///
+    /// Unlike `directions`, this takes the position within the triangle into account
+    /// so that following the flow does not leave the tessellation.
+    pub fn direction_at(&self, p: Point) -> Option<Vector> {
+        // On the boundary between two triangles, prefer the one closer to the target.
+        let idx = triangles_at(&self.positions, &self.triangles, p).min_by(|a, b| {
+            self.distances[*a]
+                .partial_cmp(&self.distances[*b])
+                .unwrap_or(Ordering::Equal)
+        })?;
+        if self.distances[idx].is_infinite() {
+            return Some(Vector::zero());
+        }
+
+        Some(
+            (self.waypoints[idx] - p)
+                .try_normalize()
+                .unwrap_or_default(),
+        )
+    }
+}
+
+/// Computes a flow field leading to `target` over a fill tessellation.
+///
+/// `position` provides the position of a vertex. The triangles are expected to
+/// [share their vertices](index.html#shared-vertices) so that their adjacency can
+/// be found.
+///
+/// Returns `None` if the target is not inside of the tessellation.
+pub fn flow_field<OutputVertex, OutputIndex>(
+    buffers: &VertexBuffers<OutputVertex, OutputIndex>,
+    target: Point,
+    position: impl Fn(&OutputVertex) -> Point,
+) -> Option<FlowField>
+where
+    OutputIndex: Copy + Into<u32>,
+{
+    let positions: Vec<Point> = buffers.vertices.iter().map(&position).collect();
+    let adjacency = TriangleAdjacency::new(&buffers.indices);
+    let triangles = &adjacency.triangles;
+    let target_triangle = triangle_at(&positions, triangles, target)?;
+
+    let centroids: Vec<Point> = triangles
+        .iter()
+        .map(|tri| {
+            let [a, b, c] = tri.map(|v| positions[v as usize].to_vector());
+            ((a + b + c) / 3.0).to_point()
+        })
+        .collect();
+
+    let num_triangles = triangles.len();
+    let mut distances = vec![f64::INFINITY; num_triangles];
+    let mut directions = vec![Vector::zero(); num_triangles];
+    let mut waypoints = centroids.clone();
+    waypoints[target_triangle] = target;
+
+    distances[target_triangle] = (target - centroids[target_triangle]).length();
+    if distances[target_triangle] > 0.0 {
+        directions[target_triangle] = (target - centroids[target_triangle]).normalize();
+    }
+
+    // Dijkstra over the adjacency of the triangles, starting from the target.
+    let mut queue = BinaryHeap::new();
+    queue.push(State {
+        distance: distances[target_triangle],
+        triangle: target_triangle,
+    });
+
+    while let Some(State { distance, triangle }) = queue.pop() {
+        if distance > distances[triangle] {
+            continue;
+        }
+
+        let tri = triangles[triangle];
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            let midpoint = positions[a as usize].lerp(positions[b as usize], 0.5);
+            for &neighbor in adjacency.edge_triangles(a, b) {
+                if neighbor == triangle {
+                    continue;
+                }
+                let to_midpoint = midpoint - centroids[neighbor];
+                let d = distance + to_midpoint.length() + (centroids[triangle] - midpoint).length();
+                if d < distances[neighbor] {
+                    distances[neighbor] = d;
+                    directions[neighbor] = to_midpoint.try_normalize().unwrap_or_default();
+                    waypoints[neighbor] = midpoint;
+                    queue.push(State {
+                        distance: d,
+                        triangle: neighbor,
+                    });
+                }
+            }
+        }
+    }
+
+    Some(FlowField {
+        positions,
+        triangles: adjacency.triangles,
+        distances,
+        directions,
+        waypoints,
+    })
+}
+
+/// Computes the distance from the centroid of each triangle of a fill tessellation
+/// to the boundary of the shape (including holes).
+///
+/// The boundary is made of the edges that belong to a single triangle, so the
+/// triangles are expected to [share their vertices](index.html#shared-vertices).
+pub fn distance_to_boundary<OutputVertex, OutputIndex>(
+    buffers: &VertexBuffers<OutputVertex, OutputIndex>,
+    position: impl Fn(&OutputVertex) -> Point,
+) -> Vec<f64>
+where
+    OutputIndex: Copy + Into<u32>,
+{
+    let positions: Vec<Point> = buffers.vertices.iter().map(&position).collect();
+    let adjacency = TriangleAdjacency::new(&buffers.indices);
+
+    let mut boundary: Vec<crate::geom::LineSegment<f64>> = Vec::new();
+    for tri in &adjacency.triangles {
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            if adjacency.is_boundary(a, b) {
+                boundary.push(crate::geom::LineSegment {
+                    from: positions[a as usize],
+                    to: positions[b as usize],
+                });
+            }
+        }
+    }
+
+    adjacency
+        .triangles
+        .iter()
+        .map(|tri| {
+            let [a, b, c] = tri.map(|v| positions[v as usize].to_vector());
+            let centroid = ((a + b + c) / 3.0).to_point();
+            boundary
+                .iter()
+                .map(|edge| edge.distance_to_point(centroid))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect()
+}
+
+#[derive(Copy, Clone, PartialEq)]
+struct State {
+    distance: f64,
+    triangle: usize,
+}
+
+impl Eq for State {}
+
+// Reversed so that the binary heap pops the smallest distance first.
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then(other.triangle.cmp(&self.triangle))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn triangle_at(positions: &[Point], triangles: &[[u32; 3]], p: Point) -> Option<usize> {
+    triangles_at(positions, triangles, p).next()
+}
+
+fn triangles_at<'l>(
+    positions: &'l [Point],
+    triangles: &'l [[u32; 3]],
+    p: Point,
+) -> impl Iterator<Item = usize> + 'l {
+    triangles.iter().enumerate().filter_map(move |(idx, tri)| {
+        let [a, b, c] = tri.map(|v| positions[v as usize]);
+        let d1 = (b - a).cross(p - a);
+        let d2 = (c - b).cross(p - b);
+        let d3 = (a - c).cross(p - c);
+        let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+        let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+        if has_neg && has_pos {
+            None
+        } else {
+            Some(idx)
+        }
+    })
+}
+
+#[cfg(test)]
+use crate::math::point;
+#[cfg(test)]
+use crate::path::Path;
+#[cfg(test)]
+use crate::test_utils::fill;
+#[cfg(test)]
+use crate::FillOptions;
+
+#[cfg(test)]
+fn u_shape() -> VertexBuffers<Point, u16> {
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(30.0, 0.0));
+    builder.line_to(point(30.0, 30.0));
+    builder.line_to(point(20.0, 30.0));
+    builder.line_to(point(20.0, 10.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(10.0, 30.0));
+    builder.line_to(point(0.0, 30.0));
+    builder.close();
+
+    fill(&builder.build(), &FillOptions::default())
+}
+
+#[test]
+fn flow_around_obstacle() {
+    let buffers = u_shape();
+    let target = point(25.0, 25.0);
+    let field = flow_field(&buffers, target, |p| *p).unwrap();
+
+    assert!(flow_field(&buffers, point(15.0, 20.0), |p| *p).is_none());
+    assert!(field.direction_at(point(15.0, 20.0)).is_none());
+    assert!(field.distances.iter().all(|d| d.is_finite()));
+
+    // Follow the flow from the other branch of the U.
+    let mut p = point(5.0, 25.0);
+    let mut steps = 0;
+    let mut went_down = false;
+    while (p - target).length() > 0.5 {
+        let direction = field.direction_at(p).unwrap();
+        p += direction * 0.1;
+        went_down |= p.y < 10.0;
+        steps += 1;
+        assert!(steps < 10000);
+    }
+    // The path goes around the obstacle.
+    assert!(went_down);
+}
+
+#[test]
+fn boundary_distances() {
+    let buffers = u_shape();
+    let distances = distance_to_boundary(&buffers, |p| *p);
+
+    assert_eq!(distances.len(), buffers.indices.len() / 3);
+    for (tri, distance) in buffers.indices.chunks(3).zip(distances.iter()) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|v| buffers.vertices[v as usize]);
+        let centroid = ((a.to_vector() + b.to_vector() + c.to_vector()) / 3.0).to_point();
+        // The centroid's distance to the sides of the U.
+        let expected = [
+            centroid.x,
+            30.0 - centroid.x,
+            centroid.y,
+            if centroid.y > 10.0 {
+                (centroid.x - 10.0).abs().min((centroid.x - 20.0).abs())
+            } else {
+                10.0 - centroid.y
+            },
+        ]
+        .iter()
+        .cloned()
+        .fold(f64::INFINITY, f64::min);
+        // Inside of the U, the concave corners can be closer than the sides.
+        assert!(*distance <= expected + 1e-9);
+        assert!(*distance > 0.0);
+    }
+}
@@ -0,0 +1,310 @@
+//! Anti-aliasing fringes along the boundary of fill tessellations.
+
+use crate::event_queue::{EventQueue, TessEventId};
+use crate::geometry_builder::{FillGeometryBuilder, GeometryBuilder, GeometryBuilderError};
+use crate::math::{Point, Vector};
+use crate::path::AttributeStore;
+use crate::triangle_adjacency::TriangleAdjacency;
+use crate::{FillVertex, TessellationResult, VertexId};
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+// How far the outer vertices can be from the boundary, relative to the width of the
+// fringe. Sharp corners have their fringe truncated beyond this limit.
+const MITER_LIMIT: f64 = 4.0;
+
+/// A geometry builder that forwards the output of the fill tessellator and keeps
+/// track of it so that its boundary can be found.
+pub(crate) struct FringeBuilder<'l> {
+    output: &'l mut dyn FillGeometryBuilder,
+    // The position and the event of each vertex.
+    vertices: HashMap<VertexId, (Point, TessEventId)>,
+    triangles: Vec<[VertexId; 3]>,
+}
+
+// A boundary edge of the tessellation, oriented so that the shape is on its left
+// in a y-up coordinate system.
+struct BoundaryEdge {
+    from: VertexId,
+    to: VertexId,
+    // The outer vertices of the fringe at each end of the edge.
+    outer_from: Option<VertexId>,
+    outer_to: Option<VertexId>,
+}
+
+impl<'l> FringeBuilder<'l> {
+    pub(crate) fn new(output: &'l mut dyn FillGeometryBuilder) -> Self {
+        FringeBuilder {
+            output,
+            vertices: HashMap::new(),
+            triangles: Vec::new(),
+        }
+    }
+
+    /// Adds the fringe around the triangles that were built so far.
+    ///
+    /// The boundary is made of the edges that belong to a single triangle. The outer
+    /// vertices of the fringe have the same sources as the boundary vertices they are
+    /// extruded from.
+    pub(crate) fn add_fringe(
+        &mut self,
+        width: f64,
+        events: &EventQueue,
+        attrib_store: Option<&dyn AttributeStore>,
+        attrib_buffer: &mut [f64],
+    ) -> TessellationResult {
+        let adjacency = TriangleAdjacency::from_triangles(
+            self.triangles.iter().map(|tri| tri.map(|v| v.0)).collect(),
+        );
+
+        // Iterate over the triangles rather than the hash map so that the output
+        // doesn't depend on the order of the latter.
+        let mut edges = Vec::new();
+        let mut outgoing: HashMap<VertexId, Vec<usize>> = HashMap::new();
+        for tri in &self.triangles {
+            for k in 0..3 {
+                let (a, b, c) = (tri[k], tri[(k + 1) % 3], tri[(k + 2) % 3]);
+                if !adjacency.is_boundary(a.0, b.0) {
+                    continue;
+                }
+
+                let side = (self.position(b) - self.position(a))
+                    .cross(self.position(c) - self.position(a));
+                let (from, to) = if side > 0.0 {
+                    (a, b)
+                } else if side < 0.0 {
+                    (b, a)
+                } else {
+                    // Degenerate triangles don't tell which side is inside.
+                    continue;
+                };
+
+                outgoing.entry(from).or_default().push(edges.len());
+                edges.push(BoundaryEdge {
+                    from,
+                    to,
+                    outer_from: None,
+                    outer_to: None,
+                });
+            }
+        }
+
+        // Add an outer vertex at each corner of the boundary, between an edge and the
+        // edge that follows it.
+        for idx in 0..edges.len() {
+            let (from, to) = (edges[idx].from, edges[idx].to);
+            let next = match self.next_edge(&edges, &outgoing, idx) {
+                Some(next) => next,
+                None => continue,
+            };
+
+            let position = self.position(to);
+            let n_in = outer_normal(self.position(from), position);
+            let n_out = outer_normal(position, self.position(edges[next].to));
+            let miter = (n_in + n_out)
+                .try_normalize()
+                .unwrap_or_else(|| (position - self.position(from)).normalize());
+            let cos = miter.dot(n_in);
+            let length = if cos > 1.0 / MITER_LIMIT {
+                width / cos
+            } else {
+                width * MITER_LIMIT
+            };
+
+            let outer = self.output.add_fill_vertex(FillVertex {
+                position: position + miter * length,
+                events,
+                current_event: self.vertices[&to].1,
+                attrib_store,
+                attrib_buffer: &mut *attrib_buffer,
+                alpha: 0.0,
+            })?;
+
+            edges[idx].outer_to = Some(outer);
+            edges[next].outer_from = Some(outer);
+        }
+
+        for edge in &edges {
+            if let (Some(outer_from), Some(outer_to)) = (edge.outer_from, edge.outer_to) {
+                self.output.add_triangle(edge.from, edge.to, outer_to);
+                self.output.add_triangle(edge.from, outer_to, outer_from);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn position(&self, id: VertexId) -> Point {
+        self.vertices[&id].0
+    }
+
+    // The boundary edge that follows an edge, which is the first one met when turning
+    // clockwise around the end of the edge, through the inside of the shape. This
+    // pairs the edges properly when several parts of the boundary meet at a vertex.
+    fn next_edge(
+        &self,
+        edges: &[BoundaryEdge],
+        outgoing: &HashMap<VertexId, Vec<usize>>,
+        idx: usize,
+    ) -> Option<usize> {
+        let center = self.position(edges[idx].to);
+        let back = self.position(edges[idx].from) - center;
+
+        let mut best = None;
+        let mut best_angle = f64::INFINITY;
+        for &candidate in outgoing.get(&edges[idx].to)? {
+            let v = self.position(edges[candidate].to) - center;
+            let mut angle = -back.cross(v).atan2(back.dot(v));
+            if angle <= 0.0 {
+                angle += 2.0 * PI;
+            }
+            if angle < best_angle {
+                best_angle = angle;
+                best = Some(candidate);
+            }
+        }
+
+        best
+    }
+}
+
+impl<'l> GeometryBuilder for FringeBuilder<'l> {
+    fn add_triangle(&mut self, a: VertexId, b: VertexId, c: VertexId) {
+        self.triangles.push([a, b, c]);
+        self.output.add_triangle(a, b, c);
+    }
+}
+
+impl<'l> FillGeometryBuilder for FringeBuilder<'l> {
+    fn add_fill_vertex(&mut self, vertex: FillVertex) -> Result<VertexId, GeometryBuilderError> {
+        let position = vertex.position;
+        let event = vertex.current_event;
+        let id = self.output.add_fill_vertex(vertex)?;
+        self.vertices.insert(id, (position, event));
+
+        Ok(id)
+    }
+}
+
+// The unit normal on the right side of an edge, which is outside of the shape.
+fn outer_normal(from: Point, to: Point) -> Vector {
+    let d = (to - from).normalize();
+    Vector::new(d.y, -d.x)
+}
+
+#[cfg(test)]
+use crate::geometry_builder::{BuffersBuilder, FillVertexConstructor, VertexBuffers};
+#[cfg(test)]
+use crate::math::point;
+#[cfg(test)]
+use crate::path::Path;
+#[cfg(test)]
+use crate::test_utils::{area, rectangle};
+#[cfg(test)]
+use crate::{FillOptions, FillTessellator};
+
+#[cfg(test)]
+struct WithAlpha;
+
+#[cfg(test)]
+impl FillVertexConstructor<(Point, f64)> for WithAlpha {
+    fn new_vertex(&mut self, vertex: FillVertex) -> (Point, f64) {
+        (vertex.position(), vertex.alpha())
+    }
+}
+
+#[cfg(test)]
+fn tessellate_with_fringe(path: &Path, width: f64) -> VertexBuffers<(Point, f64), u16> {
+    let mut buffers: VertexBuffers<(Point, f64), u16> = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate_path(
+            path,
+            &FillOptions::default().with_antialiasing_fringe(width),
+            &mut BuffersBuilder::new(&mut buffers, WithAlpha),
+        )
+        .unwrap();
+
+    buffers
+}
+
+#[test]
+fn fringe_around_square() {
+    let mut builder = Path::builder();
+    rectangle(&mut builder, point(0.0, 0.0), point(10.0, 10.0));
+    let path = builder.build();
+
+    let buffers = tessellate_with_fringe(&path, 1.0);
+
+    let outer: Vec<Point> = buffers
+        .vertices
+        .iter()
+        .filter(|(_, alpha)| *alpha == 0.0)
+        .map(|(p, _)| *p)
+        .collect();
+    assert_eq!(outer.len(), 4);
+    for p in &outer {
+        assert!((p.x.abs() - 1.0).abs() < 1e-9 || (p.x - 11.0).abs() < 1e-9);
+        assert!((p.y.abs() - 1.0).abs() < 1e-9 || (p.y - 11.0).abs() < 1e-9);
+    }
+    assert_eq!(buffers.indices.len(), (2 + 8) * 3);
+    assert!((area(&buffers, |(p, _)| *p) - 144.0).abs() < 1e-9);
+}
+
+#[test]
+fn fringe_around_hole() {
+    let mut builder = Path::builder();
+    rectangle(&mut builder, point(0.0, 0.0), point(10.0, 10.0));
+    rectangle(&mut builder, point(3.0, 3.0), point(7.0, 7.0));
+    let path = builder.build();
+
+    let buffers = tessellate_with_fringe(&path, 0.5);
+
+    let num_outer = buffers
+        .vertices
+        .iter()
+        .filter(|(_, alpha)| *alpha == 0.0)
+        .count();
+    assert_eq!(num_outer, 8);
+    // The fringe grows the square and shrinks the hole.
+    assert!((area(&buffers, |(p, _)| *p) - (11.0 * 11.0 - 3.0 * 3.0)).abs() < 1e-9);
+
+    // The fill itself is unchanged.
+    let mut fill: VertexBuffers<(Point, f64), u16> = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate_path(
+            &path,
+            &FillOptions::default(),
+            &mut BuffersBuilder::new(&mut fill, WithAlpha),
+        )
+        .unwrap();
+    assert!(fill.vertices.iter().all(|(_, alpha)| *alpha == 1.0));
+    assert_eq!(&buffers.indices[..fill.indices.len()], &fill.indices[..]);
+}
+
+#[test]
+fn fringe_around_circle() {
+    let mut buffers: VertexBuffers<(Point, f64), u16> = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate_circle(
+            point(0.0, 0.0),
+            10.0,
+            &FillOptions::tolerance(0.01).with_antialiasing_fringe(1.0),
+            &mut BuffersBuilder::new(&mut buffers, WithAlpha),
+        )
+        .unwrap();
+
+    // The quadratic curves approximating the circle overshoot it by up to 0.3%.
+    let mut num_outer = 0;
+    for (p, alpha) in &buffers.vertices {
+        let d = p.to_vector().length();
+        if *alpha == 0.0 {
+            assert!(d > 10.99 && d < 11.04);
+            num_outer += 1;
+        } else {
+            assert!(d > 9.99 && d < 10.04);
+        }
+    }
+    // One outer vertex per vertex on the circle.
+    assert_eq!(num_outer * 2, buffers.vertices.len());
+}
@@ -16,6 +16,8 @@
 //! * [`GeometryBuilder`](trait.GeometryBuilder.html)
 //! * [`FillGeometryBuilder`](trait.FillGeometryBuilder.html)
 //! * [`StrokeGeometryBuilder`](trait.StrokeGeometryBuilder.html)
+//! * [`LoopBlinnGeometryBuilder`](trait.LoopBlinnGeometryBuilder.html)
+//! * [`GradientMeshGeometryBuilder`](trait.GradientMeshGeometryBuilder.html)
 //!
 //! The traits above are what the tessellators interface with. It is very common to push
 //! vertices and indices into a pair of vectors, so to facilitate this pattern this module
@@ -190,7 +192,7 @@
 //!
 
 use crate::math::Point;
-use crate::{FillVertex, Index, StrokeVertex, VertexId};
+use crate::{FillVertex, GradientMeshVertex, Index, LoopBlinnVertex, StrokeVertex, VertexId};
 
 use std::convert::From;
 use std::ops::Add;
@@ -213,6 +215,8 @@ pub enum GeometryBuilderError {
 /// vertices):
 ///  - [`FillGeometryBuilder`](trait.FillGeometryBuilder.html)
 ///  - [`StrokeGeometryBuilder`](trait.StrokeGeometryBuilder.html)
+///  - [`LoopBlinnGeometryBuilder`](trait.LoopBlinnGeometryBuilder.html)
+///  - [`GradientMeshGeometryBuilder`](trait.GradientMeshGeometryBuilder.html)
 ///
 /// See the [`geometry_builder`](index.html) module documentation for more detailed explanation.
 pub trait GeometryBuilder {
@@ -222,8 +226,8 @@ pub trait GeometryBuilder {
     fn begin_geometry(&mut self) {}
 
     /// Called at the end of a generation.
-    /// Returns the number of vertices and indices added since the last time begin_geometry was
-    /// called.
+    ///
+    /// The geometry added since the last time begin_geometry was called is complete.
     fn end_geometry(&mut self) {}
 
     /// Insert a triangle made of vertices that were added after the last call to begin_geometry.
@@ -262,6 +266,35 @@ pub trait StrokeGeometryBuilder: GeometryBuilder {
         -> Result<VertexId, GeometryBuilderError>;
 }
 
+/// A Geometry builder to interface with the [`LoopBlinnTessellator`](../struct.LoopBlinnTessellator.html).
+///
+/// Types implementing this trait must also implement the [`GeometryBuilder`](trait.GeometryBuilder.html) trait.
+pub trait LoopBlinnGeometryBuilder: GeometryBuilder {
+    /// Inserts a vertex, providing its position and curve coordinates.
+    /// Returns a vertex id that is only valid between begin_geometry and end_geometry.
+    ///
+    /// This method can only be called between begin_geometry and end_geometry.
+    fn add_loop_blinn_vertex(
+        &mut self,
+        vertex: LoopBlinnVertex,
+    ) -> Result<VertexId, GeometryBuilderError>;
+}
+
+/// A Geometry builder to interface with the
+/// [`tessellate_gradient_mesh`](../fn.tessellate_gradient_mesh.html) function.
+///
+/// Types implementing this trait must also implement the [`GeometryBuilder`](trait.GeometryBuilder.html) trait.
+pub trait GradientMeshGeometryBuilder: GeometryBuilder {
+    /// Inserts a vertex, providing its position and color.
+    /// Returns a vertex id that is only valid between begin_geometry and end_geometry.
+    ///
+    /// This method can only be called between begin_geometry and end_geometry.
+    fn add_gradient_mesh_vertex(
+        &mut self,
+        vertex: GradientMeshVertex,
+    ) -> Result<VertexId, GeometryBuilderError>;
+}
+
 /// Structure that holds the vertex and index data.
 ///
 /// Usually written into though temporary `BuffersBuilder` objects.
@@ -377,6 +410,26 @@ impl<B: StrokeGeometryBuilder> StrokeGeometryBuilder for InvertWinding<B> {
     }
 }
 
+impl<B: LoopBlinnGeometryBuilder> LoopBlinnGeometryBuilder for InvertWinding<B> {
+    #[inline]
+    fn add_loop_blinn_vertex(
+        &mut self,
+        vertex: LoopBlinnVertex,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        self.0.add_loop_blinn_vertex(vertex)
+    }
+}
+
+impl<B: GradientMeshGeometryBuilder> GradientMeshGeometryBuilder for InvertWinding<B> {
+    #[inline]
+    fn add_gradient_mesh_vertex(
+        &mut self,
+        vertex: GradientMeshVertex,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        self.0.add_gradient_mesh_vertex(vertex)
+    }
+}
+
 /// A trait specifying how to create vertex values.
 pub trait FillVertexConstructor<OutputVertex> {
     fn new_vertex(&mut self, vertex: FillVertex) -> OutputVertex;
@@ -387,6 +440,16 @@ pub trait StrokeVertexConstructor<OutputVertex> {
     fn new_vertex(&mut self, vertex: StrokeVertex) -> OutputVertex;
 }
 
+/// A trait specifying how to create vertex values.
+pub trait LoopBlinnVertexConstructor<OutputVertex> {
+    fn new_vertex(&mut self, vertex: LoopBlinnVertex) -> OutputVertex;
+}
+
+/// A trait specifying how to create vertex values.
+pub trait GradientMeshVertexConstructor<OutputVertex> {
+    fn new_vertex(&mut self, vertex: GradientMeshVertex) -> OutputVertex;
+}
+
 /// A simple vertex constructor that just takes the position.
 pub struct Positions;
 
@@ -402,6 +465,18 @@ impl StrokeVertexConstructor<Point> for Positions {
     }
 }
 
+impl LoopBlinnVertexConstructor<Point> for Positions {
+    fn new_vertex(&mut self, vertex: LoopBlinnVertex) -> Point {
+        vertex.position
+    }
+}
+
+impl GradientMeshVertexConstructor<Point> for Positions {
+    fn new_vertex(&mut self, vertex: GradientMeshVertex) -> Point {
+        vertex.position
+    }
+}
+
 impl<F, OutputVertex> FillVertexConstructor<OutputVertex> for F
 where
     F: Fn(FillVertex) -> OutputVertex,
@@ -420,6 +495,24 @@ where
     }
 }
 
+impl<F, OutputVertex> LoopBlinnVertexConstructor<OutputVertex> for F
+where
+    F: Fn(LoopBlinnVertex) -> OutputVertex,
+{
+    fn new_vertex(&mut self, vertex: LoopBlinnVertex) -> OutputVertex {
+        self(vertex)
+    }
+}
+
+impl<F, OutputVertex> GradientMeshVertexConstructor<OutputVertex> for F
+where
+    F: Fn(GradientMeshVertex) -> OutputVertex,
+{
+    fn new_vertex(&mut self, vertex: GradientMeshVertex) -> OutputVertex {
+        self(vertex)
+    }
+}
+
 /// A `BuffersBuilder` that takes the actual vertex type as input.
 pub type SimpleBuffersBuilder<'l> = BuffersBuilder<'l, Point, u16, Positions>;
 
@@ -506,6 +599,50 @@ where
     }
 }
 
+impl<'l, OutputVertex, OutputIndex, Ctor> LoopBlinnGeometryBuilder
+    for BuffersBuilder<'l, OutputVertex, OutputIndex, Ctor>
+where
+    OutputVertex: 'l,
+    OutputIndex: Add + From<VertexId> + MaxIndex,
+    Ctor: LoopBlinnVertexConstructor<OutputVertex>,
+{
+    fn add_loop_blinn_vertex(
+        &mut self,
+        v: LoopBlinnVertex,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        self.buffers
+            .vertices
+            .push(self.vertex_constructor.new_vertex(v));
+        let len = self.buffers.vertices.len();
+        if len > OutputIndex::MAX {
+            return Err(GeometryBuilderError::TooManyVertices);
+        }
+        Ok(VertexId((len - 1) as Index))
+    }
+}
+
+impl<'l, OutputVertex, OutputIndex, Ctor> GradientMeshGeometryBuilder
+    for BuffersBuilder<'l, OutputVertex, OutputIndex, Ctor>
+where
+    OutputVertex: 'l,
+    OutputIndex: Add + From<VertexId> + MaxIndex,
+    Ctor: GradientMeshVertexConstructor<OutputVertex>,
+{
+    fn add_gradient_mesh_vertex(
+        &mut self,
+        v: GradientMeshVertex,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        self.buffers
+            .vertices
+            .push(self.vertex_constructor.new_vertex(v));
+        let len = self.buffers.vertices.len();
+        if len > OutputIndex::MAX {
+            return Err(GeometryBuilderError::TooManyVertices);
+        }
+        Ok(VertexId((len - 1) as Index))
+    }
+}
+
 /// A geometry builder that does not output any geometry.
 ///
 /// Mostly useful for testing.
@@ -553,6 +690,32 @@ impl StrokeGeometryBuilder for NoOutput {
     }
 }
 
+impl LoopBlinnGeometryBuilder for NoOutput {
+    fn add_loop_blinn_vertex(
+        &mut self,
+        _: LoopBlinnVertex,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        if self.next_vertex == u32::MAX {
+            return Err(GeometryBuilderError::TooManyVertices);
+        }
+        self.next_vertex += 1;
+        Ok(VertexId(self.next_vertex - 1))
+    }
+}
+
+impl GradientMeshGeometryBuilder for NoOutput {
+    fn add_gradient_mesh_vertex(
+        &mut self,
+        _: GradientMeshVertex,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        if self.next_vertex == u32::MAX {
+            return Err(GeometryBuilderError::TooManyVertices);
+        }
+        self.next_vertex += 1;
+        Ok(VertexId(self.next_vertex - 1))
+    }
+}
+
 /// Provides the maximum value of an index.
 ///
 /// This should be the maximum value representable by the index type up
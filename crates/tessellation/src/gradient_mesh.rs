@@ -0,0 +1,311 @@
+//! Tessellation of gradient meshes, following the SVG 2 and PDF (shading type 6)
+//! gradient mesh model.
+
+use crate::geom::CubicBezierSegment;
+use crate::math::*;
+use crate::{
+    FillOptions, GeometryBuilderError, GradientMeshGeometryBuilder, TessellationResult, VertexId,
+};
+
+/// A vertex produced by the gradient mesh tessellation functions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GradientMeshVertex {
+    pub position: Point,
+    /// The color of the patch at this position, interpolated from the colors
+    /// at the corners of the patch.
+    pub color: [f64; 4],
+    /// The coordinates of the vertex in the parameter space of the patch.
+    pub uv: Point,
+}
+
+/// A Coons patch: a surface delimited by four cubic bézier curves with a
+/// color at each corner.
+///
+/// The curves form a closed loop: `edges[0]` goes from corner 0 to corner 1,
+/// `edges[1]` from corner 1 to corner 2, `edges[2]` from corner 2 to corner 3
+/// and `edges[3]` from corner 3 back to corner 0. The last point of
+/// each curve is expected to be the first point of the next one.
+///
+/// The color of corner `i` is `colors[i]`. Colors are interpolated
+/// bilinearly in the parameter space of the patch.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CoonsPatch {
+    pub edges: [CubicBezierSegment<f64>; 4],
+    pub colors: [[f64; 4]; 4],
+}
+
+impl CoonsPatch {
+    /// Position of the patch at parameters `u` and `v` in `[0, 1]`.
+    ///
+    /// `(0, 0)` is corner 0, `(1, 0)` is corner 1, `(1, 1)` is corner 2 and
+    /// `(0, 1)` is corner 3.
+    pub fn sample(&self, u: f64, v: f64) -> Point {
+        let [e0, e1, e2, e3] = &self.edges;
+        let top = e0.sample(u).to_vector();
+        let right = e1.sample(v).to_vector();
+        let bottom = e2.sample(1.0 - u).to_vector();
+        let left = e3.sample(1.0 - v).to_vector();
+
+        let p0 = e0.from.to_vector();
+        let p1 = e1.from.to_vector();
+        let p2 = e2.from.to_vector();
+        let p3 = e3.from.to_vector();
+
+        let ruled = top * (1.0 - v) + bottom * v + left * (1.0 - u) + right * u;
+        let bilinear = p0 * ((1.0 - u) * (1.0 - v))
+            + p1 * (u * (1.0 - v))
+            + p2 * (u * v)
+            + p3 * ((1.0 - u) * v);
+
+        (ruled - bilinear).to_point()
+    }
+
+    /// Color of the patch at parameters `u` and `v` in `[0, 1]`.
+    pub fn color(&self, u: f64, v: f64) -> [f64; 4] {
+        let weights = [(1.0 - u) * (1.0 - v), u * (1.0 - v), u * v, (1.0 - u) * v];
+        let mut color = [0.0; 4];
+        for (corner, weight) in self.colors.iter().zip(weights.iter()) {
+            for (c, channel) in color.iter_mut().zip(corner.iter()) {
+                *c += channel * weight;
+            }
+        }
+
+        color
+    }
+
+    // Number of subdivisions along u and v needed to approximate the patch
+    // within the tolerance threshold.
+    fn subdivisions(&self, tolerance: f64) -> (u32, u32) {
+        let count = |curve: &CubicBezierSegment<f64>| curve.flattened(tolerance).count() as u32;
+        let [e0, e1, e2, e3] = &self.edges;
+
+        (
+            count(e0).max(count(e2)).max(1),
+            count(e1).max(count(e3)).max(1),
+        )
+    }
+}
+
+/// Tessellate a Coons patch.
+///
+/// The patch is subdivided into a grid of quads (two triangles each) so that the
+/// boundary curves are approximated within the tolerance threshold of the fill
+/// options. The other fill options are ignored.
+///
+/// ## Example
+///
+/// ```
+/// use lyon_tessellation::{tessellate_coons_patch, CoonsPatch, GradientMeshVertex, FillOptions};
+/// use lyon_tessellation::geometry_builder::{BuffersBuilder, VertexBuffers};
+/// use lyon_tessellation::geom::CubicBezierSegment;
+/// use lyon_tessellation::math::point;
+///
+/// let corners = [point(0.0, 0.0), point(10.0, 0.0), point(10.0, 10.0), point(0.0, 10.0)];
+/// let edge = |i: usize| {
+///     let (from, to) = (corners[i], corners[(i + 1) % 4]);
+///     CubicBezierSegment { from, ctrl1: from.lerp(to, 1.0 / 3.0), ctrl2: from.lerp(to, 2.0 / 3.0), to }
+/// };
+/// let patch = CoonsPatch {
+///     edges: [edge(0), edge(1), edge(2), edge(3)],
+///     colors: [
+///         [1.0, 0.0, 0.0, 1.0],
+///         [0.0, 1.0, 0.0, 1.0],
+///         [0.0, 0.0, 1.0, 1.0],
+///         [1.0, 1.0, 1.0, 1.0],
+///     ],
+/// };
+///
+/// let mut buffers: VertexBuffers<GradientMeshVertex, u16> = VertexBuffers::new();
+/// tessellate_coons_patch(
+///     &patch,
+///     &FillOptions::default(),
+///     &mut BuffersBuilder::new(&mut buffers, |vertex: GradientMeshVertex| vertex),
+/// ).unwrap();
+///
+/// // A patch with straight edges is a single quad.
+/// assert_eq!(buffers.indices.len(), 6);
+/// ```
+pub fn tessellate_coons_patch(
+    patch: &CoonsPatch,
+    options: &FillOptions,
+    output: &mut dyn GradientMeshGeometryBuilder,
+) -> TessellationResult {
+    tessellate_gradient_mesh(core::slice::from_ref(patch), options, output)
+}
+
+/// Tessellate the patches of a gradient mesh.
+///
+/// All patches are subdivided the same way so that patches sharing an edge produce
+/// the same vertices along it, which avoids cracks between them.
+///
+/// See `tessellate_coons_patch`.
+pub fn tessellate_gradient_mesh(
+    patches: &[CoonsPatch],
+    options: &FillOptions,
+    output: &mut dyn GradientMeshGeometryBuilder,
+) -> TessellationResult {
+    let tolerance = options.tolerance;
+    let mut subdivisions = (1, 1);
+    for patch in patches {
+        let (nu, nv) = patch.subdivisions(tolerance);
+        subdivisions.0 = subdivisions.0.max(nu);
+        subdivisions.1 = subdivisions.1.max(nv);
+    }
+
+    output.begin_geometry();
+
+    for patch in patches {
+        if let Err(e) = add_patch(patch, subdivisions, output) {
+            output.abort_geometry();
+            return Err(e.into());
+        }
+    }
+
+    output.end_geometry();
+
+    Ok(())
+}
+
+fn add_patch(
+    patch: &CoonsPatch,
+    (nu, nv): (u32, u32),
+    output: &mut dyn GradientMeshGeometryBuilder,
+) -> Result<(), GeometryBuilderError> {
+    let mut prev_row: Vec<VertexId> = Vec::with_capacity(nu as usize + 1);
+    let mut row: Vec<VertexId> = Vec::with_capacity(nu as usize + 1);
+
+    for j in 0..=nv {
+        let v = j as f64 / nv as f64;
+        row.clear();
+        for i in 0..=nu {
+            let u = i as f64 / nu as f64;
+            row.push(output.add_gradient_mesh_vertex(GradientMeshVertex {
+                position: patch.sample(u, v),
+                color: patch.color(u, v),
+                uv: point(u, v),
+            })?);
+        }
+
+        if j > 0 {
+            for i in 0..(nu as usize) {
+                let (a, b) = (prev_row[i], prev_row[i + 1]);
+                let (c, d) = (row[i + 1], row[i]);
+                output.add_triangle(a, b, c);
+                output.add_triangle(a, c, d);
+            }
+        }
+
+        std::mem::swap(&mut prev_row, &mut row);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+use crate::geom::Translation;
+#[cfg(test)]
+use crate::geometry_builder::{BuffersBuilder, VertexBuffers};
+
+#[cfg(test)]
+fn square_patch() -> CoonsPatch {
+    let corners = [
+        point(0.0, 0.0),
+        point(10.0, 0.0),
+        point(10.0, 10.0),
+        point(0.0, 10.0),
+    ];
+    let edge = |i: usize| {
+        let (from, to) = (corners[i], corners[(i + 1) % 4]);
+        CubicBezierSegment {
+            from,
+            ctrl1: from.lerp(to, 1.0 / 3.0),
+            ctrl2: from.lerp(to, 2.0 / 3.0),
+            to,
+        }
+    };
+
+    CoonsPatch {
+        edges: [edge(0), edge(1), edge(2), edge(3)],
+        colors: [
+            [1.0, 0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0, 1.0],
+            [0.0, 0.0, 0.0, 0.0],
+        ],
+    }
+}
+
+#[test]
+fn coons_patch_sample() {
+    let mut patch = square_patch();
+
+    assert_eq!(patch.sample(0.0, 0.0), point(0.0, 0.0));
+    assert_eq!(patch.sample(1.0, 0.0), point(10.0, 0.0));
+    assert_eq!(patch.sample(1.0, 1.0), point(10.0, 10.0));
+    assert_eq!(patch.sample(0.0, 1.0), point(0.0, 10.0));
+    assert!((patch.sample(0.25, 0.5) - point(2.5, 5.0)).length() < 1e-12);
+    assert_eq!(patch.color(0.5, 0.5), [0.25, 0.25, 0.25, 0.75]);
+    assert_eq!(patch.color(1.0, 0.0), [0.0, 1.0, 0.0, 1.0]);
+
+    // Bend the top edge, the boundary of the patch follows it.
+    patch.edges[0].ctrl1.y = -4.0;
+    patch.edges[0].ctrl2.y = -4.0;
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        assert!((patch.sample(t, 0.0) - patch.edges[0].sample(t)).length() < 1e-12);
+        assert!((patch.sample(1.0, t) - patch.edges[1].sample(t)).length() < 1e-12);
+        assert!((patch.sample(t, 1.0) - patch.edges[2].sample(1.0 - t)).length() < 1e-12);
+        assert!((patch.sample(0.0, t) - patch.edges[3].sample(1.0 - t)).length() < 1e-12);
+    }
+}
+
+#[test]
+fn gradient_mesh() {
+    let mut left = square_patch();
+    left.edges[1].ctrl1.x = 13.0;
+    left.edges[1].ctrl2.x = 13.0;
+    // The patch on the right shares its left edge with the right edge of the first patch.
+    let mut right = square_patch();
+    for edge in &mut right.edges {
+        *edge = edge.transformed(&Translation::new(10.0, 0.0));
+    }
+    right.edges[3] = left.edges[1].flip();
+
+    let mut buffers: VertexBuffers<GradientMeshVertex, u16> = VertexBuffers::new();
+    tessellate_gradient_mesh(
+        &[left, right],
+        &FillOptions::tolerance(0.01),
+        &mut BuffersBuilder::new(&mut buffers, |vertex: GradientMeshVertex| vertex),
+    )
+    .unwrap();
+
+    // Both patches are subdivided the same way.
+    let n = buffers.vertices.len() / 2;
+    assert!(n > 4);
+    assert_eq!(buffers.indices.len() % 12, 0);
+
+    // Vertices along the shared edge match.
+    let on_shared_edge = |v: &&GradientMeshVertex, u: f64| v.uv.x == u;
+    let a: Vec<Point> = buffers.vertices[..n]
+        .iter()
+        .filter(|v| on_shared_edge(v, 1.0))
+        .map(|v| v.position)
+        .collect();
+    let b: Vec<Point> = buffers.vertices[n..]
+        .iter()
+        .filter(|v| on_shared_edge(v, 0.0))
+        .map(|v| v.position)
+        .collect();
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(b.iter()) {
+        assert!((*a - *b).length() < 1e-9);
+    }
+
+    // All triangles have the same orientation.
+    for tri in buffers.indices.chunks(3) {
+        let a = buffers.vertices[tri[0] as usize].position;
+        let b = buffers.vertices[tri[1] as usize].position;
+        let c = buffers.vertices[tri[2] as usize].position;
+        assert!((b - a).cross(c - a) > 0.0);
+    }
+}
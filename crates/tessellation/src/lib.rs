@@ -38,6 +38,8 @@
 //!
 //! * [FillTessellator](struct.FillTessellator.html) - Tessellator for complex path fill operations.
 //! * [StrokeTessellator](struct.StrokeTessellator.html) - Tessellator for complex path stroke operations.
+//! * [LoopBlinnTessellator](struct.LoopBlinnTessellator.html) - Tessellator for fills with resolution-independent curves.
+//! * [tessellate_gradient_mesh](fn.tessellate_gradient_mesh.html) - Tessellation of gradient meshes made of Coons patches.
 //! * [`GeometryBuilder`](geometry_builder/trait.GeometryBuilder.html) - (See the documentation of the
 //!   [geometry_builder module](geometry_builder/index.html)) which the above two are built on. This trait
 //!   provides an interface for types that help with building and assembling the vertices and triangles that
@@ -160,6 +162,15 @@
 //! There is an [example](https://github.com/nical/lyon/tree/master/examples/wgpu) showing how
 //! it can be done with wgpu.
 //!
+//! ### Shared vertices
+//!
+//! The fill tessellator adds each vertex once and references it from all of the
+//! triangles that use it. The functions that work on the adjacency of the triangles of a
+//! tessellation, like [flow_field](fn.flow_field.html), [decimate](fn.decimate.html) or
+//! [stripify](fn.stripify.html), rely on this: two triangles are adjacent if they share
+//! the two vertices of an edge, so geometry builders that duplicate the vertices of
+//! each triangle produce tessellations where none of the triangles are adjacent.
+//!
 //! ### Flattening and tolerance
 //!
 //! Most tessellators in this crate currently operate on flattened paths (paths or shapes represented
@@ -191,12 +202,22 @@ use lyon_extra as extra;
 pub extern crate serde;
 
 mod basic_shapes;
+mod convex_decomposition;
+mod decimation;
+mod delaunay;
 mod event_queue;
 mod fill;
+mod flow_field;
+mod fringe;
 pub mod geometry_builder;
+mod gradient_mesh;
+mod loop_blinn;
 mod math_utils;
 mod monotone;
+mod service;
+mod strip;
 mod stroke;
+mod triangle_adjacency;
 
 #[cfg(test)]
 #[rustfmt::skip]
@@ -205,6 +226,8 @@ mod earcut_tests;
 mod fill_tests;
 #[cfg(test)]
 mod fuzz_tests;
+#[cfg(test)]
+mod test_utils;
 
 pub use crate::path::math;
 
@@ -219,10 +242,36 @@ pub use crate::fill::*;
 #[doc(inline)]
 pub use crate::stroke::*;
 
+#[doc(inline)]
+pub use crate::loop_blinn::*;
+
+#[doc(inline)]
+pub use crate::gradient_mesh::*;
+
+#[doc(inline)]
+pub use crate::decimation::*;
+
+#[doc(inline)]
+pub use crate::delaunay::*;
+
+#[doc(inline)]
+pub use crate::convex_decomposition::*;
+
+#[doc(inline)]
+pub use crate::flow_field::*;
+
+#[doc(inline)]
+pub use crate::strip::*;
+
+#[doc(inline)]
+pub use crate::service::*;
+
 #[doc(inline)]
 pub use crate::geometry_builder::{
     BuffersBuilder, FillGeometryBuilder, FillVertexConstructor, GeometryBuilder,
-    GeometryBuilderError, StrokeGeometryBuilder, StrokeVertexConstructor, VertexBuffers,
+    GeometryBuilderError, GradientMeshGeometryBuilder, GradientMeshVertexConstructor,
+    LoopBlinnGeometryBuilder, LoopBlinnVertexConstructor, StrokeGeometryBuilder,
+    StrokeVertexConstructor, VertexBuffers,
 };
 
 pub use crate::path::{AttributeIndex, Attributes, FillRule, LineCap, LineJoin, Side};
@@ -502,6 +551,18 @@ pub struct FillOptions {
     ///
     /// Default value: `true`.
     pub handle_intersections: bool,
+
+    /// If set, a fringe of triangles of the given width is added outside of the
+    /// boundary of the shape so that it can be rendered with anti-aliasing without
+    /// multisampling.
+    ///
+    /// The vertices of the fill have an alpha of one and the outer vertices of the
+    /// fringe an alpha of zero (see `FillVertex::alpha`). Interpolating the alpha
+    /// over the triangles and using it as coverage fades the edges of the shape out.
+    /// The width is typically one pixel, in the coordinate space of the path.
+    ///
+    /// Default value: `None`.
+    pub antialiasing_fringe: Option<f64>,
 }
 
 impl FillOptions {
@@ -517,6 +578,7 @@ impl FillOptions {
         fill_rule: Self::DEFAULT_FILL_RULE,
         sweep_orientation: Self::DEFAULT_SWEEP_ORIENTATION,
         handle_intersections: true,
+        antialiasing_fringe: None,
     };
 
     #[inline]
@@ -559,6 +621,12 @@ impl FillOptions {
         self.handle_intersections = intersections;
         self
     }
+
+    #[inline]
+    pub const fn with_antialiasing_fringe(mut self, width: f64) -> Self {
+        self.antialiasing_fringe = Some(width);
+        self
+    }
 }
 
 impl Default for FillOptions {
@@ -0,0 +1,429 @@
+//! Resolution-independent curve fills, based on "Resolution Independent Curve
+//! Rendering using Programmable Graphics Hardware" by Charles Loop and Jim Blinn.
+
+use crate::geom::QuadraticBezierSegment;
+use crate::math::*;
+use crate::path::iterator::PathIterator;
+use crate::path::{FillRule, Path, PathEvent};
+use crate::{
+    FillGeometryBuilder, FillOptions, FillTessellator, FillVertex, GeometryBuilder,
+    GeometryBuilderError, LoopBlinnGeometryBuilder, TessellationResult, VertexId,
+};
+
+/// A vertex produced by the `LoopBlinnTessellator`.
+///
+/// The `u` and `v` coordinates are meant to be interpolated across the triangles
+/// and the fragment shader discards the fragments for which `sign * (u * u - v)`
+/// is positive:
+///
+/// ```glsl
+/// in vec3 v_uv_sign;
+///
+/// void main() {
+///     float u = v_uv_sign.x;
+///     float v = v_uv_sign.y;
+///     if (v_uv_sign.z * (u * u - v) > 0.0) {
+///         discard;
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// Vertices of the interior triangles have coordinates that pass this test everywhere
+/// so that the interior and the curves can be rendered with the same shader.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LoopBlinnVertex {
+    pub position: Point,
+    pub u: f64,
+    pub v: f64,
+    /// Either `1.0` or `-1.0` depending on which side of the curve is filled.
+    pub sign: f64,
+}
+
+impl LoopBlinnVertex {
+    fn interior(position: Point) -> Self {
+        LoopBlinnVertex {
+            position,
+            u: 0.0,
+            v: 1.0,
+            sign: 1.0,
+        }
+    }
+}
+
+/// A fill tessellator that does not flatten quadratic bézier curves.
+///
+/// The output is made of interior triangles covering the shape with its curves
+/// replaced by straight lines, and of one triangle for each quadratic bézier curve,
+/// formed by its endpoints and its control point. The curve triangles carry the
+/// coordinates used by the fragment shader to discard the pixels on the wrong side
+/// of the curve (see `LoopBlinnVertex`). This produces fills that remain smooth at
+/// any scale without re-tessellating.
+///
+/// Cubic bézier curves are approximated with quadratic ones using the tolerance
+/// threshold of the fill options.
+///
+/// The curve triangles are expected to not overlap each other or other parts of the
+/// path. In order for this to be the case, curves that are large or close to other
+/// edges may need to be subdivided beforehand.
+///
+/// ## Example
+///
+/// ```
+/// use lyon_tessellation::{LoopBlinnTessellator, LoopBlinnVertex, FillOptions};
+/// use lyon_tessellation::geometry_builder::{BuffersBuilder, VertexBuffers};
+/// use lyon_tessellation::math::point;
+/// use lyon_tessellation::path::Path;
+///
+/// let mut builder = Path::builder();
+/// builder.begin(point(0.0, 0.0));
+/// builder.line_to(point(10.0, 0.0));
+/// builder.quadratic_bezier_to(point(10.0, 10.0), point(0.0, 10.0));
+/// builder.end(true);
+/// let path = builder.build();
+///
+/// let mut buffers: VertexBuffers<LoopBlinnVertex, u16> = VertexBuffers::new();
+/// let mut tessellator = LoopBlinnTessellator::new();
+/// tessellator.tessellate(
+///     &path,
+///     &FillOptions::default(),
+///     &mut BuffersBuilder::new(&mut buffers, |vertex: LoopBlinnVertex| vertex),
+/// ).unwrap();
+///
+/// // One interior triangle and one curve triangle.
+/// assert_eq!(buffers.indices.len(), 6);
+/// ```
+pub struct LoopBlinnTessellator {
+    fill: FillTessellator,
+    edges: Vec<Edge>,
+    curves: Vec<Curve>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    from: Point,
+    ctrl: Option<Point>,
+    to: Point,
+    // Whether this edge is the first one of its sub-path.
+    first: bool,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Curve {
+    curve: QuadraticBezierSegment<f64>,
+    sign: f64,
+}
+
+impl Default for LoopBlinnTessellator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoopBlinnTessellator {
+    /// Constructor.
+    pub fn new() -> Self {
+        LoopBlinnTessellator {
+            fill: FillTessellator::new(),
+            edges: Vec::new(),
+            curves: Vec::new(),
+        }
+    }
+
+    /// Compute the tessellation from a path iterator.
+    ///
+    /// The interior and curve triangles are produced in the same geometry.
+    pub fn tessellate(
+        &mut self,
+        path: impl IntoIterator<Item = PathEvent>,
+        options: &FillOptions,
+        output: &mut dyn LoopBlinnGeometryBuilder,
+    ) -> TessellationResult {
+        self.edges.clear();
+        self.curves.clear();
+
+        let mut first_edge = true;
+        for evt in path.into_iter().cubics_to_quadratics(options.tolerance) {
+            match evt {
+                PathEvent::Begin { .. } => {
+                    first_edge = true;
+                }
+                PathEvent::Line { from, to } => {
+                    self.add_edge(from, None, to, &mut first_edge);
+                }
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    self.add_edge(from, Some(ctrl), to, &mut first_edge);
+                }
+                PathEvent::End { last, first, .. } => {
+                    // Sub-paths are implicitly closed when filling.
+                    if last != first {
+                        self.add_edge(last, None, first, &mut first_edge);
+                    }
+                }
+                PathEvent::Cubic { .. } => {
+                    unreachable!();
+                }
+            }
+        }
+
+        let interior = self.build_interior(options.fill_rule);
+
+        let mut builder = InteriorBuilder { output };
+        self.fill.tessellate(&interior, options, &mut builder)?;
+        let output = builder.output;
+
+        for curve in &self.curves {
+            if let Err(e) = add_curve_triangle(curve, output) {
+                output.abort_geometry();
+                return Err(e.into());
+            }
+        }
+
+        output.end_geometry();
+
+        Ok(())
+    }
+
+    fn add_edge(&mut self, from: Point, ctrl: Option<Point>, to: Point, first: &mut bool) {
+        if from == to && ctrl.is_none() {
+            return;
+        }
+
+        self.edges.push(Edge {
+            from,
+            ctrl,
+            to,
+            first: *first,
+        });
+        *first = false;
+    }
+
+    // Build a path where quadratic bézier curves are replaced by their chord if
+    // they bulge out of the shape, or by the two sides of their control polygon
+    // if they bulge inward, and register the corresponding curve triangles.
+    fn build_interior(&mut self, fill_rule: FillRule) -> Path {
+        let mut builder = Path::builder();
+        let mut open = false;
+        for edge in &self.edges {
+            if edge.first {
+                if open {
+                    builder.end(true);
+                }
+                builder.begin(edge.from);
+                open = true;
+            }
+
+            let ctrl = match edge.ctrl {
+                Some(ctrl) if (edge.to - edge.from).cross(ctrl - edge.from) != 0.0 => ctrl,
+                _ => {
+                    builder.line_to(edge.to);
+                    continue;
+                }
+            };
+
+            let curve = QuadraticBezierSegment {
+                from: edge.from,
+                ctrl,
+                to: edge.to,
+            };
+
+            // A point between the chord and the curve.
+            let chord_midpoint = edge.from.lerp(edge.to, 0.5);
+            let probe = chord_midpoint.lerp(curve.sample(0.5), 0.5);
+
+            if fill_rule.is_in(chord_winding_number(probe, &self.edges)) {
+                // The curve bulges inward, the interior must not contain the curve
+                // triangle and the triangle is filled on the side of the control point.
+                builder.line_to(ctrl);
+                builder.line_to(edge.to);
+                self.curves.push(Curve { curve, sign: -1.0 });
+            } else {
+                builder.line_to(edge.to);
+                self.curves.push(Curve { curve, sign: 1.0 });
+            }
+        }
+
+        if open {
+            builder.end(true);
+        }
+
+        builder.build()
+    }
+}
+
+// Winding number of a point relative to the polygon formed by the chords of the edges.
+fn chord_winding_number(p: Point, edges: &[Edge]) -> i16 {
+    let mut winding = 0;
+    for edge in edges {
+        let (a, b) = (edge.from, edge.to);
+        let side = (b - a).cross(p - a);
+        if a.y <= p.y {
+            if b.y > p.y && side > 0.0 {
+                winding += 1;
+            }
+        } else if b.y <= p.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+
+    winding
+}
+
+fn add_curve_triangle(
+    curve: &Curve,
+    output: &mut dyn LoopBlinnGeometryBuilder,
+) -> Result<(), GeometryBuilderError> {
+    let sign = curve.sign;
+    let a = output.add_loop_blinn_vertex(LoopBlinnVertex {
+        position: curve.curve.from,
+        u: 0.0,
+        v: 0.0,
+        sign,
+    })?;
+    let b = output.add_loop_blinn_vertex(LoopBlinnVertex {
+        position: curve.curve.ctrl,
+        u: 0.5,
+        v: 0.0,
+        sign,
+    })?;
+    let c = output.add_loop_blinn_vertex(LoopBlinnVertex {
+        position: curve.curve.to,
+        u: 1.0,
+        v: 1.0,
+        sign,
+    })?;
+    output.add_triangle(a, b, c);
+
+    Ok(())
+}
+
+// Forwards the output of the fill tessellator, leaving the geometry open so that
+// the curve triangles can be added to it.
+struct InteriorBuilder<'l> {
+    output: &'l mut dyn LoopBlinnGeometryBuilder,
+}
+
+impl<'l> GeometryBuilder for InteriorBuilder<'l> {
+    fn begin_geometry(&mut self) {
+        self.output.begin_geometry();
+    }
+
+    fn end_geometry(&mut self) {}
+
+    fn add_triangle(&mut self, a: VertexId, b: VertexId, c: VertexId) {
+        self.output.add_triangle(a, b, c);
+    }
+
+    fn abort_geometry(&mut self) {
+        self.output.abort_geometry();
+    }
+}
+
+impl<'l> FillGeometryBuilder for InteriorBuilder<'l> {
+    fn add_fill_vertex(&mut self, vertex: FillVertex) -> Result<VertexId, GeometryBuilderError> {
+        self.output
+            .add_loop_blinn_vertex(LoopBlinnVertex::interior(vertex.position()))
+    }
+}
+
+#[cfg(test)]
+use crate::geometry_builder::{BuffersBuilder, VertexBuffers};
+
+#[cfg(test)]
+fn tessellate_loop_blinn(path: &Path) -> VertexBuffers<LoopBlinnVertex, u16> {
+    let mut buffers: VertexBuffers<LoopBlinnVertex, u16> = VertexBuffers::new();
+    LoopBlinnTessellator::new()
+        .tessellate(
+            path,
+            &FillOptions::default(),
+            &mut BuffersBuilder::new(&mut buffers, |vertex: LoopBlinnVertex| vertex),
+        )
+        .unwrap();
+
+    buffers
+}
+
+#[test]
+fn loop_blinn_convex_curve() {
+    // A square with its top side bulging outward.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.quadratic_bezier_to(point(5.0, 20.0), point(0.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let buffers = tessellate_loop_blinn(&path);
+
+    let curve_vertices: Vec<&LoopBlinnVertex> = buffers
+        .vertices
+        .iter()
+        .filter(|v| **v != LoopBlinnVertex::interior(v.position))
+        .collect();
+    assert_eq!(curve_vertices.len(), 3);
+    assert!(curve_vertices.iter().all(|v| v.sign == 1.0));
+    assert!(curve_vertices
+        .iter()
+        .any(|v| v.position == point(5.0, 20.0) && v.u == 0.5 && v.v == 0.0));
+
+    // Two interior triangles for the square and one curve triangle.
+    assert_eq!(buffers.indices.len(), 9);
+}
+
+#[test]
+fn loop_blinn_concave_curve() {
+    // A square with its top side bulging inward.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.quadratic_bezier_to(point(5.0, 5.0), point(0.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let buffers = tessellate_loop_blinn(&path);
+
+    let curve_vertices: Vec<&LoopBlinnVertex> = buffers
+        .vertices
+        .iter()
+        .filter(|v| **v != LoopBlinnVertex::interior(v.position))
+        .collect();
+    assert_eq!(curve_vertices.len(), 3);
+    assert!(curve_vertices.iter().all(|v| v.sign == -1.0));
+
+    // The interior goes through the control point: three triangles for the
+    // pentagon and one curve triangle.
+    assert_eq!(buffers.indices.len(), 12);
+    assert!(buffers
+        .vertices
+        .contains(&LoopBlinnVertex::interior(point(5.0, 5.0))));
+}
+
+#[test]
+fn loop_blinn_cubic() {
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.cubic_bezier_to(point(20.0, 0.0), point(20.0, 10.0), point(10.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let buffers = tessellate_loop_blinn(&path);
+
+    let num_curve_vertices = buffers
+        .vertices
+        .iter()
+        .filter(|v| **v != LoopBlinnVertex::interior(v.position))
+        .count();
+    assert!(num_curve_vertices > 3);
+    assert_eq!(num_curve_vertices % 3, 0);
+}
+
+#[test]
+fn loop_blinn_empty_path() {
+    let buffers = tessellate_loop_blinn(&Path::new());
+    assert!(buffers.vertices.is_empty());
+    assert!(buffers.indices.is_empty());
+}
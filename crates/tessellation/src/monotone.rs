@@ -1,4 +1,5 @@
 use crate::fill::{is_after, Side};
+use crate::geom::predicates::orient2d;
 use crate::math::{point, Point};
 use crate::{FillGeometryBuilder, VertexId};
 
@@ -66,7 +67,7 @@ impl BasicMonotoneTessellator {
                 let mut a = self.stack[i];
                 let mut b = self.stack[i + 1];
 
-                let winding = (a.pos - b.pos).cross(current.pos - b.pos) >= 0.0;
+                let winding = orient2d(b.pos, a.pos, current.pos) >= 0.0;
 
                 if !winding {
                     std::mem::swap(&mut a, &mut b);
@@ -86,8 +87,9 @@ impl BasicMonotoneTessellator {
                     std::mem::swap(&mut a, &mut b);
                 }
 
-                let cross = (current.pos - b.pos).cross(a.pos - b.pos);
-                if cross >= 0.0 {
+                // With nearly aligned vertices the rounded cross product can have
+                // the wrong sign, which would produce a flipped triangle.
+                if orient2d(b.pos, current.pos, a.pos) >= 0.0 {
                     self.push_triangle(&b, &a, &current);
                     last_popped = self.stack.pop();
                 } else {
@@ -184,6 +186,36 @@ fn test_monotone_tess() {
     println!(" ------------ ");
 }
 
+#[test]
+fn test_monotone_tess_nearly_aligned() {
+    // The second vertex is very slightly to the right of the line between its
+    // neighbors, but the rounded cross product says otherwise.
+    let points = [
+        point(0.0, 0.0),
+        point(10.953457471565638, 5.705026817223316),
+        point(12.615575780013051, 6.570728770027348),
+        point(30.088636139441324, 15.671442237740441),
+        point(-1.0, 16.671442237740443),
+    ];
+
+    let mut tess = BasicMonotoneTessellator::new();
+    tess.begin(points[0], VertexId(0));
+    for (i, p) in points.iter().enumerate().take(4).skip(1) {
+        tess.vertex(*p, VertexId(i as u32), Side::Right);
+    }
+    tess.end(points[4], VertexId(4));
+
+    assert_eq!(tess.triangles.len(), 3);
+    for &(a, b, c) in &tess.triangles {
+        let (a, b, c) = (
+            points[a.0 as usize],
+            points[b.0 as usize],
+            points[c.0 as usize],
+        );
+        assert!(orient2d(b, a, c) >= 0.0);
+    }
+}
+
 struct SideEvents {
     // We decide whether we have to flush a convex vertex chain based on
     // whether the two sides are far apart. reference_point.x contains the
@@ -0,0 +1,298 @@
+//! Tessellation on background threads.
+
+use crate::geometry_builder::{BuffersBuilder, MaxIndex, VertexBuffers};
+use crate::path::Path;
+use crate::{FillOptions, FillTessellator, FillVertexConstructor, TessellationError, VertexId};
+
+use std::ops::Add;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// The result of a tessellation job.
+pub type JobResult<OutputVertex, OutputIndex> =
+    Result<VertexBuffers<OutputVertex, OutputIndex>, TessellationError>;
+
+type BoxedCallback<OutputVertex, OutputIndex> =
+    Box<dyn FnOnce(JobResult<OutputVertex, OutputIndex>) + Send>;
+
+struct Job<OutputVertex, OutputIndex> {
+    path: Path,
+    options: FillOptions,
+    cancelled: Arc<AtomicBool>,
+    callback: BoxedCallback<OutputVertex, OutputIndex>,
+}
+
+/// Fills paths on a pool of worker threads.
+///
+/// Each worker owns a `FillTessellator` that is reused from one job to the next.
+/// Paths are cheap to clone, so the same path can be sent to the service while the
+/// rest of the application keeps using it.
+///
+/// Dropping the service waits for the queued jobs to be processed.
+///
+/// ## Example
+///
+/// ```
+/// use lyon_tessellation::{FillOptions, FillVertex, TessellationService};
+/// use lyon_tessellation::math::{point, Point};
+/// use lyon_tessellation::path::Path;
+///
+/// let mut builder = Path::builder();
+/// builder.begin(point(0.0, 0.0));
+/// builder.line_to(point(1.0, 0.0));
+/// builder.line_to(point(1.0, 1.0));
+/// builder.end(true);
+/// let path = builder.build();
+///
+/// let service: TessellationService<Point, u16> =
+///     TessellationService::new(2, |vertex: FillVertex| vertex.position());
+///
+/// let job = service.fill(path, FillOptions::default());
+/// let buffers = job.wait().unwrap().unwrap();
+///
+/// assert_eq!(buffers.indices.len(), 3);
+/// ```
+pub struct TessellationService<OutputVertex, OutputIndex> {
+    sender: Option<Sender<Job<OutputVertex, OutputIndex>>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl<OutputVertex, OutputIndex> TessellationService<OutputVertex, OutputIndex>
+where
+    OutputVertex: Send + 'static,
+    OutputIndex: Add + From<VertexId> + MaxIndex + Send + 'static,
+{
+    /// Starts `num_threads` worker threads (at least one) producing vertices with the provided
+    /// vertex constructor.
+    pub fn new<Ctor>(num_threads: usize, vertex_constructor: Ctor) -> Self
+    where
+        Ctor: FillVertexConstructor<OutputVertex> + Clone + Send + 'static,
+    {
+        let (sender, receiver) = channel::<Job<OutputVertex, OutputIndex>>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..num_threads.max(1))
+            .map(|_| {
+                let receiver = receiver.clone();
+                let vertex_constructor = vertex_constructor.clone();
+                std::thread::spawn(move || {
+                    let mut tessellator = FillTessellator::new();
+                    loop {
+                        // Release the lock before processing the job.
+                        let job = match receiver.lock() {
+                            Ok(receiver) => receiver.recv(),
+                            Err(_) => return,
+                        };
+                        let job = match job {
+                            Ok(job) => job,
+                            // The service was dropped.
+                            Err(_) => return,
+                        };
+
+                        if job.cancelled.load(Ordering::Relaxed) {
+                            continue;
+                        }
+
+                        let mut buffers = VertexBuffers::new();
+                        let result = tessellator.tessellate_path(
+                            &job.path,
+                            &job.options,
+                            &mut BuffersBuilder::new(&mut buffers, vertex_constructor.clone()),
+                        );
+
+                        (job.callback)(result.map(|_| buffers));
+                    }
+                })
+            })
+            .collect();
+
+        TessellationService {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    /// Queues a fill job, the result is retrieved via the returned handle.
+    pub fn fill(&self, path: Path, options: FillOptions) -> JobHandle<OutputVertex, OutputIndex> {
+        let (sender, receiver) = channel();
+        let cancelled = self.submit(path, options, move |result| {
+            // The receiver may have been dropped in the mean time.
+            let _ = sender.send(result);
+        });
+
+        JobHandle {
+            receiver,
+            cancelled,
+        }
+    }
+
+    /// Queues a fill job, the callback is invoked on the worker thread with the result.
+    ///
+    /// Unlike the jobs queued with `fill`, these jobs can't be cancelled.
+    pub fn fill_with_callback<Callback>(&self, path: Path, options: FillOptions, callback: Callback)
+    where
+        Callback: FnOnce(JobResult<OutputVertex, OutputIndex>) + Send + 'static,
+    {
+        self.submit(path, options, callback);
+    }
+
+    fn submit<Callback>(
+        &self,
+        path: Path,
+        options: FillOptions,
+        callback: Callback,
+    ) -> Arc<AtomicBool>
+    where
+        Callback: FnOnce(JobResult<OutputVertex, OutputIndex>) + Send + 'static,
+    {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let job = Job {
+            path,
+            options,
+            cancelled: cancelled.clone(),
+            callback: Box::new(callback),
+        };
+
+        // Sending can only fail if all workers panicked.
+        let _ = self.sender.as_ref().unwrap().send(job);
+
+        cancelled
+    }
+}
+
+impl<OutputVertex, OutputIndex> Drop for TessellationService<OutputVertex, OutputIndex> {
+    fn drop(&mut self) {
+        // Closing the channel stops the workers once the queue is empty.
+        self.sender = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// The pending result of a job submitted to a `TessellationService`.
+///
+/// Dropping the handle before the job starts cancels it, which avoids tessellating
+/// paths that are already stale when the queue is long.
+pub struct JobHandle<OutputVertex, OutputIndex> {
+    receiver: Receiver<JobResult<OutputVertex, OutputIndex>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<OutputVertex, OutputIndex> JobHandle<OutputVertex, OutputIndex> {
+    /// Blocks until the job is done.
+    ///
+    /// Returns `None` if the job could not complete because the worker panicked.
+    pub fn wait(self) -> Option<JobResult<OutputVertex, OutputIndex>> {
+        self.receiver.recv().ok()
+    }
+
+    /// Returns the result if the job is done, or the handle otherwise.
+    pub fn try_get(self) -> Result<Option<JobResult<OutputVertex, OutputIndex>>, Self> {
+        match self.receiver.try_recv() {
+            Ok(result) => Ok(Some(result)),
+            Err(TryRecvError::Disconnected) => Ok(None),
+            Err(TryRecvError::Empty) => Err(self),
+        }
+    }
+
+    /// Cancels the job if it has not started yet.
+    pub fn cancel(self) {}
+}
+
+impl<OutputVertex, OutputIndex> Drop for JobHandle<OutputVertex, OutputIndex> {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+use crate::math::{point, Point};
+#[cfg(test)]
+use crate::FillVertex;
+
+#[test]
+fn tessellation_service() {
+    let mut paths = Vec::new();
+    for i in 0..20 {
+        let mut builder = Path::builder();
+        builder.add_circle(
+            point(0.0, 0.0),
+            1.0 + i as f64,
+            crate::path::Winding::Positive,
+        );
+        paths.push(builder.build());
+    }
+
+    let service: TessellationService<Point, u32> =
+        TessellationService::new(3, |vertex: FillVertex| vertex.position());
+
+    let jobs: Vec<_> = paths
+        .iter()
+        .map(|path| service.fill(path.clone(), FillOptions::tolerance(0.01)))
+        .collect();
+
+    let (sender, receiver) = channel();
+    service.fill_with_callback(
+        paths[5].clone(),
+        FillOptions::tolerance(0.01),
+        move |result| {
+            sender.send(result.unwrap().indices.len()).unwrap();
+        },
+    );
+
+    let mut tessellator = FillTessellator::new();
+    for (path, job) in paths.iter().zip(jobs) {
+        let mut expected: VertexBuffers<Point, u32> = VertexBuffers::new();
+        tessellator
+            .tessellate_path(
+                path,
+                &FillOptions::tolerance(0.01),
+                &mut BuffersBuilder::new(&mut expected, |vertex: FillVertex| vertex.position()),
+            )
+            .unwrap();
+
+        let buffers = job.wait().unwrap().unwrap();
+        assert_eq!(buffers.vertices, expected.vertices);
+        assert_eq!(buffers.indices, expected.indices);
+    }
+
+    assert!(receiver.recv().unwrap() > 0);
+}
+
+#[test]
+fn cancelled_jobs() {
+    use std::sync::atomic::AtomicUsize;
+
+    let mut builder = Path::builder();
+    builder.add_circle(point(0.0, 0.0), 1.0, crate::path::Winding::Positive);
+    let path = builder.build();
+
+    // Count the vertices produced by the worker.
+    let num_vertices = Arc::new(AtomicUsize::new(0));
+    let counter = num_vertices.clone();
+    let service: TessellationService<Point, u32> =
+        TessellationService::new(1, move |vertex: FillVertex| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            vertex.position()
+        });
+
+    // Keep the worker busy until the second job is cancelled.
+    let (sender, receiver) = channel::<()>();
+    let (len_sender, len_receiver) = channel();
+    service.fill_with_callback(path.clone(), FillOptions::default(), move |result| {
+        len_sender.send(result.unwrap().vertices.len()).unwrap();
+        receiver.recv().unwrap();
+    });
+
+    let handle = service.fill(path, FillOptions::default());
+    handle.cancel();
+    sender.send(()).unwrap();
+    drop(service);
+
+    let len = len_receiver.recv().unwrap();
+    assert!(len > 0);
+    assert_eq!(num_vertices.load(Ordering::SeqCst), len);
+}
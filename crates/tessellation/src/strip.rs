@@ -0,0 +1,423 @@
+//! Reorder the output of the tessellators for better GPU throughput.
+
+use crate::triangle_adjacency::TriangleAdjacency;
+
+/// Converts a triangle list into a single triangle strip.
+///
+/// Strips are built by greedily walking from each triangle to an adjacent one
+/// and are joined with degenerate triangles. The winding order of the triangles
+/// is preserved, assuming the GPU alternates the winding of the triangles of a strip
+/// the usual way (the first triangle is `(s[0], s[1], s[2])`, the second one
+/// `(s[2], s[1], s[3])`, etc.).
+///
+/// The triangles are expected to [share their vertices](index.html#shared-vertices),
+/// otherwise the strip is mostly made of degenerate triangles.
+pub fn stripify<OutputIndex>(indices: &[OutputIndex]) -> Vec<OutputIndex>
+where
+    OutputIndex: Copy + Into<u32>,
+{
+    let adjacency = TriangleAdjacency::new(indices);
+    let num_triangles = adjacency.triangles.len();
+    let triangle = |idx: usize| [indices[idx * 3], indices[idx * 3 + 1], indices[idx * 3 + 2]];
+
+    let mut visited = vec![false; num_triangles];
+    let mut num_visited = 0;
+
+    // The neighbor of triangle `tri` across its edge `(a, b)`, with the same winding.
+    // Returns the neighbor and its vertex that is not on the edge.
+    let neighbor = |tri: usize, a: OutputIndex, b: OutputIndex, visited: &[bool]| {
+        let idx = adjacency.opposite(tri, a.into(), b.into())?;
+        if visited[idx] {
+            return None;
+        }
+        let third = triangle(idx)
+            .iter()
+            .cloned()
+            .find(|v| (*v).into() != a.into() && (*v).into() != b.into())?;
+        Some((idx, third))
+    };
+
+    // The triangles that have each triangle as a neighbor, and the number of
+    // unvisited neighbors of each triangle.
+    let mut neighbor_of = vec![Vec::new(); num_triangles];
+    let mut num_unvisited_neighbors = vec![0; num_triangles];
+    for (idx, count) in num_unvisited_neighbors.iter_mut().enumerate() {
+        let tri = triangle(idx);
+        for k in 0..3 {
+            if let Some((n, _)) = neighbor(idx, tri[k], tri[(k + 1) % 3], &visited) {
+                neighbor_of[n].push(idx);
+                *count += 1;
+            }
+        }
+    }
+
+    // The triangles by number of unvisited neighbors. Triangles are pushed again
+    // when their number of unvisited neighbors changes, so the entries that are
+    // visited or out of date are skipped.
+    let mut buckets: [Vec<usize>; 4] = Default::default();
+    for idx in (0..num_triangles).rev() {
+        buckets[num_unvisited_neighbors[idx]].push(idx);
+    }
+
+    let mut output: Vec<OutputIndex> = Vec::with_capacity(indices.len());
+    let mut strip: Vec<OutputIndex> = Vec::new();
+    let mut candidate: Vec<OutputIndex> = Vec::new();
+    let mut strip_triangles: Vec<usize> = Vec::new();
+    let mut candidate_triangles: Vec<usize> = Vec::new();
+
+    while num_visited < num_triangles {
+        // Start from the triangle with the fewest unvisited neighbors so that
+        // isolated triangles don't end up in strips of their own.
+        let mut start = None;
+        for (count, bucket) in buckets.iter_mut().enumerate() {
+            while let Some(idx) = bucket.pop() {
+                if !visited[idx] && num_unvisited_neighbors[idx] == count {
+                    start = Some(idx);
+                    break;
+                }
+            }
+            if start.is_some() {
+                break;
+            }
+        }
+        let start = start.unwrap();
+
+        // Try to start the strip with each edge of the first triangle and keep the
+        // longest.
+        strip.clear();
+        strip_triangles.clear();
+        for rotation in 0..3 {
+            let tri = triangle(start);
+            candidate.clear();
+            candidate_triangles.clear();
+            candidate.extend_from_slice(&[
+                tri[rotation],
+                tri[(rotation + 1) % 3],
+                tri[(rotation + 2) % 3],
+            ]);
+            candidate_triangles.push(start);
+            visited[start] = true;
+
+            loop {
+                let n = candidate.len();
+                // Every other triangle of the strip has its winding reversed.
+                let (a, b) = if n % 2 == 1 {
+                    (candidate[n - 2], candidate[n - 1])
+                } else {
+                    (candidate[n - 1], candidate[n - 2])
+                };
+                let last = *candidate_triangles.last().unwrap();
+                match neighbor(last, a, b, &visited) {
+                    Some((idx, v)) => {
+                        visited[idx] = true;
+                        candidate.push(v);
+                        candidate_triangles.push(idx);
+                    }
+                    None => {
+                        break;
+                    }
+                }
+            }
+
+            for &idx in &candidate_triangles {
+                visited[idx] = false;
+            }
+
+            if candidate.len() > strip.len() {
+                std::mem::swap(&mut strip, &mut candidate);
+                std::mem::swap(&mut strip_triangles, &mut candidate_triangles);
+            }
+        }
+
+        for &idx in &strip_triangles {
+            visited[idx] = true;
+        }
+        for &idx in &strip_triangles {
+            for &n in &neighbor_of[idx] {
+                if !visited[n] {
+                    num_unvisited_neighbors[n] -= 1;
+                    buckets[num_unvisited_neighbors[n]].push(n);
+                }
+            }
+        }
+        num_visited += strip_triangles.len();
+
+        if let Some(&last) = output.last() {
+            // Join the strips with degenerate triangles, making sure the first
+            // triangle of the new strip starts at an even position.
+            output.push(last);
+            output.push(strip[0]);
+            if output.len() % 2 == 1 {
+                output.push(strip[0]);
+            }
+        }
+
+        output.extend_from_slice(&strip);
+    }
+
+    output
+}
+
+/// Reorders the triangles of a triangle list to improve the efficiency of the
+/// post-transform vertex cache of GPUs.
+///
+/// This is an implementation of Tom Forsyth's "Linear-Speed Vertex Cache
+/// Optimisation". The winding order of the triangles is preserved.
+///
+/// `num_vertices` must be larger than the highest index.
+pub fn optimize_vertex_cache<OutputIndex>(indices: &mut [OutputIndex], num_vertices: usize)
+where
+    OutputIndex: Copy + Into<u32>,
+{
+    const CACHE_SIZE: usize = 32;
+    const CACHE_DECAY_POWER: f64 = 1.5;
+    const LAST_TRIANGLE_SCORE: f64 = 0.75;
+    const VALENCE_BOOST_SCALE: f64 = 2.0;
+    const VALENCE_BOOST_POWER: f64 = 0.5;
+
+    let num_triangles = indices.len() / 3;
+    if num_triangles == 0 {
+        return;
+    }
+
+    let vertex = |t: usize, k: usize| indices[t * 3 + k].into() as usize;
+
+    // Triangles using each vertex.
+    let mut offsets = vec![0; num_vertices + 1];
+    for t in 0..num_triangles {
+        for k in 0..3 {
+            offsets[vertex(t, k) + 1] += 1;
+        }
+    }
+    for v in 0..num_vertices {
+        offsets[v + 1] += offsets[v];
+    }
+    let mut vertex_triangles = vec![0; num_triangles * 3];
+    let mut fill = offsets.clone();
+    for t in 0..num_triangles {
+        for k in 0..3 {
+            let v = vertex(t, k);
+            vertex_triangles[fill[v]] = t;
+            fill[v] += 1;
+        }
+    }
+
+    // Number of triangles that are not emitted yet for each vertex.
+    let mut remaining: Vec<u32> = (0..num_vertices)
+        .map(|v| (offsets[v + 1] - offsets[v]) as u32)
+        .collect();
+    let mut cache_position: Vec<Option<usize>> = vec![None; num_vertices];
+
+    let vertex_score = |cache_position: Option<usize>, remaining: u32| -> f64 {
+        if remaining == 0 {
+            return -1.0;
+        }
+        let mut score = match cache_position {
+            Some(p) if p < 3 => LAST_TRIANGLE_SCORE,
+            Some(p) => {
+                let scale = 1.0 / (CACHE_SIZE - 3) as f64;
+                (1.0 - (p - 3) as f64 * scale).powf(CACHE_DECAY_POWER)
+            }
+            None => 0.0,
+        };
+        score += VALENCE_BOOST_SCALE * (remaining as f64).powf(-VALENCE_BOOST_POWER);
+        score
+    };
+
+    let mut scores: Vec<f64> = (0..num_vertices)
+        .map(|v| vertex_score(None, remaining[v]))
+        .collect();
+    let triangle_score =
+        |t: usize, scores: &[f64]| (0..3).map(|k| scores[vertex(t, k)]).sum::<f64>();
+    let mut emitted = vec![false; num_triangles];
+
+    let mut cache: Vec<usize> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut order = Vec::with_capacity(num_triangles);
+    let mut next_unemitted = 0;
+
+    let mut best = Some(0);
+    while let Some(t) = best {
+        emitted[t] = true;
+        order.push(t);
+
+        // Move the vertices of the triangle at the front of the cache.
+        for k in (0..3).rev() {
+            let v = vertex(t, k);
+            if let Some(p) = cache.iter().position(|&c| c == v) {
+                cache.remove(p);
+            }
+            cache.insert(0, v);
+            remaining[v] -= 1;
+        }
+
+        for (p, &v) in cache.iter().enumerate() {
+            cache_position[v] = if p < CACHE_SIZE { Some(p) } else { None };
+            scores[v] = vertex_score(cache_position[v], remaining[v]);
+        }
+        cache.truncate(CACHE_SIZE);
+
+        // Pick the best triangle among the ones using vertices in the cache.
+        best = None;
+        let mut best_score = -1.0;
+        for &v in &cache {
+            for &t in &vertex_triangles[offsets[v]..offsets[v + 1]] {
+                if emitted[t] {
+                    continue;
+                }
+                let score = triangle_score(t, &scores);
+                if score > best_score {
+                    best_score = score;
+                    best = Some(t);
+                }
+            }
+        }
+
+        if best.is_none() {
+            while next_unemitted < num_triangles && emitted[next_unemitted] {
+                next_unemitted += 1;
+            }
+            if next_unemitted < num_triangles {
+                best = Some(next_unemitted);
+            }
+        }
+    }
+
+    let reordered: Vec<OutputIndex> = order
+        .iter()
+        .flat_map(|&t| (0..3).map(move |k| t * 3 + k))
+        .map(|i| indices[i])
+        .collect();
+    indices[..reordered.len()].copy_from_slice(&reordered);
+}
+
+#[cfg(test)]
+use crate::geometry_builder::VertexBuffers;
+#[cfg(test)]
+use crate::math::Point;
+#[cfg(test)]
+use crate::test_utils::fill;
+#[cfg(test)]
+use crate::FillOptions;
+
+#[cfg(test)]
+fn logo_tessellation() -> VertexBuffers<Point, u16> {
+    let mut path = crate::path::Path::builder().with_svg();
+    crate::extra::rust_logo::build_logo_path(&mut path);
+
+    fill(&path.build(), &FillOptions::tolerance(0.05))
+}
+
+// The indices of a grid of n by n quads, with the triangles in a shuffled order.
+#[cfg(test)]
+fn shuffled_grid(n: u16) -> Vec<u16> {
+    let mut triangles = Vec::new();
+    for j in 0..n {
+        for i in 0..n {
+            let a = j * (n + 1) + i;
+            let (b, c, d) = (a + 1, a + n + 2, a + n + 1);
+            triangles.push([a, b, c]);
+            triangles.push([a, c, d]);
+        }
+    }
+
+    let num_triangles = triangles.len();
+    (0..num_triangles)
+        .flat_map(|i| triangles[(i * 7919) % num_triangles])
+        .collect()
+}
+
+// Returns the triangles, rotated so that the smallest index is first.
+#[cfg(test)]
+fn sorted_triangles(indices: &[u16]) -> Vec<[u16; 3]> {
+    let mut triangles: Vec<[u16; 3]> = indices
+        .chunks(3)
+        .map(|tri| {
+            let mut tri = [tri[0], tri[1], tri[2]];
+            while tri[0] > tri[1] || tri[0] > tri[2] {
+                tri.rotate_left(1);
+            }
+            tri
+        })
+        .collect();
+    triangles.sort_unstable();
+
+    triangles
+}
+
+// Converts a triangle strip back into a triangle list.
+#[cfg(test)]
+fn triangle_list(strip: &[u16]) -> Vec<u16> {
+    let mut indices = Vec::new();
+    for i in 0..(strip.len().max(2) - 2) {
+        let (a, b, c) = (strip[i], strip[i + 1], strip[i + 2]);
+        if a == b || b == c || a == c {
+            continue;
+        }
+        if i % 2 == 0 {
+            indices.extend_from_slice(&[a, b, c]);
+        } else {
+            indices.extend_from_slice(&[b, a, c]);
+        }
+    }
+
+    indices
+}
+
+// Average number of cache misses per triangle with a FIFO cache.
+#[cfg(test)]
+fn acmr(indices: &[u16]) -> f64 {
+    let mut cache = std::collections::VecDeque::new();
+    let mut misses = 0;
+    for idx in indices {
+        if !cache.contains(idx) {
+            misses += 1;
+            cache.push_back(*idx);
+            if cache.len() > 16 {
+                cache.pop_front();
+            }
+        }
+    }
+
+    misses as f64 / (indices.len() / 3) as f64
+}
+
+#[test]
+fn stripify_triangles() {
+    let logo = logo_tessellation();
+    let strip = stripify(&logo.indices);
+    assert_eq!(
+        sorted_triangles(&triangle_list(&strip)),
+        sorted_triangles(&logo.indices)
+    );
+    assert!(strip.len() < logo.indices.len());
+
+    // A grid is mostly made of long strips.
+    let grid = shuffled_grid(20);
+    let strip = stripify(&grid);
+    assert_eq!(
+        sorted_triangles(&triangle_list(&strip)),
+        sorted_triangles(&grid)
+    );
+    assert!(strip.len() < grid.len() / 2);
+
+    assert!(stripify::<u16>(&[]).is_empty());
+}
+
+#[test]
+fn vertex_cache() {
+    let logo = logo_tessellation();
+    let mut optimized = logo.indices.clone();
+    optimize_vertex_cache(&mut optimized, logo.vertices.len());
+    assert_eq!(
+        sorted_triangles(&optimized),
+        sorted_triangles(&logo.indices)
+    );
+    assert!(acmr(&optimized) <= acmr(&logo.indices));
+
+    let grid = shuffled_grid(20);
+    let mut optimized = grid.clone();
+    optimize_vertex_cache(&mut optimized, 21 * 21);
+    assert_eq!(sorted_triangles(&optimized), sorted_triangles(&grid));
+    assert!(acmr(&grid) > 2.0);
+    assert!(acmr(&optimized) < 0.8);
+}
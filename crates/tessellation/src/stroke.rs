@@ -3,7 +3,7 @@
 
 use crate::geom::arrayvec::ArrayVec;
 use crate::geom::utils::tangent;
-use crate::geom::{CubicBezierSegment, Line, LineSegment, QuadraticBezierSegment};
+use crate::geom::{Arc, CubicBezierSegment, Line, LineSegment, QuadraticBezierSegment};
 use crate::math::*;
 use crate::math_utils::compute_normal;
 use crate::path::builder::{Build, NoAttributes, PathBuilder};
@@ -392,6 +392,54 @@ impl<'l> StrokeBuilder<'l> {
         self.builder.options.miter_limit = limit;
     }
 
+    /// Adds a circular arc from the current position around `center`.
+    ///
+    /// Unlike arcs approximated with bézier curves (for example with
+    /// `SvgPathBuilder::arc`), the arc is flattened based on the radius of its outer
+    /// offset (see `Arc::offset`), so both sides of the stroke are within the
+    /// tolerance of the exact offset arcs.
+    pub fn circular_arc_to(
+        &mut self,
+        center: Point,
+        sweep_angle: Angle,
+        attributes: Attributes,
+    ) -> EndpointId {
+        self.validator.edge();
+        let (from, from_id, start_width) = self.prev;
+        let to_id = self.attrib_store.add(attributes);
+
+        let radius = (from - center).length();
+        let arc = Arc {
+            center,
+            radii: vector(radius, radius),
+            start_angle: (from - center).angle_from_x_axis(),
+            sweep_angle,
+            x_rotation: Angle::zero(),
+        };
+        let to = arc.to();
+
+        if let Some(attrib_index) = self.builder.options.variable_line_width {
+            let end_width = self.builder.options.line_width * attributes[attrib_index];
+            self.builder.circular_arc_to(
+                &arc,
+                from_id,
+                to_id,
+                start_width,
+                end_width,
+                self.attrib_store,
+            );
+
+            self.prev = (to, to_id, end_width);
+        } else {
+            self.builder
+                .circular_arc_to_fw(&arc, from_id, to_id, self.attrib_store);
+
+            self.prev = (to, to_id, self.builder.options.line_width);
+        }
+
+        to_id
+    }
+
     fn get_width(&self, attributes: Attributes) -> f64 {
         if let Some(idx) = self.builder.options.variable_line_width {
             self.builder.options.line_width * attributes[idx]
@@ -1026,6 +1074,45 @@ impl<'l> StrokeBuilderImpl<'l> {
         );
     }
 
+    pub(crate) fn circular_arc_to(
+        &mut self,
+        arc: &Arc<f64>,
+        from_id: EndpointId,
+        to_id: EndpointId,
+        start_width: f64,
+        end_width: f64,
+        attributes: &dyn AttributeStore,
+    ) {
+        flatten_circular_arc(
+            arc,
+            f64::max(start_width, end_width) * 0.5,
+            self.options.tolerance,
+            &mut |position, t, is_flattening_step| {
+                let src = if t == 1.0 {
+                    VertexSource::Endpoint { id: to_id }
+                } else {
+                    VertexSource::Edge {
+                        from: from_id,
+                        to: to_id,
+                        t,
+                    }
+                };
+
+                self.step(
+                    EndpointData {
+                        position,
+                        half_width: (start_width * (1.0 - t) + end_width * t) * 0.5,
+                        line_join: self.options.line_join,
+                        src,
+                        is_flattening_step,
+                        ..Default::default()
+                    },
+                    attributes,
+                );
+            },
+        );
+    }
+
     pub(crate) fn cubic_bezier_to(
         &mut self,
         curve: &CubicBezierSegment<f64>,
@@ -1137,6 +1224,44 @@ impl<'l> StrokeBuilderImpl<'l> {
         );
     }
 
+    pub(crate) fn circular_arc_to_fw(
+        &mut self,
+        arc: &Arc<f64>,
+        from_id: EndpointId,
+        to_id: EndpointId,
+        attributes: &dyn AttributeStore,
+    ) {
+        let half_width = self.options.line_width * 0.5;
+        flatten_circular_arc(
+            arc,
+            half_width,
+            self.options.tolerance,
+            &mut |position, t, is_flattening_step| {
+                let src = if t == 1.0 {
+                    VertexSource::Endpoint { id: to_id }
+                } else {
+                    VertexSource::Edge {
+                        from: from_id,
+                        to: to_id,
+                        t,
+                    }
+                };
+
+                self.fixed_width_step(
+                    EndpointData {
+                        position,
+                        half_width,
+                        line_join: self.options.line_join,
+                        src,
+                        is_flattening_step,
+                        ..Default::default()
+                    },
+                    attributes,
+                );
+            },
+        );
+    }
+
     pub(crate) fn cubic_bezier_to_fw(
         &mut self,
         curve: &CubicBezierSegment<f64>,
@@ -2711,6 +2836,24 @@ pub(crate) fn circle_flattening_step(radius: f64, mut tolerance: f64) -> f64 {
     2.0 * ((radius - tolerance) / radius).acos()
 }
 
+// Flattens a circular arc with segments of the same angle, chosen so that the offset
+// arc at `half_width` (the outer side of the stroke) is within the tolerance.
+fn flatten_circular_arc<F>(arc: &Arc<f64>, half_width: f64, tolerance: f64, cb: &mut F)
+where
+    F: FnMut(Point, f64, bool),
+{
+    let outer_radius = arc
+        .offset(half_width)
+        .map_or(arc.radii.x, |outer| outer.radii.x);
+    let step = circle_flattening_step(outer_radius, tolerance);
+    let num_segments = f64::max((arc.sweep_angle.radians.abs() / step).ceil(), 1.0) as u32;
+    for i in 1..num_segments {
+        let t = i as f64 / num_segments as f64;
+        cb(arc.sample(t), t, true);
+    }
+    cb(arc.to(), 1.0, false);
+}
+
 fn flatten_quad<F>(curve: &QuadraticBezierSegment<f64>, tolerance: f64, cb: &mut F)
 where
     F: FnMut(Point, f64, bool),
@@ -3305,3 +3448,52 @@ fn issue_821() {
         .tessellate_path(&path, &options, &mut builder)
         .unwrap();
 }
+
+#[test]
+fn stroke_circular_arc() {
+    let mut tess = StrokeTessellator::new();
+    let options = StrokeOptions::tolerance(0.01).with_line_width(4.0);
+    let mut output: VertexBuffers<Point, u16> = VertexBuffers::new();
+    {
+        let mut output = simple_builder(&mut output);
+        let mut builder = tess.builder_with_attributes(0, &options, &mut output);
+        builder.begin(point(10.0, 0.0), &[]);
+        let end = builder.circular_arc_to(point(0.0, 0.0), Angle::radians(PI), &[]);
+        builder.line_to(point(-10.0, -5.0), &[]);
+        builder.end(false);
+        builder.build().unwrap();
+        assert_eq!(end, EndpointId(1));
+    }
+
+    // The vertices of the arc are on the inner and outer offset arcs.
+    let mut num_flattening_steps = 0;
+    for p in &output.vertices {
+        if p.y < 0.0 {
+            continue;
+        }
+        if p.y > 0.1 {
+            num_flattening_steps += 1;
+        }
+        let d = p.to_vector().length();
+        assert!(
+            (d - 8.0).abs() <= 0.01 || (d - 12.0).abs() <= 0.01,
+            "{:?}",
+            p
+        );
+    }
+    // The segments are chosen from the outer side, with a radius of 12.
+    let num_segments = (PI / circle_flattening_step(12.0, 0.01)).ceil() as usize;
+    assert_eq!(num_flattening_steps, (num_segments - 1) * 2);
+
+    let options = options.with_variable_line_width(0);
+    let mut output: VertexBuffers<Point, u16> = VertexBuffers::new();
+    {
+        let mut output = simple_builder(&mut output);
+        let mut builder = tess.builder_with_attributes(1, &options, &mut output);
+        builder.begin(point(10.0, 0.0), &[1.0]);
+        builder.circular_arc_to(point(0.0, 0.0), Angle::radians(PI), &[2.0]);
+        builder.end(false);
+        builder.build().unwrap();
+    }
+    assert!(!output.indices.is_empty());
+}
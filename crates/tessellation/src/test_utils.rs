@@ -0,0 +1,59 @@
+//! Helpers shared by the tests of the tessellators and of the algorithms working on
+//! their output.
+
+use crate::geometry_builder::{simple_builder, VertexBuffers};
+use crate::math::{point, Point};
+use crate::path::path::Builder;
+use crate::path::Path;
+use crate::{FillOptions, FillTessellator};
+
+/// Tessellates the fill of a path into a simple vertex buffer.
+pub(crate) fn fill(path: &Path, options: &FillOptions) -> VertexBuffers<Point, u16> {
+    let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate_path(path, options, &mut simple_builder(&mut buffers))
+        .unwrap();
+
+    buffers
+}
+
+/// Tessellates the fill of a path with additional vertices into a simple vertex buffer.
+pub(crate) fn fill_with_steiner_points(
+    path: &Path,
+    steiner_points: &[Point],
+    options: &FillOptions,
+) -> VertexBuffers<Point, u16> {
+    let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate_with_steiner_points(
+            path,
+            steiner_points,
+            options,
+            &mut simple_builder(&mut buffers),
+        )
+        .unwrap();
+
+    buffers
+}
+
+/// The area covered by the triangles of a tessellation, counting overlaps twice.
+pub(crate) fn area<V>(buffers: &VertexBuffers<V, u16>, position: impl Fn(&V) -> Point) -> f64 {
+    buffers
+        .indices
+        .chunks(3)
+        .map(|tri| {
+            let [a, b, c] =
+                [tri[0], tri[1], tri[2]].map(|v| position(&buffers.vertices[v as usize]));
+            (b - a).cross(c - a).abs() * 0.5
+        })
+        .sum()
+}
+
+/// Adds an axis-aligned rectangle to a path.
+pub(crate) fn rectangle(builder: &mut Builder, min: Point, max: Point) {
+    builder.begin(min);
+    builder.line_to(point(max.x, min.y));
+    builder.line_to(max);
+    builder.line_to(point(min.x, max.y));
+    builder.close();
+}
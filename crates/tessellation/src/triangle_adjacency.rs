@@ -0,0 +1,68 @@
+//! Adjacency of the triangles of a tessellation.
+//!
+//! Two triangles are adjacent if they share the two vertices of an edge. See the
+//! [shared vertices](../index.html#shared-vertices) section of the crate documentation.
+
+use std::collections::HashMap;
+
+/// The triangles of an index buffer and the triangles sharing each of their edges.
+pub(crate) struct TriangleAdjacency {
+    pub triangles: Vec<[u32; 3]>,
+    // The triangles sharing each edge, in the order of the triangles.
+    edges: HashMap<(u32, u32), Vec<usize>>,
+}
+
+impl TriangleAdjacency {
+    pub fn new<OutputIndex: Copy + Into<u32>>(indices: &[OutputIndex]) -> Self {
+        TriangleAdjacency::from_triangles(triangles(indices))
+    }
+
+    pub fn from_triangles(triangles: Vec<[u32; 3]>) -> Self {
+        let mut edges: HashMap<(u32, u32), Vec<usize>> =
+            HashMap::with_capacity(triangles.len() * 2);
+        for (idx, tri) in triangles.iter().enumerate() {
+            for k in 0..3 {
+                edges
+                    .entry(edge_key(tri[k], tri[(k + 1) % 3]))
+                    .or_default()
+                    .push(idx);
+            }
+        }
+
+        TriangleAdjacency { triangles, edges }
+    }
+
+    /// The triangles sharing the edge between two vertices, in either direction.
+    pub fn edge_triangles(&self, a: u32, b: u32) -> &[usize] {
+        self.edges
+            .get(&edge_key(a, b))
+            .map_or(&[], |tris| &tris[..])
+    }
+
+    /// Whether the edge between two vertices belongs to a single triangle.
+    pub fn is_boundary(&self, a: u32, b: u32) -> bool {
+        self.edge_triangles(a, b).len() == 1
+    }
+
+    /// The triangle other than `tri` that has the edge going from `b` to `a`, which is
+    /// the neighbor across the edge `(a, b)` of `tri` if both have the same winding.
+    pub fn opposite(&self, tri: usize, a: u32, b: u32) -> Option<usize> {
+        self.edge_triangles(a, b).iter().cloned().find(|&idx| {
+            let t = &self.triangles[idx];
+            idx != tri && (0..3).any(|k| t[k] == b && t[(k + 1) % 3] == a)
+        })
+    }
+}
+
+/// The key of the edge between two vertices, independently of its direction.
+pub(crate) fn edge_key(a: u32, b: u32) -> (u32, u32) {
+    (a.min(b), a.max(b))
+}
+
+/// The triangles of an index buffer.
+pub(crate) fn triangles<OutputIndex: Copy + Into<u32>>(indices: &[OutputIndex]) -> Vec<[u32; 3]> {
+    indices
+        .chunks_exact(3)
+        .map(|tri| [tri[0].into(), tri[1].into(), tri[2].into()])
+        .collect()
+}